@@ -0,0 +1,58 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+/// An anchor point, for positioning an image within a larger or smaller
+/// canvas.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Anchor {
+	TopLeft,
+	Top,
+	TopRight,
+	Left,
+	Center,
+	Right,
+	BottomLeft,
+	Bottom,
+	BottomRight,
+}
+
+impl Default for Anchor {
+	#[inline]
+	fn default() -> Self {
+		Anchor::Center
+	}
+}
+
+impl Anchor {
+	/// Get the offset of an area of the given size within the given canvas.
+	///
+	/// The offset is negative when the area is bigger than the canvas.
+	#[inline]
+	pub fn offset(&self, width: u32, height: u32, canvas_width: u32, canvas_height: u32) -> (i64, i64) {
+		let x = canvas_width as i64 - width as i64;
+		let y = canvas_height as i64 - height as i64;
+
+		match *self {
+			Anchor::TopLeft     => (0, 0),
+			Anchor::Top         => (x / 2, 0),
+			Anchor::TopRight    => (x, 0),
+			Anchor::Left        => (0, y / 2),
+			Anchor::Center      => (x / 2, y / 2),
+			Anchor::Right       => (x, y / 2),
+			Anchor::BottomLeft  => (0, y),
+			Anchor::Bottom      => (x / 2, y),
+			Anchor::BottomRight => (x, y),
+		}
+	}
+}
@@ -19,8 +19,10 @@ use orientation::Orientation;
 use pixel::{self, Pixel};
 use view::{self, View};
 use region::{self, Region};
+use anchor::Anchor;
 use color;
 use iter::pixel::{Iter as Pixels, IterMut as PixelsMut};
+use util::Marker;
 
 /// Buffer for an image.
 ///
@@ -60,7 +62,7 @@ pub struct Buffer<P, C, D>
 	data:   D,
 	stride: usize,
 
-	pixel:   PhantomData<P>,
+	pixel:   Marker<P>,
 	channel: PhantomData<C>,
 }
 
@@ -494,6 +496,100 @@ impl<P, C, D> Buffer<P, C, D>
 	}
 }
 
+impl<P, C, D> Buffer<P, C, D>
+	where P: pixel::Write<C> + pixel::Read<C>,
+	      C: pixel::Channel,
+	      D: Deref<Target = [C]>,
+{
+	/// Crop the given region into a new `Buffer`.
+	///
+	/// # Panics
+	///
+	/// Requires that `x + width <= self.width()` and `y + height <= self.height()`,
+	/// otherwise it will panic.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::Region;
+	/// use picto::color::Rgb;
+	///
+	/// let image   = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	/// let cropped = image.crop(Region::new().x(10).y(10).width(100).height(50));
+	///
+	/// assert_eq!(cropped.width(), 100);
+	/// assert_eq!(cropped.height(), 50);
+	/// ```
+	#[inline]
+	pub fn crop(&self, region: region::Builder) -> Buffer<P, C, Vec<C>> {
+		self.readable(Default::default()).crop(region)
+	}
+
+	/// Pad each side with the given amount of pixels, set to the given pixel.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Rgb;
+	///
+	/// let image  = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	/// let padded = image.pad(10, 20, 10, 20, &Rgb::new(1.0, 1.0, 1.0));
+	///
+	/// assert_eq!(padded.width(), 360);
+	/// assert_eq!(padded.height(), 260);
+	/// ```
+	#[inline]
+	pub fn pad(&self, top: u32, right: u32, bottom: u32, left: u32, fill: &P) -> Buffer<P, C, Vec<C>> {
+		self.readable(Default::default()).pad(top, right, bottom, left, fill)
+	}
+
+	/// Change the canvas to the given dimensions, positioning the current
+	/// content at the given anchor.
+	///
+	/// Any area not covered by the content is set to the given pixel, and any
+	/// content outside of the canvas is cropped.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::{read, Anchor};
+	/// use picto::color::Rgb;
+	///
+	/// let image    = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	/// let extended = image.extend_canvas(320, 320, Anchor::Center, &Rgb::new(0.0, 0.0, 0.0));
+	///
+	/// assert_eq!(extended.width(), 320);
+	/// assert_eq!(extended.height(), 320);
+	/// ```
+	#[inline]
+	pub fn extend_canvas(&self, width: u32, height: u32, anchor: Anchor, fill: &P) -> Buffer<P, C, Vec<C>> {
+		self.readable(Default::default()).extend_canvas(width, height, anchor, fill)
+	}
+
+	/// Tile the content across the given output, starting from its top left
+	/// corner.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::{read, buffer};
+	/// use picto::color::Rgb;
+	///
+	/// let     pattern = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	/// let mut output  = buffer::Rgb::new(1024, 1024);
+	///
+	/// pattern.tile_into(&mut output);
+	/// ```
+	#[inline]
+	pub fn tile_into<'o, O>(&self, output: O)
+		where O: Into<view::Write<'o, P, C>>
+	{
+		self.readable(Default::default()).tile_into(output)
+	}
+}
+
 impl<'a, P, C, D> From<&'a Buffer<P, C, D>> for view::Read<'a, P, C>
 	where P: pixel::Read<C>,
 	      C: pixel::Channel,
@@ -555,6 +651,8 @@ impl<P, C, D> DerefMut for Buffer<P, C, D>
 mod test {
 	use super::*;
 	use color::*;
+	use region::Region;
+	use anchor::Anchor;
 
 	#[test]
 	fn new() {
@@ -615,4 +713,54 @@ mod test {
 		assert_eq!(vec![255, 0, 255, 255],
 			b.into_raw());
 	}
+
+	#[test]
+	fn crop() {
+		let a = Buffer::<Rgb, u8, _>::from_fn(4, 4, |x, y| Rgb::new(x as f32 / 3.0, y as f32 / 3.0, 0.0));
+		let b = a.crop(Region::new().x(1).y(2).width(2).height(2));
+
+		assert_eq!((2, 2), b.dimensions());
+		assert_eq!(a.get(1, 2), b.get(0, 0));
+		assert_eq!(a.get(2, 3), b.get(1, 1));
+	}
+
+	#[test]
+	fn pad() {
+		let a = Buffer::<Rgb, u8, _>::from_pixel(2, 2, &Rgb::new(1.0, 1.0, 1.0));
+		let b = a.pad(1, 2, 3, 4, &Rgb::new(1.0, 0.0, 0.0));
+
+		assert_eq!((8, 6), b.dimensions());
+		assert_eq!(Rgb::new(1.0, 0.0, 0.0), b.get(3, 1));
+		assert_eq!(Rgb::new(1.0, 1.0, 1.0), b.get(4, 1));
+		assert_eq!(Rgb::new(1.0, 1.0, 1.0), b.get(5, 2));
+		assert_eq!(Rgb::new(1.0, 0.0, 0.0), b.get(6, 2));
+		assert_eq!(Rgb::new(1.0, 0.0, 0.0), b.get(5, 3));
+	}
+
+	#[test]
+	fn extend_canvas() {
+		let a = Buffer::<Rgb, u8, _>::from_fn(2, 2, |x, y| Rgb::new(x as f32, y as f32, 1.0));
+		let b = a.extend_canvas(4, 3, Anchor::BottomRight, &Rgb::new(0.0, 0.0, 0.0));
+
+		assert_eq!((4, 3), b.dimensions());
+		assert_eq!(Rgb::new(0.0, 0.0, 0.0), b.get(1, 2));
+		assert_eq!(a.get(0, 0), b.get(2, 1));
+		assert_eq!(a.get(1, 1), b.get(3, 2));
+
+		let c = a.extend_canvas(1, 1, Anchor::BottomRight, &Rgb::new(0.0, 0.0, 0.0));
+
+		assert_eq!((1, 1), c.dimensions());
+		assert_eq!(a.get(1, 1), c.get(0, 0));
+	}
+
+	#[test]
+	fn tile_into() {
+		let     a = Buffer::<Rgb, u8, _>::from_fn(2, 1, |x, _| Rgb::new(x as f32, 0.0, 0.0));
+		let mut b = Buffer::<Rgb, u8, _>::new(5, 2);
+
+		a.tile_into(&mut b);
+
+		assert_eq!(a.get(0, 0), b.get(4, 1));
+		assert_eq!(a.get(1, 0), b.get(3, 0));
+	}
 }
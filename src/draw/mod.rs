@@ -0,0 +1,405 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::f32::consts::PI;
+
+use pixel;
+use view::View;
+use color::Rgba;
+
+mod raster;
+pub use self::raster::{Rasterizer, Rule};
+
+mod style;
+pub use self::style::Style;
+
+mod stroke;
+pub use self::stroke::{Stroke, Join, Cap};
+
+mod path;
+pub use self::path::{Path, Command};
+
+mod paint;
+pub use self::paint::Paint;
+
+/// Maximum distance between a curve and the segments approximating it.
+const TOLERANCE: f32 = 0.01;
+
+/// Draw a line between the two points, a filling `Style` draws it one pixel
+/// wide.
+///
+/// # Example
+///
+/// ```
+/// use picto::{buffer, draw};
+/// use picto::color::{Rgb, Rgba};
+///
+/// let mut image = buffer::Rgb::from_pixel(100, 100, &Rgb::new(1.0, 1.0, 1.0));
+///
+/// draw::line(&mut image, (10.0, 10.0), (90.0, 50.0),
+///     &draw::Style::stroke(Rgba::new(1.0, 0.0, 0.0, 1.0), 3.0));
+/// ```
+#[inline]
+pub fn line<'o, O, P, C>(output: O, from: (f32, f32), to: (f32, f32), style: &Style)
+	where O: Into<View<'o, P, C>>,
+	      P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	polyline(output, &[from, to], style)
+}
+
+/// Draw connected lines through the points, a filling `Style` draws them one
+/// pixel wide.
+#[inline]
+pub fn polyline<'o, O, P, C>(output: O, points: &[(f32, f32)], style: &Style)
+	where O: Into<View<'o, P, C>>,
+	      P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	render(output, &outline(style).polygons(points, false, TOLERANCE), Rule::NonZero, style)
+}
+
+/// Draw a rectangle with the upper left corner at the given point.
+///
+/// # Example
+///
+/// ```
+/// use picto::{buffer, draw};
+/// use picto::color::{Rgb, Rgba};
+///
+/// let mut image = buffer::Rgb::from_pixel(100, 100, &Rgb::new(1.0, 1.0, 1.0));
+///
+/// draw::rectangle(&mut image, (10.0, 10.0), (30.0, 20.0),
+///     &draw::Style::fill(Rgba::new(0.0, 0.0, 1.0, 0.5)));
+/// draw::rectangle(&mut image, (10.0, 10.0), (30.0, 20.0),
+///     &draw::Style::stroke(Rgba::new(0.0, 0.0, 0.0, 1.0), 1.0));
+/// ```
+#[inline]
+pub fn rectangle<'o, O, P, C>(output: O, (x, y): (f32, f32), (width, height): (f32, f32), style: &Style)
+	where O: Into<View<'o, P, C>>,
+	      P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	polygon(output, &[(x, y), (x + width, y), (x + width, y + height), (x, y + height)], style)
+}
+
+/// Draw a circle with the given center and radius.
+///
+/// # Example
+///
+/// ```
+/// use picto::{buffer, draw};
+/// use picto::color::{Rgb, Rgba};
+///
+/// let mut image = buffer::Rgb::from_pixel(100, 100, &Rgb::new(1.0, 1.0, 1.0));
+///
+/// draw::circle(&mut image, (50.0, 50.0), 20.0,
+///     &draw::Style::fill(Rgba::new(0.0, 1.0, 0.0, 1.0)));
+/// ```
+#[inline]
+pub fn circle<'o, O, P, C>(output: O, center: (f32, f32), radius: f32, style: &Style)
+	where O: Into<View<'o, P, C>>,
+	      P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	ellipse(output, center, (radius, radius), style)
+}
+
+/// Draw an ellipse with the given center and horizontal and vertical radii.
+#[inline]
+pub fn ellipse<'o, O, P, C>(output: O, center: (f32, f32), radii: (f32, f32), style: &Style)
+	where O: Into<View<'o, P, C>>,
+	      P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	let mut points = arc_points(center, radii, 0.0, 360.0);
+	points.pop();
+
+	polygon(output, &points, style)
+}
+
+/// Draw an elliptic arc with the given center and radii, from the start to
+/// the end angle in degrees.
+///
+/// Angles grow clockwise starting from the positive horizontal axis, a
+/// filling `Style` draws a pie slice.
+///
+/// # Example
+///
+/// ```
+/// use picto::{buffer, draw};
+/// use picto::color::{Rgb, Rgba};
+///
+/// let mut image = buffer::Rgb::from_pixel(100, 100, &Rgb::new(1.0, 1.0, 1.0));
+///
+/// draw::arc(&mut image, (50.0, 50.0), (30.0, 20.0), 0.0, 90.0,
+///     &draw::Style::stroke(Rgba::new(0.0, 0.0, 0.0, 1.0), 2.0));
+/// ```
+pub fn arc<'o, O, P, C>(output: O, center: (f32, f32), radii: (f32, f32), start: f32, end: f32, style: &Style)
+	where O: Into<View<'o, P, C>>,
+	      P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	let mut points = arc_points(center, radii, start, end);
+
+	if style.outline().is_some() {
+		polyline(output, &points, style)
+	}
+	else {
+		points.push(center);
+		render(output, &[points], style.fill_rule(), style)
+	}
+}
+
+/// Draw a closed polygon through the given points, filled using the `Rule`
+/// of the `Style`.
+///
+/// # Example
+///
+/// ```
+/// use picto::{buffer, draw};
+/// use picto::color::{Rgb, Rgba};
+///
+/// let mut image = buffer::Rgb::from_pixel(100, 100, &Rgb::new(1.0, 1.0, 1.0));
+///
+/// draw::polygon(&mut image, &[(50.0, 10.0), (90.0, 90.0), (10.0, 90.0)],
+///     &draw::Style::fill(Rgba::new(1.0, 0.5, 0.0, 1.0)));
+/// ```
+#[inline]
+pub fn polygon<'o, O, P, C>(output: O, points: &[(f32, f32)], style: &Style)
+	where O: Into<View<'o, P, C>>,
+	      P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	if let Some(stroke) = style.outline() {
+		render(output, &stroke.polygons(points, true, TOLERANCE), Rule::NonZero, style)
+	}
+	else {
+		render(output, &[points.to_vec()], style.fill_rule(), style)
+	}
+}
+
+/// Draw the `Path`, open sub-paths are closed implicitly when filling.
+///
+/// # Example
+///
+/// ```
+/// use picto::{buffer, draw};
+/// use picto::color::{Rgb, Rgba, Gradient};
+///
+/// let mut image = buffer::Rgb::from_pixel(100, 100, &Rgb::new(1.0, 1.0, 1.0));
+/// let     path  = draw::Path::new()
+///     .move_to((10.0, 90.0))
+///     .quad_to((50.0, 0.0), (90.0, 90.0))
+///     .close();
+///
+/// draw::path(&mut image, &path, &draw::Style::fill(draw::Paint::Linear {
+///     gradient: Gradient::new(vec![Rgba::new(1.0, 0.0, 0.0, 1.0), Rgba::new(0.0, 0.0, 1.0, 1.0)]),
+///     from:     (10.0, 0.0),
+///     to:       (90.0, 0.0),
+/// }));
+///
+/// draw::path(&mut image, &path, &draw::Style::stroke(Rgba::new(0.0, 0.0, 0.0, 1.0), 2.0)
+///     .join(draw::Join::Round));
+/// ```
+pub fn path<'o, O, P, C>(output: O, path: &Path, style: &Style)
+	where O: Into<View<'o, P, C>>,
+	      P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	let contours = path.flatten(TOLERANCE);
+
+	if let Some(stroke) = style.outline() {
+		let polygons = contours.iter()
+			.flat_map(|&(ref points, closed)| stroke.polygons(points, closed, TOLERANCE))
+			.collect::<Vec<_>>();
+
+		render(output, &polygons, Rule::NonZero, style)
+	}
+	else {
+		let polygons = contours.into_iter()
+			.map(|(points, _)| points)
+			.collect::<Vec<_>>();
+
+		render(output, &polygons, style.fill_rule(), style)
+	}
+}
+
+/// Get the `Stroke` of the style, lines drawn with a filling `Style` are one
+/// pixel wide.
+fn outline(style: &Style) -> Stroke {
+	style.outline().cloned().unwrap_or_else(|| Stroke::new(1.0))
+}
+
+/// Get the points approximating the elliptic arc.
+fn arc_points((cx, cy): (f32, f32), (rx, ry): (f32, f32), start: f32, end: f32) -> Vec<(f32, f32)> {
+	let radius   = rx.abs().max(ry.abs());
+	let sweep    = (end - start).to_radians();
+	let step     = if radius > TOLERANCE { 2.0 * (1.0 - TOLERANCE / radius).acos() } else { PI / 4.0 };
+	let segments = (sweep.abs() / step).ceil().clamp(1.0, 4096.0) as usize;
+
+	(0 .. segments + 1).map(|i| {
+		let angle = start.to_radians() + sweep * i as f32 / segments as f32;
+		(cx + rx * angle.cos(), cy + ry * angle.sin())
+	}).collect()
+}
+
+/// Rasterize the contours and blend the style paint over the output.
+fn render<'o, O, P, C>(output: O, contours: &[Vec<(f32, f32)>], rule: Rule, style: &Style)
+	where O: Into<View<'o, P, C>>,
+	      P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	let mut output = output.into();
+	let mut raster = Rasterizer::new();
+
+	for contour in contours {
+		raster.contour(contour);
+	}
+
+	let mode  = style.composite();
+	let paint = style.paint();
+
+	raster.render(output.width(), output.height(), rule, style.is_antialiased(), |x, y, coverage| {
+		let color    = paint.at(x as f32 + 0.5, y as f32 + 0.5);
+		let source   = Rgba::new(color.red, color.green, color.blue, color.alpha * coverage);
+		let backdrop = output.get(x, y).into();
+
+		output.set(x, y, &mode.apply(backdrop, source).into());
+	});
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use buffer::Buffer;
+	use color::{Luma, Gradient};
+
+	fn canvas() -> Buffer<Luma, f32, Vec<f32>> {
+		Buffer::from_pixel(10, 10, &Luma::new(0.0))
+	}
+
+	fn white() -> Rgba {
+		Rgba::new(1.0, 1.0, 1.0, 1.0)
+	}
+
+	fn total(image: &Buffer<Luma, f32, Vec<f32>>) -> f32 {
+		image.pixels().map(|(_, _, p)| p.get().luma).sum()
+	}
+
+	#[test]
+	fn rectangle() {
+		let mut image = canvas();
+		super::rectangle(&mut image, (2.0, 2.0), (3.0, 2.0), &Style::fill(white()));
+
+		assert_eq!(6.0, total(&image));
+		assert_eq!(Luma::new(1.0), image.get(4, 3));
+		assert_eq!(Luma::new(0.0), image.get(5, 3));
+
+		let mut image = canvas();
+		super::rectangle(&mut image, (2.0, 2.0), (4.0, 4.0), &Style::stroke(white(), 2.0));
+
+		// The outer corners are beveled.
+		assert_eq!(Luma::new(0.0), image.get(4, 4));
+		assert_eq!(Luma::new(1.0), image.get(2, 2));
+		assert!((total(&image) - 30.0).abs() < 0.01);
+
+		let mut image = canvas();
+		super::rectangle(&mut image, (2.0, 2.0), (4.0, 4.0), &Style::stroke(white(), 2.0).join(Join::Miter));
+
+		// The outer corners are square.
+		assert_eq!(Luma::new(1.0), image.get(1, 1));
+		assert!((total(&image) - 32.0).abs() < 0.01);
+	}
+
+	#[test]
+	fn line() {
+		let mut image = canvas();
+		super::line(&mut image, (0.0, 5.0), (10.0, 5.0), &Style::stroke(white(), 2.0));
+
+		assert!((total(&image) - 20.0).abs() < 0.01);
+		assert_eq!(Luma::new(1.0), image.get(3, 4));
+		assert_eq!(Luma::new(0.0), image.get(3, 6));
+	}
+
+	#[test]
+	fn circle() {
+		let mut image = canvas();
+		super::circle(&mut image, (5.0, 5.0), 4.0, &Style::fill(white()));
+
+		assert!((total(&image) - PI * 16.0).abs() < 0.2);
+	}
+
+	#[test]
+	fn curves() {
+		let mut image = canvas();
+		let     path  = Path::new()
+			.move_to((1.0, 9.0))
+			.cubic_to((1.0, 1.0), (9.0, 1.0), (9.0, 9.0))
+			.close();
+
+		super::path(&mut image, &path, &Style::fill(white()));
+
+		// The area under the cubic is 3/5 of its control polygon box.
+		assert!((total(&image) - 38.4).abs() < 0.1);
+		assert_eq!(Luma::new(1.0), image.get(5, 6));
+		assert_eq!(Luma::new(0.0), image.get(1, 1));
+	}
+
+	#[test]
+	fn rules() {
+		let path = Path::new()
+			.move_to((1.0, 1.0)).line_to((9.0, 1.0)).line_to((9.0, 9.0)).line_to((1.0, 9.0)).close()
+			.move_to((3.0, 3.0)).line_to((7.0, 3.0)).line_to((7.0, 7.0)).line_to((3.0, 7.0)).close();
+
+		let mut image = canvas();
+		super::path(&mut image, &path, &Style::fill(white()));
+		assert_eq!(Luma::new(1.0), image.get(5, 5));
+
+		let mut image = canvas();
+		super::path(&mut image, &path, &Style::fill(white()).rule(Rule::EvenOdd));
+		assert_eq!(Luma::new(0.0), image.get(5, 5));
+		assert_eq!(Luma::new(1.0), image.get(2, 5));
+	}
+
+	#[test]
+	fn gradient() {
+		let mut image = canvas();
+		super::rectangle(&mut image, (0.0, 0.0), (10.0, 10.0), &Style::fill(Paint::Linear {
+			gradient: Gradient::new(vec![Rgba::new(0.0, 0.0, 0.0, 1.0), white()]),
+			from:     (0.0, 0.0),
+			to:       (10.0, 0.0),
+		}));
+
+		assert_eq!(Luma::new(0.05), image.get(0, 0));
+		assert_eq!(Luma::new(0.95), image.get(9, 9));
+	}
+
+	#[test]
+	fn blend() {
+		let mut image = canvas();
+		super::rectangle(&mut image, (0.0, 0.0), (1.0, 1.0), &Style::fill(Rgba::new(1.0, 1.0, 1.0, 0.5)));
+
+		assert_eq!(Luma::new(0.5), image.get(0, 0));
+	}
+}
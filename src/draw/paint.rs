@@ -0,0 +1,122 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use color::{Rgba, Gradient};
+
+/// What shapes are painted with.
+#[derive(Clone, Debug)]
+pub enum Paint {
+	/// A single color.
+	Solid(Rgba),
+
+	/// A gradient along the line between the two points.
+	Linear {
+		/// The colors.
+		gradient: Gradient<Rgba>,
+
+		/// Where the gradient starts.
+		from: (f32, f32),
+
+		/// Where the gradient ends.
+		to: (f32, f32),
+	},
+
+	/// A gradient going out from the center to the radius.
+	Radial {
+		/// The colors.
+		gradient: Gradient<Rgba>,
+
+		/// Where the gradient starts.
+		center: (f32, f32),
+
+		/// The distance where the gradient ends.
+		radius: f32,
+	},
+}
+
+impl Paint {
+	/// Get the color at the given point, points beyond the ends of a gradient
+	/// get the color at the nearest end.
+	pub fn at(&self, x: f32, y: f32) -> Rgba {
+		match *self {
+			Paint::Solid(color) =>
+				color,
+
+			Paint::Linear { ref gradient, from, to } => {
+				let (dx, dy) = (to.0 - from.0, to.1 - from.1);
+				let length   = dx * dx + dy * dy;
+				let t        = if length > 0.0 {
+					((x - from.0) * dx + (y - from.1) * dy) / length
+				}
+				else {
+					0.0
+				};
+
+				pick(gradient, t)
+			}
+
+			Paint::Radial { ref gradient, center, radius } => {
+				let distance = ((x - center.0).powi(2) + (y - center.1).powi(2)).sqrt();
+				let t        = if radius > 0.0 { distance / radius } else { 1.0 };
+
+				pick(gradient, t)
+			}
+		}
+	}
+}
+
+impl From<Rgba> for Paint {
+	#[inline]
+	fn from(value: Rgba) -> Self {
+		Paint::Solid(value)
+	}
+}
+
+/// Get the color of the gradient at the position between `0.0` and `1.0`
+/// within its domain.
+#[inline]
+fn pick(gradient: &Gradient<Rgba>, t: f32) -> Rgba {
+	let (start, end) = gradient.domain();
+	gradient.get(start + t.clamp(0.0, 1.0) * (end - start))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn linear() {
+		let paint = Paint::Linear {
+			gradient: Gradient::new(vec![Rgba::new(0.0, 0.0, 0.0, 1.0), Rgba::new(1.0, 1.0, 1.0, 1.0)]),
+			from:     (0.0, 0.0),
+			to:       (10.0, 0.0),
+		};
+
+		assert_eq!(Rgba::new(0.0, 0.0, 0.0, 1.0), paint.at(-5.0, 3.0));
+		assert_eq!(Rgba::new(0.5, 0.5, 0.5, 1.0), paint.at(5.0, 3.0));
+		assert_eq!(Rgba::new(1.0, 1.0, 1.0, 1.0), paint.at(15.0, 3.0));
+	}
+
+	#[test]
+	fn radial() {
+		let paint = Paint::Radial {
+			gradient: Gradient::new(vec![Rgba::new(0.0, 0.0, 0.0, 1.0), Rgba::new(1.0, 1.0, 1.0, 1.0)]),
+			center:   (0.0, 0.0),
+			radius:   10.0,
+		};
+
+		assert_eq!(Rgba::new(0.0, 0.0, 0.0, 1.0), paint.at(0.0, 0.0));
+		assert_eq!(Rgba::new(0.5, 0.5, 0.5, 1.0), paint.at(3.0, 4.0));
+	}
+}
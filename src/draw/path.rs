@@ -0,0 +1,234 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+/// A command within a `Path`.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum Command {
+	/// Start a new sub-path at the point.
+	MoveTo((f32, f32)),
+
+	/// Draw a line to the point.
+	LineTo((f32, f32)),
+
+	/// Draw a quadratic Bézier curve with the control point to the point.
+	QuadTo((f32, f32), (f32, f32)),
+
+	/// Draw a cubic Bézier curve with the two control points to the point.
+	CubicTo((f32, f32), (f32, f32), (f32, f32)),
+
+	/// Close the current sub-path.
+	Close,
+}
+
+/// A vector path made of sub-paths of lines and Bézier curves.
+///
+/// # Example
+///
+/// ```
+/// use picto::draw::Path;
+///
+/// // A heart.
+/// let path = Path::new()
+///     .move_to((50.0, 30.0))
+///     .cubic_to((50.0, 0.0), (0.0, 0.0), (0.0, 30.0))
+///     .quad_to((0.0, 60.0), (50.0, 90.0))
+///     .quad_to((100.0, 60.0), (100.0, 30.0))
+///     .cubic_to((100.0, 0.0), (50.0, 0.0), (50.0, 30.0))
+///     .close();
+///
+/// assert_eq!(6, path.commands().len());
+/// ```
+#[derive(PartialEq, Clone, Default, Debug)]
+pub struct Path {
+	commands: Vec<Command>,
+}
+
+impl Path {
+	/// Create an empty `Path`.
+	#[inline]
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	/// Start a new sub-path at the point.
+	#[inline]
+	pub fn move_to(mut self, point: (f32, f32)) -> Self {
+		self.commands.push(Command::MoveTo(point));
+		self
+	}
+
+	/// Draw a line to the point.
+	#[inline]
+	pub fn line_to(mut self, point: (f32, f32)) -> Self {
+		self.commands.push(Command::LineTo(point));
+		self
+	}
+
+	/// Draw a quadratic Bézier curve with the control point to the point.
+	#[inline]
+	pub fn quad_to(mut self, control: (f32, f32), point: (f32, f32)) -> Self {
+		self.commands.push(Command::QuadTo(control, point));
+		self
+	}
+
+	/// Draw a cubic Bézier curve with the two control points to the point.
+	#[inline]
+	pub fn cubic_to(mut self, first: (f32, f32), second: (f32, f32), point: (f32, f32)) -> Self {
+		self.commands.push(Command::CubicTo(first, second, point));
+		self
+	}
+
+	/// Close the current sub-path, going back to its start.
+	#[inline]
+	pub fn close(mut self) -> Self {
+		self.commands.push(Command::Close);
+		self
+	}
+
+	/// Get the commands.
+	#[inline]
+	pub fn commands(&self) -> &[Command] {
+		&self.commands
+	}
+
+	/// Approximate the sub-paths with lines within the given tolerance,
+	/// returning the points of every sub-path and whether it was closed.
+	pub fn flatten(&self, tolerance: f32) -> Vec<(Vec<(f32, f32)>, bool)> {
+		let mut result  = Vec::new();
+		let mut current = Vec::new();
+
+		for command in &self.commands {
+			let last = current.last().cloned();
+
+			match *command {
+				Command::MoveTo(point) => {
+					if current.len() > 1 {
+						result.push((current, false));
+					}
+
+					current = vec![point];
+				}
+
+				Command::LineTo(point) => {
+					if last.is_none() {
+						current.push((0.0, 0.0));
+					}
+
+					current.push(point);
+				}
+
+				Command::QuadTo(control, point) => {
+					let start = last.unwrap_or((0.0, 0.0));
+					if last.is_none() {
+						current.push(start);
+					}
+
+					let dd = distance((start.0 - 2.0 * control.0 + point.0, start.1 - 2.0 * control.1 + point.1));
+					let n  = segments((dd / (8.0 * tolerance)).sqrt());
+
+					for i in 1 .. n + 1 {
+						let t = i as f32 / n as f32;
+						let u = 1.0 - t;
+
+						current.push((
+							u * u * start.0 + 2.0 * u * t * control.0 + t * t * point.0,
+							u * u * start.1 + 2.0 * u * t * control.1 + t * t * point.1));
+					}
+				}
+
+				Command::CubicTo(first, second, point) => {
+					let start = last.unwrap_or((0.0, 0.0));
+					if last.is_none() {
+						current.push(start);
+					}
+
+					let dd = distance((start.0 - 2.0 * first.0 + second.0, start.1 - 2.0 * first.1 + second.1))
+						.max(distance((first.0 - 2.0 * second.0 + point.0, first.1 - 2.0 * second.1 + point.1)));
+					let n  = segments((0.75 * dd / tolerance).sqrt());
+
+					for i in 1 .. n + 1 {
+						let t = i as f32 / n as f32;
+						let u = 1.0 - t;
+
+						current.push((
+							u * u * u * start.0 + 3.0 * u * u * t * first.0 + 3.0 * u * t * t * second.0 + t * t * t * point.0,
+							u * u * u * start.1 + 3.0 * u * u * t * first.1 + 3.0 * u * t * t * second.1 + t * t * t * point.1));
+					}
+				}
+
+				Command::Close => {
+					if let Some(&start) = current.first() {
+						if current.len() > 1 {
+							result.push((current, true));
+						}
+
+						// Drawing after closing continues from the start.
+						current = vec![start];
+					}
+				}
+			}
+		}
+
+		if current.len() > 1 {
+			result.push((current, false));
+		}
+
+		result
+	}
+}
+
+#[inline]
+fn distance((x, y): (f32, f32)) -> f32 {
+	(x * x + y * y).sqrt()
+}
+
+#[inline]
+fn segments(value: f32) -> usize {
+	if value.is_finite() {
+		value.ceil().clamp(1.0, 1024.0) as usize
+	}
+	else {
+		1
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn lines() {
+		let path = Path::new()
+			.move_to((0.0, 0.0)).line_to((1.0, 0.0)).line_to((1.0, 1.0)).close()
+			.move_to((5.0, 5.0)).line_to((6.0, 6.0));
+
+		assert_eq!(vec![
+			(vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)], true),
+			(vec![(5.0, 5.0), (6.0, 6.0)], false),
+		], path.flatten(0.1));
+	}
+
+	#[test]
+	fn curves() {
+		let path = Path::new()
+			.move_to((0.0, 0.0))
+			.quad_to((50.0, 100.0), (100.0, 0.0));
+
+		let points = &path.flatten(0.1)[0].0;
+
+		assert!(points.len() > 10);
+		assert_eq!((100.0, 0.0), points[points.len() - 1]);
+		assert!(points.iter().all(|p| p.1 >= 0.0 && p.1 <= 50.0));
+	}
+}
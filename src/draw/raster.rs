@@ -0,0 +1,249 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+/// Rule deciding which areas are inside overlapping contours.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Rule {
+	/// Areas with a winding number other than zero are inside.
+	NonZero,
+
+	/// Areas with an odd winding number are inside.
+	EvenOdd,
+}
+
+impl Default for Rule {
+	#[inline]
+	fn default() -> Self {
+		Rule::NonZero
+	}
+}
+
+impl Rule {
+	#[inline]
+	fn inside(&self, winding: i32) -> bool {
+		match *self {
+			Rule::NonZero => winding != 0,
+			Rule::EvenOdd => winding % 2 != 0,
+		}
+	}
+}
+
+/// The amount of sub-scanlines sampled per row when anti-aliasing.
+const SAMPLES: u32 = 16;
+
+#[derive(Copy, Clone, Debug)]
+struct Edge {
+	x0: f32,
+	y0: f32,
+	x1: f32,
+	y1: f32,
+
+	winding: i32,
+}
+
+/// Scanline rasterizer for closed contours, computing the coverage of every
+/// pixel.
+///
+/// Every row is sampled with multiple sub-scanlines and the horizontal
+/// coverage of the spans is computed exactly.
+#[derive(Clone, Default, Debug)]
+pub struct Rasterizer {
+	edges: Vec<Edge>,
+}
+
+impl Rasterizer {
+	/// Create an empty `Rasterizer`.
+	#[inline]
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	/// Add a contour, it's implicitly closed.
+	pub fn contour(&mut self, points: &[(f32, f32)]) {
+		if points.len() < 2 {
+			return;
+		}
+
+		for (i, &(x0, y0)) in points.iter().enumerate() {
+			let (x1, y1) = points[(i + 1) % points.len()];
+
+			if y0 == y1 || !(x0.is_finite() && y0.is_finite() && x1.is_finite() && y1.is_finite()) {
+				continue;
+			}
+
+			self.edges.push(if y0 < y1 {
+				Edge { x0, y0, x1, y1, winding: 1 }
+			}
+			else {
+				Edge { x0: x1, y0: y1, x1: x0, y1: y0, winding: -1 }
+			});
+		}
+	}
+
+	/// Call the function with the coordinates and coverage of every covered
+	/// pixel within the given width and height.
+	pub fn render<F>(&self, width: u32, height: u32, rule: Rule, antialias: bool, mut func: F)
+		where F: FnMut(u32, u32, f32)
+	{
+		if self.edges.is_empty() || width == 0 || height == 0 {
+			return;
+		}
+
+		let samples = if antialias { SAMPLES } else { 1 };
+		let weight  = 1.0 / samples as f32;
+
+		let top = self.edges.iter().map(|e| e.y0).fold(f32::MAX, f32::min).floor().max(0.0) as u32;
+		let bottom = self.edges.iter().map(|e| e.y1).fold(f32::MIN, f32::max).ceil().min(height as f32) as u32;
+
+		let mut row       = vec![0.0f32; width as usize];
+		let mut active    = Vec::<&Edge>::new();
+		let mut crossings = Vec::new();
+
+		for y in top .. bottom {
+			let y0 = y as f32;
+			let y1 = y0 + 1.0;
+
+			active.clear();
+			active.extend(self.edges.iter().filter(|e| e.y0 < y1 && e.y1 > y0));
+
+			if active.is_empty() {
+				continue;
+			}
+
+			let mut left  = width as usize;
+			let mut right = 0;
+
+			for sample in 0 .. samples {
+				let sy = y0 + (sample as f32 + 0.5) * weight;
+
+				crossings.clear();
+				crossings.extend(active.iter().filter(|e| e.y0 <= sy && sy < e.y1).map(|e|
+					(e.x0 + (sy - e.y0) * (e.x1 - e.x0) / (e.y1 - e.y0), e.winding)));
+
+				crossings.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(::std::cmp::Ordering::Equal));
+
+				let mut winding = 0;
+				for pair in crossings.windows(2) {
+					winding += pair[0].1;
+
+					if !rule.inside(winding) {
+						continue;
+					}
+
+					let start = pair[0].0.max(0.0).min(width as f32);
+					let end   = pair[1].0.max(0.0).min(width as f32);
+
+					if end <= start {
+						continue;
+					}
+
+					if antialias {
+						let first = start as usize;
+						let last  = end as usize;
+
+						if first == last {
+							row[first] += (end - start) * weight;
+						}
+						else {
+							row[first] += (first as f32 + 1.0 - start) * weight;
+
+							for value in &mut row[first + 1 .. last] {
+								*value += weight;
+							}
+
+							if last < width as usize {
+								row[last] += (end - last as f32) * weight;
+							}
+						}
+
+						left  = left.min(first);
+						right = right.max(last + 1);
+					}
+					else {
+						let first = (start - 0.5).ceil() as usize;
+						let last  = ((end - 0.5).ceil() as usize).min(width as usize);
+
+						for value in &mut row[first .. last.max(first)] {
+							*value = 1.0;
+						}
+
+						left  = left.min(first);
+						right = right.max(last);
+					}
+				}
+			}
+
+			for (x, value) in row.iter_mut().enumerate().take(right).skip(left) {
+				let coverage = value.min(1.0);
+				*value = 0.0;
+
+				if coverage > 0.0 {
+					func(x as u32, y, coverage);
+				}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn coverage(raster: &Rasterizer, rule: Rule, antialias: bool) -> Vec<f32> {
+		let mut result = vec![0.0; 16];
+		raster.render(4, 4, rule, antialias, |x, y, c| result[(y * 4 + x) as usize] = c);
+		result
+	}
+
+	#[test]
+	fn square() {
+		let mut raster = Rasterizer::new();
+		raster.contour(&[(1.0, 1.0), (3.0, 1.0), (3.0, 3.0), (1.0, 3.0)]);
+
+		assert_eq!(vec![
+			0.0, 0.0, 0.0, 0.0,
+			0.0, 1.0, 1.0, 0.0,
+			0.0, 1.0, 1.0, 0.0,
+			0.0, 0.0, 0.0, 0.0,
+		], coverage(&raster, Rule::NonZero, true));
+	}
+
+	#[test]
+	fn partial() {
+		let mut raster = Rasterizer::new();
+		raster.contour(&[(0.5, 0.0), (2.0, 0.0), (2.0, 0.5), (0.5, 0.5)]);
+
+		let result = coverage(&raster, Rule::NonZero, true);
+		assert_eq!(&[0.25, 0.5, 0.0, 0.0], &result[0 .. 4]);
+
+		let result = coverage(&raster, Rule::NonZero, false);
+		assert_eq!(&[0.0, 0.0, 0.0, 0.0], &result[0 .. 4]);
+
+		let mut raster = Rasterizer::new();
+		raster.contour(&[(0.4, 0.0), (1.6, 0.0), (1.6, 1.0), (0.4, 1.0)]);
+
+		let result = coverage(&raster, Rule::NonZero, false);
+		assert_eq!(&[1.0, 1.0, 0.0, 0.0], &result[0 .. 4]);
+	}
+
+	#[test]
+	fn rules() {
+		let mut raster = Rasterizer::new();
+		raster.contour(&[(0.0, 0.0), (4.0, 0.0), (4.0, 4.0), (0.0, 4.0)]);
+		raster.contour(&[(1.0, 1.0), (3.0, 1.0), (3.0, 3.0), (1.0, 3.0)]);
+
+		assert_eq!(1.0, coverage(&raster, Rule::NonZero, true)[5]);
+		assert_eq!(0.0, coverage(&raster, Rule::EvenOdd, true)[5]);
+	}
+}
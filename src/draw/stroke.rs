@@ -0,0 +1,287 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::f32::consts::PI;
+
+/// How consecutive segments of a stroke are joined.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Join {
+	/// Extend the outer edges until they meet, falling back to `Bevel` past
+	/// the miter limit.
+	Miter,
+
+	/// Round the corner.
+	Round,
+
+	/// Cut the corner.
+	Bevel,
+}
+
+impl Default for Join {
+	#[inline]
+	fn default() -> Self {
+		Join::Bevel
+	}
+}
+
+/// How the ends of an open stroke look.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Cap {
+	/// End exactly at the end points.
+	Butt,
+
+	/// Extend past the end points by half the width.
+	Square,
+
+	/// Round the ends.
+	Round,
+}
+
+impl Default for Cap {
+	#[inline]
+	fn default() -> Self {
+		Cap::Butt
+	}
+}
+
+/// The geometry of a stroke.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct Stroke {
+	width:       f32,
+	join:        Join,
+	cap:         Cap,
+	miter_limit: f32,
+}
+
+impl Stroke {
+	/// Create a `Stroke` with the given width, bevel joins and butt caps.
+	#[inline]
+	pub fn new(width: f32) -> Self {
+		Stroke {
+			width,
+			join:        Default::default(),
+			cap:         Default::default(),
+			miter_limit: 4.0,
+		}
+	}
+
+	/// Set the `Join`.
+	#[inline]
+	pub fn join(mut self, value: Join) -> Self {
+		self.join = value;
+		self
+	}
+
+	/// Set the `Cap`.
+	#[inline]
+	pub fn cap(mut self, value: Cap) -> Self {
+		self.cap = value;
+		self
+	}
+
+	/// Set the miter limit, the maximum ratio between the miter length and
+	/// the width.
+	#[inline]
+	pub fn miter_limit(mut self, value: f32) -> Self {
+		self.miter_limit = value.max(1.0);
+		self
+	}
+
+	/// Get the width.
+	#[inline]
+	pub fn width(&self) -> f32 {
+		self.width
+	}
+
+	/// Build the polygons covering the stroke of the given points.
+	///
+	/// All the polygons wind the same way, so they're meant to be filled with
+	/// the non-zero rule.
+	pub fn polygons(&self, points: &[(f32, f32)], closed: bool, tolerance: f32) -> Vec<Vec<(f32, f32)>> {
+		let mut points = points.to_vec();
+		points.dedup();
+
+		if closed && points.len() > 1 && points.first() == points.last() {
+			points.pop();
+		}
+
+		let mut polygons = Vec::new();
+		let     half     = self.width / 2.0;
+
+		if points.len() < 2 || half <= 0.0 {
+			return polygons;
+		}
+
+		let closed   = closed && points.len() > 2;
+		let segments = if closed { points.len() } else { points.len() - 1 };
+
+		// The unit direction of every segment.
+		let directions = (0 .. segments).map(|i| {
+			let (x0, y0) = points[i];
+			let (x1, y1) = points[(i + 1) % points.len()];
+			let length   = ((x1 - x0).powi(2) + (y1 - y0).powi(2)).sqrt();
+
+			((x1 - x0) / length, (y1 - y0) / length)
+		}).collect::<Vec<_>>();
+
+		let normal = |i: usize| (-directions[i].1 * half, directions[i].0 * half);
+
+		for i in 0 .. segments {
+			let (x0, y0) = points[i];
+			let (x1, y1) = points[(i + 1) % points.len()];
+			let (nx, ny) = normal(i);
+
+			polygons.push(oriented(vec![
+				(x0 + nx, y0 + ny), (x1 + nx, y1 + ny),
+				(x1 - nx, y1 - ny), (x0 - nx, y0 - ny)]));
+		}
+
+		let joins = if closed { segments } else { segments - 1 };
+
+		for i in 0 .. joins {
+			let (x, y) = points[(i + 1) % points.len()];
+			let a      = directions[i];
+			let b      = directions[(i + 1) % segments];
+			let cross  = a.0 * b.1 - a.1 * b.0;
+
+			if cross == 0.0 {
+				continue;
+			}
+
+			// The outer side is opposite to where the stroke turns.
+			let side   = if cross > 0.0 { -1.0 } else { 1.0 };
+			let (ax, ay) = normal(i);
+			let (bx, by) = normal((i + 1) % segments);
+			let (ax, ay) = (ax * side, ay * side);
+			let (bx, by) = (bx * side, by * side);
+
+			match self.join {
+				Join::Round => {
+					polygons.push(circle((x, y), half, tolerance));
+				}
+
+				Join::Miter => {
+					let (mx, my) = (ax + bx, ay + by);
+					let cos      = ((mx * mx + my * my).sqrt() / (2.0 * half)).max(f32::EPSILON);
+
+					if 1.0 / cos <= self.miter_limit {
+						let scale = 1.0 / (2.0 * cos * cos);
+
+						polygons.push(oriented(vec![(x, y), (x + ax, y + ay),
+							(x + mx * scale, y + my * scale), (x + bx, y + by)]));
+					}
+					else {
+						polygons.push(oriented(vec![(x, y), (x + ax, y + ay), (x + bx, y + by)]));
+					}
+				}
+
+				Join::Bevel => {
+					polygons.push(oriented(vec![(x, y), (x + ax, y + ay), (x + bx, y + by)]));
+				}
+			}
+		}
+
+		if !closed {
+			let ends = [
+				(points[0], (-directions[0].0, -directions[0].1)),
+				(points[points.len() - 1], directions[segments - 1]),
+			];
+
+			for &((x, y), (dx, dy)) in &ends {
+				match self.cap {
+					Cap::Butt => (),
+
+					Cap::Square => {
+						let (nx, ny) = (-dy * half, dx * half);
+						let (ex, ey) = (dx * half, dy * half);
+
+						polygons.push(oriented(vec![
+							(x + nx, y + ny), (x + nx + ex, y + ny + ey),
+							(x - nx + ex, y - ny + ey), (x - nx, y - ny)]));
+					}
+
+					Cap::Round => {
+						polygons.push(circle((x, y), half, tolerance));
+					}
+				}
+			}
+		}
+
+		polygons
+	}
+}
+
+/// Get the polygon approximating the circle within the given tolerance.
+pub fn circle((cx, cy): (f32, f32), radius: f32, tolerance: f32) -> Vec<(f32, f32)> {
+	let step     = if radius > tolerance { 2.0 * (1.0 - tolerance / radius).acos() } else { PI / 4.0 };
+	let segments = (2.0 * PI / step).ceil().clamp(8.0, 4096.0) as usize;
+
+	(0 .. segments).map(|i| {
+		let angle = 2.0 * PI * i as f32 / segments as f32;
+		(cx + radius * angle.cos(), cy + radius * angle.sin())
+	}).collect()
+}
+
+/// Get the signed area of the polygon.
+pub fn area(points: &[(f32, f32)]) -> f32 {
+	let mut sum = 0.0;
+
+	for (i, &(x0, y0)) in points.iter().enumerate() {
+		let (x1, y1) = points[(i + 1) % points.len()];
+		sum += x0 * y1 - x1 * y0;
+	}
+
+	sum / 2.0
+}
+
+/// Make the polygon wind positively.
+pub fn oriented(mut points: Vec<(f32, f32)>) -> Vec<(f32, f32)> {
+	if area(&points) < 0.0 {
+		points.reverse();
+	}
+
+	points
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn total(polygons: &[Vec<(f32, f32)>]) -> f32 {
+		polygons.iter().map(|p| area(p)).sum()
+	}
+
+	#[test]
+	fn caps() {
+		let points = [(0.0, 0.0), (10.0, 0.0)];
+
+		assert_eq!(20.0, total(&Stroke::new(2.0).polygons(&points, false, 0.01)));
+		assert_eq!(24.0, total(&Stroke::new(2.0).cap(Cap::Square).polygons(&points, false, 0.01)));
+	}
+
+	#[test]
+	fn joins() {
+		let points = [(0.0, 0.0), (10.0, 0.0), (10.0, 10.0)];
+
+		// The corner square, split in half by the bevel.
+		let miter = Stroke::new(2.0).join(Join::Miter).polygons(&points, false, 0.01);
+		let bevel = Stroke::new(2.0).polygons(&points, false, 0.01);
+
+		assert!((total(&miter) - total(&bevel) - 0.5).abs() < 0.0001);
+
+		// Past the limit the miter becomes a bevel.
+		let limited = Stroke::new(2.0).join(Join::Miter).miter_limit(1.0).polygons(&points, false, 0.01);
+		assert!((total(&limited) - total(&bevel)).abs() < 0.0001);
+	}
+}
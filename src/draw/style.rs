@@ -0,0 +1,147 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use color::Rgba;
+use processing::composite;
+use super::{Paint, Stroke, Join, Cap, Rule};
+
+/// How shapes are drawn.
+#[derive(Clone, Debug)]
+pub struct Style {
+	paint:     Paint,
+	stroke:    Option<Stroke>,
+	rule:      Rule,
+	antialias: bool,
+	mode:      composite::Mode,
+}
+
+impl Style {
+	/// Create a `Style` filling shapes with the given paint.
+	#[inline]
+	pub fn fill<T: Into<Paint>>(paint: T) -> Self {
+		Style {
+			paint:     paint.into(),
+			stroke:    None,
+			rule:      Default::default(),
+			antialias: true,
+			mode:      Default::default(),
+		}
+	}
+
+	/// Create a `Style` outlining shapes with the given paint and width.
+	#[inline]
+	pub fn stroke<T: Into<Paint>>(paint: T, width: f32) -> Self {
+		Style {
+			paint:     paint.into(),
+			stroke:    Some(Stroke::new(width)),
+			rule:      Default::default(),
+			antialias: true,
+			mode:      Default::default(),
+		}
+	}
+
+	/// Set the `Join` of the stroke, ignored when filling.
+	#[inline]
+	pub fn join(mut self, value: Join) -> Self {
+		self.stroke = self.stroke.map(|s| s.join(value));
+		self
+	}
+
+	/// Set the `Cap` of the stroke, ignored when filling.
+	#[inline]
+	pub fn cap(mut self, value: Cap) -> Self {
+		self.stroke = self.stroke.map(|s| s.cap(value));
+		self
+	}
+
+	/// Set the miter limit of the stroke, ignored when filling.
+	#[inline]
+	pub fn miter_limit(mut self, value: f32) -> Self {
+		self.stroke = self.stroke.map(|s| s.miter_limit(value));
+		self
+	}
+
+	/// Set the fill `Rule`, non-zero by default and ignored when stroking.
+	#[inline]
+	pub fn rule(mut self, value: Rule) -> Self {
+		self.rule = value;
+		self
+	}
+
+	/// Enable or disable anti-aliasing, enabled by default.
+	#[inline]
+	pub fn antialias(mut self, value: bool) -> Self {
+		self.antialias = value;
+		self
+	}
+
+	/// Set the compositing `Mode`, normal blending over the backdrop by
+	/// default.
+	#[inline]
+	pub fn mode(mut self, value: composite::Mode) -> Self {
+		self.mode = value;
+		self
+	}
+
+	/// Get the `Paint`.
+	#[inline]
+	pub fn paint(&self) -> &Paint {
+		&self.paint
+	}
+
+	/// Get the color, gradients give the color where they start.
+	#[inline]
+	pub fn color(&self) -> Rgba {
+		match self.paint {
+			Paint::Solid(color) =>
+				color,
+
+			Paint::Linear { from, .. } =>
+				self.paint.at(from.0, from.1),
+
+			Paint::Radial { center, .. } =>
+				self.paint.at(center.0, center.1),
+		}
+	}
+
+	/// Get the stroke width, `None` when filling.
+	#[inline]
+	pub fn width(&self) -> Option<f32> {
+		self.stroke.map(|s| s.width())
+	}
+
+	/// Get the `Stroke`, `None` when filling.
+	#[inline]
+	pub fn outline(&self) -> Option<&Stroke> {
+		self.stroke.as_ref()
+	}
+
+	/// Get the fill `Rule`.
+	#[inline]
+	pub fn fill_rule(&self) -> Rule {
+		self.rule
+	}
+
+	/// Check if anti-aliasing is enabled.
+	#[inline]
+	pub fn is_antialiased(&self) -> bool {
+		self.antialias
+	}
+
+	/// Get the compositing `Mode`.
+	#[inline]
+	pub fn composite(&self) -> composite::Mode {
+		self.mode
+	}
+}
@@ -0,0 +1,130 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use color::Rgba;
+
+/// How pixels outside of the image are handled when sampling or filtering.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub enum EdgeMode {
+	/// Repeat the nearest pixel on the edge.
+	Clamp,
+
+	/// Wrap around to the opposite edge, useful for tiling textures.
+	Wrap,
+
+	/// Reflect the image at the edges, repeating the pixel on the edge.
+	Mirror,
+
+	/// Use the given color.
+	Constant(Rgba),
+}
+
+impl Default for EdgeMode {
+	#[inline]
+	fn default() -> Self {
+		EdgeMode::Clamp
+	}
+}
+
+impl EdgeMode {
+	/// Map the coordinate to one within `0 .. length`, returns `None` when the
+	/// constant color should be used instead or when `length` is zero.
+	#[inline]
+	pub fn index(&self, value: i64, length: u32) -> Option<u32> {
+		let length = length as i64;
+
+		if length == 0 {
+			return None;
+		}
+
+		if value >= 0 && value < length {
+			return Some(value as u32);
+		}
+
+		match *self {
+			EdgeMode::Clamp =>
+				Some(if value < 0 { 0 } else { length - 1 } as u32),
+
+			EdgeMode::Wrap =>
+				Some(modulo(value, length) as u32),
+
+			EdgeMode::Mirror => {
+				let value = modulo(value, length * 2);
+
+				Some(if value < length { value } else { length * 2 - 1 - value } as u32)
+			}
+
+			EdgeMode::Constant(..) =>
+				None
+		}
+	}
+}
+
+/// Euclidean modulo, always positive.
+#[inline]
+fn modulo(value: i64, length: i64) -> i64 {
+	let value = value % length;
+
+	if value < 0 {
+		value + length
+	}
+	else {
+		value
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn clamp() {
+		assert_eq!(Some(0), EdgeMode::Clamp.index(-3, 4));
+		assert_eq!(Some(2), EdgeMode::Clamp.index(2, 4));
+		assert_eq!(Some(3), EdgeMode::Clamp.index(7, 4));
+	}
+
+	#[test]
+	fn wrap() {
+		assert_eq!(Some(1), EdgeMode::Wrap.index(-3, 4));
+		assert_eq!(Some(3), EdgeMode::Wrap.index(-1, 4));
+		assert_eq!(Some(3), EdgeMode::Wrap.index(7, 4));
+	}
+
+	#[test]
+	fn mirror() {
+		assert_eq!(Some(0), EdgeMode::Mirror.index(-1, 4));
+		assert_eq!(Some(2), EdgeMode::Mirror.index(-3, 4));
+		assert_eq!(Some(3), EdgeMode::Mirror.index(4, 4));
+		assert_eq!(Some(1), EdgeMode::Mirror.index(6, 4));
+		assert_eq!(Some(0), EdgeMode::Mirror.index(8, 4));
+		assert_eq!(Some(0), EdgeMode::Mirror.index(5, 1));
+	}
+
+	#[test]
+	fn constant() {
+		let mode = EdgeMode::Constant(Rgba::new(0.0, 0.0, 0.0, 0.0));
+
+		assert_eq!(None, mode.index(-1, 4));
+		assert_eq!(Some(1), mode.index(1, 4));
+		assert_eq!(None, mode.index(4, 4));
+	}
+
+	#[test]
+	fn empty() {
+		assert_eq!(None, EdgeMode::Clamp.index(0, 0));
+		assert_eq!(None, EdgeMode::Wrap.index(3, 0));
+		assert_eq!(None, EdgeMode::Mirror.index(-3, 0));
+	}
+}
@@ -17,6 +17,7 @@ use std::marker::PhantomData;
 use pixel;
 use region::Region;
 use iter::Coordinates;
+use util::Marker;
 
 /// Immutable iterator over pixels.
 pub struct Iter<'a, P, C>
@@ -29,7 +30,7 @@ pub struct Iter<'a, P, C>
 	data:   &'a [C],
 	stride: usize,
 
-	pixel:   PhantomData<P>,
+	pixel:   Marker<P>,
 	channel: PhantomData<C>,
 }
 
@@ -59,7 +60,7 @@ pub struct Item<'a, P, C>
 	where P: pixel::Read<C>,
 	      C: pixel::Channel,
 {
-	pixel:   PhantomData<P>,
+	pixel:   Marker<P>,
 	channel: PhantomData<C>,
 	data:    &'a [C],
 }
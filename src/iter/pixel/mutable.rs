@@ -18,6 +18,7 @@ use std::marker::PhantomData;
 use pixel;
 use region::Region;
 use iter::Coordinates;
+use util::Marker;
 
 /// Mutable iterator over pixels.
 pub struct Iter<'a, P, C>
@@ -30,7 +31,7 @@ pub struct Iter<'a, P, C>
 	data:   &'a mut [C],
 	stride: usize,
 
-	pixel:   PhantomData<P>,
+	pixel:   Marker<P>,
 	channel: PhantomData<C>,
 }
 
@@ -60,7 +61,7 @@ pub struct Item<'a, P, C>
 	where P: pixel::Read<C> + pixel::Write<C>,
 	      C: pixel::Channel,
 {
-	pixel:   PhantomData<P>,
+	pixel:   Marker<P>,
 	channel: PhantomData<C>,
 	data:    &'a mut [C],
 }
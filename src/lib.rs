@@ -50,6 +50,9 @@ extern crate color_quant;
 #[cfg(feature = "processing")]
 extern crate exoquant;
 
+#[cfg(feature = "parallel")]
+extern crate rayon;
+
 #[macro_use]
 mod util;
 
@@ -65,6 +68,12 @@ pub use region::Region;
 mod orientation;
 pub use orientation::Orientation;
 
+mod edge;
+pub use edge::EdgeMode;
+
+mod anchor;
+pub use anchor::Anchor;
+
 /// Basic traits for types within buffers and views.
 pub mod pixel;
 pub use pixel::Pixel;
@@ -84,6 +93,14 @@ pub mod iter;
 #[cfg(feature = "processing")]
 pub mod processing;
 
+/// Vector drawing.
+#[cfg(feature = "processing")]
+pub mod draw;
+
+/// Bitmap font text rendering.
+#[cfg(feature = "processing")]
+pub mod text;
+
 /// Image format related functions.
 pub mod format;
 pub use format::Format;
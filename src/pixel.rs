@@ -18,10 +18,11 @@ use color::{Luma, Rgb, Hsl, Hsv, Hwb, Lab, Lch, Xyz, Yxy};
 use color::{Lumaa, Rgba, Hsla, Hsva, Hwba, Laba, Lcha, Xyza, Yxya};
 use color::pixel::Srgb;
 use color::RgbHue;
+use util::Shared;
 
 /// A `Channel` abstracts away the underlying type the `Pixel` components are
 /// stored as.
-pub trait Channel: Zero + Copy + 'static {
+pub trait Channel: Zero + Copy + Shared + 'static {
 	/// Convert the passed value to a proper `Channel`.
 	fn from<T: Float + 'static>(value: T) -> Self;
 }
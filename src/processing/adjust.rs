@@ -0,0 +1,445 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use buffer::Buffer;
+use pixel;
+use view::{self, View};
+use color::{Rgba, Rgb, Lch, LabHue, Hue, Limited};
+use processing::srgb;
+
+/// A per pixel color adjustment.
+///
+/// The alpha is left alone by all the provided adjustments.
+pub trait Adjustment {
+	/// Adjust the given pixel.
+	fn adjust(&self, pixel: Rgba) -> Rgba;
+}
+
+impl<F> Adjustment for F
+	where F: Fn(Rgba) -> Rgba
+{
+	#[inline]
+	fn adjust(&self, pixel: Rgba) -> Rgba {
+		self(pixel)
+	}
+}
+
+/// Trait for adjustable types.
+pub trait Adjust<P, C>
+	where P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	/// Apply the given `Adjustment`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// image.adjust(&adjust::Levels::new((0.1, 0.9), 1.2, (0.0, 1.0)));
+	/// ```
+	fn adjust<A: Adjustment>(self, adjustment: &A) -> Buffer<P, C, Vec<C>>;
+}
+
+impl<'i, P, C, I> Adjust<P, C> for I
+	where P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+	      I: Into<view::Read<'i, P, C>>
+{
+	#[inline]
+	fn adjust<A: Adjustment>(self, adjustment: &A) -> Buffer<P, C, Vec<C>> {
+		it::<_, P, C, P, C, A>(self, adjustment)
+	}
+}
+
+/// Apply the given `Adjustment`.
+#[inline]
+pub fn it<'i, I, PI, CI, PO, CO, A>(input: I, adjustment: &A) -> Buffer<PO, CO, Vec<CO>>
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      A:  Adjustment,
+{
+	let input = input.into();
+
+	let mut output = Buffer::<PO, CO, _>::new(input.width(), input.height());
+	for (x, y) in output.region().absolute() {
+		output.set(x, y, &adjustment.adjust(input.get(x, y).into()).into());
+	}
+
+	output
+}
+
+/// Apply the given `Adjustment` in place, only touching the pixels within the
+/// view.
+///
+/// # Example
+///
+/// ```
+/// use picto::{read, Region};
+/// use picto::color::Rgb;
+/// use picto::processing::prelude::*;
+///
+/// let mut image = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+///
+/// // Brighten the upper left corner.
+/// adjust::in_place(image.view(Region::new().width(10).height(10)),
+///     &adjust::Exposure(1.0));
+/// ```
+#[inline]
+pub fn in_place<'o, O, P, C, A>(output: O, adjustment: &A)
+	where O: Into<View<'o, P, C>>,
+	      P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+	      A: Adjustment,
+{
+	let mut output = output.into();
+
+	for (_, _, mut px) in output.pixels_mut() {
+		let p = adjustment.adjust(px.get().into());
+		px.set(&p.into());
+	}
+}
+
+/// Apply the function to the color channels, leaving the alpha alone.
+#[inline]
+fn channels<F>(pixel: Rgba, func: F) -> Rgba
+	where F: Fn(f32) -> f32
+{
+	Rgba::new(func(pixel.red), func(pixel.green), func(pixel.blue), pixel.alpha)
+}
+
+/// Input and output levels.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Levels {
+	input:  (f32, f32),
+	gamma:  f32,
+	output: (f32, f32),
+}
+
+impl Levels {
+	/// Create new levels, mapping the input black and white points to the
+	/// output ones, with the given midtone gamma.
+	#[inline]
+	pub fn new(input: (f32, f32), gamma: f32, output: (f32, f32)) -> Self {
+		Levels {
+			input,
+			gamma: if gamma > 0.0 { gamma } else { 1.0 },
+			output,
+		}
+	}
+}
+
+impl Adjustment for Levels {
+	#[inline]
+	fn adjust(&self, pixel: Rgba) -> Rgba {
+		let range = (self.input.1 - self.input.0).max(f32::EPSILON);
+
+		channels(pixel, |value| {
+			let value = ((value - self.input.0) / range).clamp(0.0, 1.0).powf(1.0 / self.gamma);
+			self.output.0 + value * (self.output.1 - self.output.0)
+		})
+	}
+}
+
+/// A smooth curve through control points, using monotone cubic
+/// interpolation so it never overshoots them.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Curve {
+	points:   Vec<(f32, f32)>,
+	tangents: Vec<f32>,
+}
+
+impl Curve {
+	/// Create a curve through the given points, returns `None` if there are
+	/// less than two points or the `x` coordinates aren't strictly increasing.
+	pub fn new(points: &[(f32, f32)]) -> Option<Self> {
+		if points.len() < 2 || points.windows(2).any(|w| w[1].0 <= w[0].0) {
+			return None;
+		}
+
+		let slopes = points.windows(2).map(|w|
+			(w[1].1 - w[0].1) / (w[1].0 - w[0].0)).collect::<Vec<_>>();
+
+		let mut tangents = Vec::with_capacity(points.len());
+		tangents.push(slopes[0]);
+
+		for w in slopes.windows(2) {
+			tangents.push(if w[0] * w[1] <= 0.0 { 0.0 } else { (w[0] + w[1]) / 2.0 });
+		}
+
+		tangents.push(slopes[slopes.len() - 1]);
+
+		// Limit the tangents to keep the curve monotone (Fritsch-Carlson).
+		for (i, &slope) in slopes.iter().enumerate() {
+			if slope == 0.0 {
+				tangents[i]     = 0.0;
+				tangents[i + 1] = 0.0;
+				continue;
+			}
+
+			let a = tangents[i] / slope;
+			let b = tangents[i + 1] / slope;
+			let s = a * a + b * b;
+
+			if s > 9.0 {
+				let t = 3.0 / s.sqrt();
+
+				tangents[i]     = t * a * slope;
+				tangents[i + 1] = t * b * slope;
+			}
+		}
+
+		Some(Curve {
+			points:   points.to_vec(),
+			tangents,
+		})
+	}
+
+	/// Get the value of the curve at the given position, positions outside
+	/// of the control points are clamped to them.
+	pub fn get(&self, x: f32) -> f32 {
+		let first = self.points[0];
+		let last  = self.points[self.points.len() - 1];
+
+		if x <= first.0 {
+			return first.1;
+		}
+
+		if x >= last.0 {
+			return last.1;
+		}
+
+		let i = self.points.windows(2).position(|w| x < w[1].0).unwrap_or(0);
+
+		let (x0, y0) = self.points[i];
+		let (x1, y1) = self.points[i + 1];
+		let h        = x1 - x0;
+		let t        = (x - x0) / h;
+		let t2       = t * t;
+		let t3       = t2 * t;
+
+		(2.0 * t3 - 3.0 * t2 + 1.0) * y0
+			+ (t3 - 2.0 * t2 + t) * h * self.tangents[i]
+			+ (-2.0 * t3 + 3.0 * t2) * y1
+			+ (t3 - t2) * h * self.tangents[i + 1]
+	}
+}
+
+/// Curves applied on all the color channels and then per channel.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct Curves {
+	all:   Option<Curve>,
+	red:   Option<Curve>,
+	green: Option<Curve>,
+	blue:  Option<Curve>,
+}
+
+impl Curves {
+	/// Create curves leaving everything alone.
+	#[inline]
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	/// Set the curve applied on all the color channels.
+	#[inline]
+	pub fn all(mut self, curve: Curve) -> Self {
+		self.all = Some(curve);
+		self
+	}
+
+	/// Set the curve applied on the red channel.
+	#[inline]
+	pub fn red(mut self, curve: Curve) -> Self {
+		self.red = Some(curve);
+		self
+	}
+
+	/// Set the curve applied on the green channel.
+	#[inline]
+	pub fn green(mut self, curve: Curve) -> Self {
+		self.green = Some(curve);
+		self
+	}
+
+	/// Set the curve applied on the blue channel.
+	#[inline]
+	pub fn blue(mut self, curve: Curve) -> Self {
+		self.blue = Some(curve);
+		self
+	}
+}
+
+impl Adjustment for Curves {
+	#[inline]
+	fn adjust(&self, pixel: Rgba) -> Rgba {
+		let apply = |curve: &Option<Curve>, value: f32|
+			curve.as_ref().map(|c| c.get(value)).unwrap_or(value);
+
+		let pixel = channels(pixel, |value| apply(&self.all, value));
+
+		Rgba::new(
+			apply(&self.red, pixel.red),
+			apply(&self.green, pixel.green),
+			apply(&self.blue, pixel.blue),
+			pixel.alpha)
+	}
+}
+
+/// Gamma correction, values greater than `1.0` brighten the midtones.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Gamma(pub f32);
+
+impl Adjustment for Gamma {
+	#[inline]
+	fn adjust(&self, pixel: Rgba) -> Rgba {
+		let exponent = if self.0 > 0.0 { 1.0 / self.0 } else { 1.0 };
+		channels(pixel, |value| value.max(0.0).powf(exponent))
+	}
+}
+
+/// Brightness and contrast, both in the `-1.0 ..= 1.0` range.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct BrightnessContrast {
+	brightness: f32,
+	contrast:   f32,
+}
+
+impl BrightnessContrast {
+	/// Create a new brightness and contrast adjustment.
+	#[inline]
+	pub fn new(brightness: f32, contrast: f32) -> Self {
+		BrightnessContrast {
+			brightness: brightness.clamp(-1.0, 1.0),
+			contrast:   contrast.clamp(-1.0, 1.0),
+		}
+	}
+}
+
+impl Adjustment for BrightnessContrast {
+	#[inline]
+	fn adjust(&self, pixel: Rgba) -> Rgba {
+		let factor = if self.contrast < 1.0 {
+			(1.0 + self.contrast) / (1.0 - self.contrast)
+		}
+		else {
+			f32::MAX
+		};
+
+		channels(pixel, |value|
+			((value - 0.5) * factor + 0.5 + self.brightness).clamp(0.0, 1.0))
+	}
+}
+
+/// Vibrance, saturating the less saturated colors more than the already
+/// saturated ones; negative values desaturate.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Vibrance(pub f32);
+
+impl Adjustment for Vibrance {
+	#[inline]
+	fn adjust(&self, pixel: Rgba) -> Rgba {
+		let max        = pixel.red.max(pixel.green).max(pixel.blue);
+		let min        = pixel.red.min(pixel.green).min(pixel.blue);
+		let luma       = 0.2126 * pixel.red + 0.7152 * pixel.green + 0.0722 * pixel.blue;
+		let saturation = (max - min).clamp(0.0, 1.0);
+		let factor     = 1.0 + self.0 * (1.0 - saturation);
+
+		channels(pixel, |value| (luma + (value - luma) * factor).clamp(0.0, 1.0))
+	}
+}
+
+/// Hue rotation by the given degrees, in `Lch` so the lightness is kept.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct HueShift(pub f32);
+
+impl Adjustment for HueShift {
+	#[inline]
+	fn adjust(&self, pixel: Rgba) -> Rgba {
+		let lch = Lch::from(pixel.color).shift_hue(LabHue::from(self.0));
+		Rgba { color: Rgb::from(lch).clamp(), alpha: pixel.alpha }
+	}
+}
+
+/// Exposure by the given stops, scaling the linear light intensity.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Exposure(pub f32);
+
+impl Adjustment for Exposure {
+	#[inline]
+	fn adjust(&self, pixel: Rgba) -> Rgba {
+		let factor = 2.0f32.powf(self.0);
+
+		channels(pixel, |value|
+			srgb::from_linear(srgb::to_linear(value) * factor).clamp(0.0, 1.0))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use region::Region;
+	use color::Luma;
+
+	#[test]
+	fn levels() {
+		let levels = Levels::new((0.2, 0.6), 1.0, (0.0, 1.0));
+		let px     = levels.adjust(Rgba::new(0.2, 0.4, 0.8, 0.5));
+
+		assert!(px.red.abs() < 0.001);
+		assert!((px.green - 0.5).abs() < 0.001);
+		assert!((px.blue - 1.0).abs() < 0.001);
+		assert_eq!(0.5, px.alpha);
+	}
+
+	#[test]
+	fn curve() {
+		assert!(Curve::new(&[(0.0, 0.0)]).is_none());
+		assert!(Curve::new(&[(0.5, 0.0), (0.2, 1.0)]).is_none());
+
+		let curve = Curve::new(&[(0.0, 0.0), (0.25, 0.4), (0.75, 0.6), (1.0, 1.0)]).unwrap();
+
+		assert!((curve.get(0.25) - 0.4).abs() < 0.0001);
+		assert!((curve.get(0.75) - 0.6).abs() < 0.0001);
+
+		let mut last = 0.0;
+		for i in 0 .. 101 {
+			let value = curve.get(i as f32 / 100.0);
+
+			assert!(value >= last);
+			last = value;
+		}
+	}
+
+	#[test]
+	fn region() {
+		let mut image = Buffer::<Luma, f32, _>::from_pixel(4, 4, &Luma::new(0.5));
+		in_place(image.view(Region::new().x(2).width(2)), &Gamma(2.0));
+
+		assert_eq!(image.get(0, 0), Luma::new(0.5));
+		assert!(image.get(3, 3).luma > 0.7);
+	}
+}
@@ -0,0 +1,169 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use buffer::Buffer;
+use pixel;
+use view;
+use edge::EdgeMode;
+use color::Rgba;
+use util::GetEdge;
+
+/// Trait for bilateral filterable types.
+pub trait Bilateral<P, C>
+	where P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	/// Smooth with the given spatial and range sigmas, keeping the edges
+	/// intact.
+	///
+	/// The spatial sigma is in pixels, the range sigma is the color distance
+	/// with channels in the `0.0 ..= 1.0` range.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// image.bilateral(2.0, 0.1);
+	/// ```
+	fn bilateral(self, spatial: f32, range: f32) -> Buffer<P, C, Vec<C>>;
+
+	/// Smooth with the given spatial and range sigmas, handling the edges with
+	/// the given `EdgeMode`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::{read, EdgeMode};
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// image.bilateral_with(2.0, 0.1, EdgeMode::Mirror);
+	/// ```
+	fn bilateral_with(self, spatial: f32, range: f32, edge: EdgeMode) -> Buffer<P, C, Vec<C>>;
+}
+
+impl<'i, P, C, I> Bilateral<P, C> for I
+	where P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+	      I: Into<view::Read<'i, P, C>>
+{
+	#[inline]
+	fn bilateral(self, spatial: f32, range: f32) -> Buffer<P, C, Vec<C>> {
+		by::<_, P, C, P, C>(self, spatial, range, Default::default())
+	}
+
+	#[inline]
+	fn bilateral_with(self, spatial: f32, range: f32, edge: EdgeMode) -> Buffer<P, C, Vec<C>> {
+		by::<_, P, C, P, C>(self, spatial, range, edge)
+	}
+}
+
+/// Smooth with the given spatial and range sigmas, handling the edges with the
+/// given `EdgeMode`.
+#[inline]
+pub fn by<'i, I, PI, CI, PO, CO>(input: I, mut spatial: f32, mut range: f32, edge: EdgeMode) -> Buffer<PO, CO, Vec<CO>>
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	let input = input.into();
+	let input = &input;
+
+	if spatial <= 0.0 {
+		spatial = 1.0;
+	}
+
+	if range <= 0.0 {
+		range = 0.1;
+	}
+
+	let     radius = (spatial * 2.0).ceil() as i64;
+	let mut output = Buffer::<PO, CO, _>::new(input.width(), input.height());
+
+	// The spatial weights only depend on the offset, so compute them once.
+	let mut weights = Vec::with_capacity(((radius * 2 + 1) * (radius * 2 + 1)) as usize);
+	for dy in -radius .. radius + 1 {
+		for dx in -radius .. radius + 1 {
+			weights.push((dx, dy, (-((dx * dx + dy * dy) as f32) / (2.0 * spatial * spatial)).exp()));
+		}
+	}
+
+	for (x, y) in output.region().absolute() {
+		let     center = input.get(x, y).into();
+		let mut sum    = (0.0, 0.0, 0.0, 0.0);
+		let mut total  = 0.0;
+
+		for &(dx, dy, weight) in &weights {
+			let p = input.get_edge(x as i64 + dx, y as i64 + dy, edge);
+
+			let distance = (p.red - center.red).powi(2)
+				+ (p.green - center.green).powi(2)
+				+ (p.blue - center.blue).powi(2);
+
+			let weight = weight * (-distance / (2.0 * range * range)).exp();
+
+			sum.0 += p.red * weight;
+			sum.1 += p.green * weight;
+			sum.2 += p.blue * weight;
+			sum.3 += p.alpha * weight;
+			total += weight;
+		}
+
+		output.set(x, y, &Rgba::new(sum.0 / total, sum.1 / total, sum.2 / total, sum.3 / total).into());
+	}
+
+	output
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use buffer;
+	use color::Luma;
+
+	#[test]
+	fn edge() {
+		let input = buffer::Luma::from_fn(8, 8, |x, _|
+			if x < 4 { Luma::new(0.2) } else { Luma::new(0.8) });
+
+		let output = input.bilateral(2.0, 0.05);
+
+		assert_eq!(input, output);
+	}
+
+	#[test]
+	fn smooth() {
+		let input = buffer::Luma::from_fn(8, 8, |x, y|
+			if (x + y) % 2 == 0 { Luma::new(0.48) } else { Luma::new(0.52) });
+
+		let output = input.bilateral(2.0, 0.5);
+
+		for (_, _, p) in output.pixels() {
+			assert!((p.get().luma - 0.5).abs() < 0.01);
+		}
+	}
+}
@@ -15,9 +15,10 @@
 use buffer::Buffer;
 use pixel;
 use view;
+use edge::EdgeMode;
 use color::Rgba;
 use processing::sampler::gaussian;
-use processing::sample;
+use processing::{resample, srgb};
 
 /// Trait for blurrable types.
 pub trait Blur<P, C>
@@ -39,6 +40,37 @@ pub trait Blur<P, C>
 	/// image.blur(1.0);
 	/// ```
 	fn blur(self, sigma: f32) -> Buffer<P, C, Vec<C>>;
+
+	/// Blur by the given radius, handling the edges with the given `EdgeMode`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::{read, EdgeMode};
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// image.blur_with(1.0, EdgeMode::Mirror);
+	/// ```
+	fn blur_with(self, sigma: f32, edge: EdgeMode) -> Buffer<P, C, Vec<C>>;
+
+	/// Blur by the given radius in linear light, decoding the sRGB input before
+	/// blurring and encoding it back after.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// image.blur_linear(1.0);
+	/// ```
+	fn blur_linear(self, sigma: f32) -> Buffer<P, C, Vec<C>>;
 }
 
 impl<'i, P, C, I> Blur<P, C> for I
@@ -51,11 +83,35 @@ impl<'i, P, C, I> Blur<P, C> for I
 	fn blur(self, sigma: f32) -> Buffer<P, C, Vec<C>> {
 		by::<_, P, C, P, C>(self, sigma)
 	}
+
+	#[inline]
+	fn blur_with(self, sigma: f32, edge: EdgeMode) -> Buffer<P, C, Vec<C>> {
+		by_with::<_, P, C, P, C>(self, sigma, edge)
+	}
+
+	#[inline]
+	fn blur_linear(self, sigma: f32) -> Buffer<P, C, Vec<C>> {
+		linear::<_, P, C, P, C>(self, sigma, Default::default())
+	}
 }
 
 /// Blur by the given radius.
 #[inline]
-pub fn by<'i, I, PI, CI, PO, CO>(input: I, mut sigma: f32) -> Buffer<PO, CO, Vec<CO>>
+pub fn by<'i, I, PI, CI, PO, CO>(input: I, sigma: f32) -> Buffer<PO, CO, Vec<CO>>
+	where PO: From<Rgba> + Into<Rgba>,
+	      PO: pixel::Read<CO> + pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      I:  Into<view::Read<'i, PI, CI>>
+{
+	by_with(input, sigma, Default::default())
+}
+
+/// Blur by the given radius, handling the edges with the given `EdgeMode`.
+#[inline]
+pub fn by_with<'i, I, PI, CI, PO, CO>(input: I, mut sigma: f32, edge: EdgeMode) -> Buffer<PO, CO, Vec<CO>>
 	where PO: From<Rgba> + Into<Rgba>,
 	      PO: pixel::Read<CO> + pixel::Write<CO>,
 	      CO: pixel::Channel,
@@ -70,11 +126,20 @@ pub fn by<'i, I, PI, CI, PO, CO>(input: I, mut sigma: f32) -> Buffer<PO, CO, Vec
 		sigma = 1.0;
 	}
 
-	let mut tmp = Buffer::<PO, CO, _>::new(input.width(), input.height());
-	sample::vertically_with(&input, &mut tmp, sigma * 2.0, |x| gaussian::function(x, sigma));
-
-	let mut out = Buffer::<PO, CO, _>::new(input.width(), input.height());
-	sample::horizontally_with(&tmp, &mut out, sigma * 2.0, |x| gaussian::function(x, sigma));
+	resample::scale_with(&input, input.width(), input.height(), edge, sigma * 2.0, |x| gaussian::function(x, sigma))
+}
 
-	out
+/// Blur by the given radius in linear light, handling the edges with the given
+/// `EdgeMode`.
+#[inline]
+pub fn linear<'i, I, PI, CI, PO, CO>(input: I, sigma: f32, edge: EdgeMode) -> Buffer<PO, CO, Vec<CO>>
+	where PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      I:  Into<view::Read<'i, PI, CI>>
+{
+	srgb::encode(&by_with::<_, Rgba, f32, Rgba, f32>(&srgb::decode(input), sigma, edge))
 }
@@ -0,0 +1,450 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use buffer::{self, Buffer};
+use pixel;
+use view;
+use color::Rgba;
+
+/// Energy added to the protected pixels and taken from the ones to remove,
+/// large enough to dominate any gradient.
+const BIAS: f64 = 1.0e6;
+
+/// Areas to keep or to get rid of while carving, any pixel that isn't black
+/// in a mask is part of the area.
+///
+/// The areas to remove are only used when shrinking, when enlarging no seams
+/// are inserted through them.
+#[derive(Copy, Clone, Default, Debug)]
+pub struct Mask<'a> {
+	protect: Option<&'a buffer::Luma>,
+	remove:  Option<&'a buffer::Luma>,
+}
+
+impl<'a> Mask<'a> {
+	/// Create an empty `Mask`.
+	#[inline]
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	/// Set the areas no seam should go through.
+	#[inline]
+	pub fn protect(mut self, mask: &'a buffer::Luma) -> Self {
+		self.protect = Some(mask);
+		self
+	}
+
+	/// Set the areas seams should go through first.
+	#[inline]
+	pub fn remove(mut self, mask: &'a buffer::Luma) -> Self {
+		self.remove = Some(mask);
+		self
+	}
+}
+
+/// Trait for seam carvable types.
+pub trait Carve<P, C>
+	where P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	/// Resize to the given width and height removing or inserting the seams
+	/// with the lowest energy.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image   = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	/// let resized = image.carve(300, 250);
+	///
+	/// assert_eq!(resized.width(), 300);
+	/// assert_eq!(resized.height(), 250);
+	/// ```
+	fn carve(self, width: u32, height: u32) -> Buffer<P, C, Vec<C>>;
+
+	/// Resize to the given width and height removing or inserting the seams
+	/// with the lowest energy, following the given `Mask`.
+	///
+	/// # Panics
+	///
+	/// Requires that the masks are as big as the input, otherwise it will
+	/// panic.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::{read, buffer};
+	/// use picto::color::{Rgb, Luma};
+	/// use picto::processing::prelude::*;
+	///
+	/// let image   = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	/// let boat    = buffer::Luma::from_fn(320, 240, |x, y|
+	///     if x > 100 && x < 220 && y > 60 { Luma::new(1.0) } else { Luma::new(0.0) });
+	/// let resized = image.carve_with(240, 240, carve::Mask::new().protect(&boat));
+	///
+	/// assert_eq!(resized.width(), 240);
+	/// ```
+	fn carve_with(self, width: u32, height: u32, mask: Mask) -> Buffer<P, C, Vec<C>>;
+}
+
+impl<'i, P, C, I> Carve<P, C> for I
+	where P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+	      I: Into<view::Read<'i, P, C>>
+{
+	#[inline]
+	fn carve(self, width: u32, height: u32) -> Buffer<P, C, Vec<C>> {
+		resize::<_, P, C, P, C>(self, width, height)
+	}
+
+	#[inline]
+	fn carve_with(self, width: u32, height: u32, mask: Mask) -> Buffer<P, C, Vec<C>> {
+		resize_with::<_, P, C, P, C>(self, width, height, mask)
+	}
+}
+
+/// Resize to the given width and height removing or inserting the seams with
+/// the lowest energy.
+#[inline]
+pub fn resize<'i, I, PI, CI, PO, CO>(input: I, width: u32, height: u32) -> Buffer<PO, CO, Vec<CO>>
+	where PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      I:  Into<view::Read<'i, PI, CI>>
+{
+	resize_with::<_, PI, CI, PO, CO>(input, width, height, Mask::new())
+}
+
+/// Resize to the given width and height removing or inserting the seams with
+/// the lowest energy, following the given `Mask`.
+///
+/// Empty inputs give a blank buffer of the requested size.
+///
+/// # Panics
+///
+/// Requires that the width and height are not zero and that the masks are as
+/// big as the input, otherwise it will panic.
+pub fn resize_with<'i, I, PI, CI, PO, CO>(input: I, width: u32, height: u32, mask: Mask) -> Buffer<PO, CO, Vec<CO>>
+	where PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      I:  Into<view::Read<'i, PI, CI>>
+{
+	if width == 0 || height == 0 {
+		panic!("invalid size");
+	}
+
+	let input = input.into();
+
+	if input.width() == 0 || input.height() == 0 {
+		return Buffer::new(width, height);
+	}
+
+	let mut image = Image::new(&input, mask);
+
+	image.resize(width as usize);
+	image = image.transpose();
+	image.resize(height as usize);
+	image = image.transpose();
+
+	Buffer::from_fn(width, height, |x, y|
+		image.pixels[y as usize * image.width + x as usize])
+}
+
+/// The image being carved, with the energy bias of every pixel.
+#[derive(Clone)]
+struct Image {
+	width:  usize,
+	height: usize,
+	pixels: Vec<Rgba>,
+	bias:   Vec<f64>,
+}
+
+impl Image {
+	fn new<P, C>(input: &view::Read<P, C>, mask: Mask) -> Self
+		where P: Into<Rgba> + pixel::Read<C>,
+		      C: pixel::Channel,
+	{
+		for mask in mask.protect.iter().chain(mask.remove.iter()) {
+			if mask.dimensions() != input.dimensions() {
+				panic!("mask size mismatch");
+			}
+		}
+
+		let marked = |mask: Option<&buffer::Luma>, x, y|
+			mask.map(|m| m.get(x, y).luma > 0.0).unwrap_or(false);
+
+		let mut pixels = Vec::with_capacity((input.width() * input.height()) as usize);
+		let mut bias   = Vec::with_capacity(pixels.capacity());
+
+		for y in 0 .. input.height() {
+			for x in 0 .. input.width() {
+				pixels.push(input.get(x, y).into());
+				bias.push(
+					if marked(mask.protect, x, y) { BIAS } else { 0.0 } -
+					if marked(mask.remove, x, y) { BIAS } else { 0.0 });
+			}
+		}
+
+		Image {
+			width:  input.width() as usize,
+			height: input.height() as usize,
+			pixels,
+			bias,
+		}
+	}
+
+	/// Swap the rows and the columns.
+	fn transpose(&self) -> Self {
+		let index = |i: usize| (i % self.height) * self.width + i / self.height;
+
+		Image {
+			width:  self.height,
+			height: self.width,
+			pixels: (0 .. self.pixels.len()).map(|i| self.pixels[index(i)]).collect(),
+			bias:   (0 .. self.bias.len()).map(|i| self.bias[index(i)]).collect(),
+		}
+	}
+
+	/// Get the dual gradient energy of every pixel, with the bias.
+	///
+	/// The bias dwarfs the gradients, so the energy is kept in `f64` to not
+	/// lose them within the protected and removed areas.
+	fn energy(&self) -> Vec<f64> {
+		let get = |x: usize, y: usize| self.pixels[y * self.width + x];
+		let gradient = |a: Rgba, b: Rgba|
+			(a.red - b.red).powi(2) + (a.green - b.green).powi(2) +
+			(a.blue - b.blue).powi(2) + (a.alpha - b.alpha).powi(2);
+
+		let mut energy = Vec::with_capacity(self.pixels.len());
+
+		for y in 0 .. self.height {
+			for x in 0 .. self.width {
+				let dx = gradient(get((x + 1).min(self.width - 1), y), get(x.saturating_sub(1), y));
+				let dy = gradient(get(x, (y + 1).min(self.height - 1)), get(x, y.saturating_sub(1)));
+
+				energy.push((dx + dy).sqrt() as f64 + self.bias[y * self.width + x]);
+			}
+		}
+
+		energy
+	}
+
+	/// Find the vertical seam with the lowest total energy, as the column of
+	/// every row.
+	fn seam(&self) -> Vec<usize> {
+		let     width = self.width;
+		let mut cost  = self.energy();
+
+		for y in 1 .. self.height {
+			for x in 0 .. width {
+				let above = (x.saturating_sub(1) ..= (x + 1).min(width - 1))
+					.map(|x| cost[(y - 1) * width + x])
+					.fold(f64::MAX, f64::min);
+
+				cost[y * width + x] += above;
+			}
+		}
+
+		let row     = |y: usize| &cost[y * width .. (y + 1) * width];
+		let minimum = |row: &[f64], range: ::std::ops::Range<usize>| range
+			.min_by(|&a, &b| row[a].partial_cmp(&row[b]).unwrap())
+			.unwrap();
+
+		let mut seam = vec![0; self.height];
+		seam[self.height - 1] = minimum(row(self.height - 1), 0 .. width);
+
+		for y in (0 .. self.height - 1).rev() {
+			let x = seam[y + 1];
+			seam[y] = minimum(row(y), x.saturating_sub(1) .. (x + 2).min(width));
+		}
+
+		seam
+	}
+
+	/// Remove the seam.
+	fn remove(&mut self, seam: &[usize]) {
+		self.pixels = without(&self.pixels, self.width, seam);
+		self.bias   = without(&self.bias, self.width, seam);
+		self.width -= 1;
+	}
+
+	/// Remove or insert seams until the width is reached.
+	fn resize(&mut self, width: usize) {
+		while self.width > width {
+			let seam = self.seam();
+			self.remove(&seam);
+		}
+
+		while self.width < width {
+			let amount = (width - self.width).min((self.width / 2).max(1));
+			self.insert(amount);
+		}
+	}
+
+	/// Duplicate the given amount of seams, found by removing them from a
+	/// copy so the same seam isn't picked over and over.
+	fn insert(&mut self, amount: usize) {
+		let mut copy    = self.clone();
+		let mut columns = (0 .. self.pixels.len()).map(|i| i % self.width).collect::<Vec<_>>();
+		let mut counts  = vec![0; self.pixels.len()];
+
+		for bias in &mut copy.bias {
+			*bias = bias.max(0.0);
+		}
+
+		for _ in 0 .. amount {
+			let seam = copy.seam();
+
+			for (y, &x) in seam.iter().enumerate() {
+				counts[y * self.width + columns[y * copy.width + x]] += 1;
+			}
+
+			columns = without(&columns, copy.width, &seam);
+			copy.remove(&seam);
+		}
+
+		let mut pixels = Vec::with_capacity(self.pixels.len() + amount * self.height);
+		let mut bias   = Vec::with_capacity(pixels.capacity());
+
+		for y in 0 .. self.height {
+			for x in 0 .. self.width {
+				let index = y * self.width + x;
+				let next  = y * self.width + (x + 1).min(self.width - 1);
+				let (a, b) = (self.pixels[index], self.pixels[next]);
+
+				pixels.push(a);
+				bias.push(self.bias[index]);
+
+				for _ in 0 .. counts[index] {
+					pixels.push(Rgba::new(
+						(a.red + b.red) / 2.0,
+						(a.green + b.green) / 2.0,
+						(a.blue + b.blue) / 2.0,
+						(a.alpha + b.alpha) / 2.0));
+
+					bias.push(self.bias[index]);
+				}
+			}
+		}
+
+		self.pixels = pixels;
+		self.bias   = bias;
+		self.width += amount;
+	}
+}
+
+/// Get the rows without the column of the seam.
+fn without<T: Copy>(data: &[T], width: usize, seam: &[usize]) -> Vec<T> {
+	let mut result = Vec::with_capacity(data.len() - seam.len());
+
+	for (y, &x) in seam.iter().enumerate() {
+		result.extend_from_slice(&data[y * width .. y * width + x]);
+		result.extend_from_slice(&data[y * width + x + 1 .. (y + 1) * width]);
+	}
+
+	result
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use buffer;
+	use color::{Rgb, Luma};
+
+	fn stripes(width: u32, height: u32, columns: &[(u32, Rgb)]) -> buffer::Rgb {
+		Buffer::from_fn(width, height, |x, _|
+			columns.iter().find(|c| c.0 == x).map(|c| c.1).unwrap_or(Rgb::new(1.0, 1.0, 1.0)))
+	}
+
+	fn count(image: &buffer::Rgb, color: Rgb) -> usize {
+		image.pixels().filter(|(_, _, p)| p.get() == color).count()
+	}
+
+	#[test]
+	fn shrink() {
+		let black = Rgb::new(0.0, 0.0, 0.0);
+		let image = stripes(8, 3, &[(5, black)]);
+
+		let result = resize::<_, Rgb, u8, Rgb, u8>(&image, 5, 3);
+		assert_eq!((5, 3), result.dimensions());
+		assert_eq!(3, count(&result, black));
+
+		let result = resize::<_, Rgb, u8, Rgb, u8>(&image, 8, 2);
+		assert_eq!((8, 2), result.dimensions());
+		assert_eq!(2, count(&result, black));
+	}
+
+	#[test]
+	fn grow() {
+		let black = Rgb::new(0.0, 0.0, 0.0);
+		let image = stripes(8, 3, &[(5, black)]);
+
+		let result = resize::<_, Rgb, u8, Rgb, u8>(&image, 20, 3);
+		assert_eq!((20, 3), result.dimensions());
+		assert_eq!(3, count(&result, black));
+		assert_eq!(57, count(&result, Rgb::new(1.0, 1.0, 1.0)));
+	}
+
+	#[test]
+	fn mask() {
+		let red  = Rgb::new(1.0, 0.0, 0.0);
+		let blue = Rgb::new(0.0, 0.0, 1.0);
+		let image = stripes(8, 3, &[(2, red), (5, blue)]);
+
+		let column = |n| buffer::Luma::from_fn(8, 3, |x, _|
+			if x == n { Luma::new(1.0) } else { Luma::new(0.0) });
+
+		let (protect, remove) = (column(2), column(5));
+		let result = resize_with::<_, Rgb, u8, Rgb, u8>(&image, 7, 3, Mask::new().protect(&protect).remove(&remove));
+
+		assert_eq!(3, count(&result, red));
+		assert_eq!(0, count(&result, blue));
+
+		// Only the protected column is left.
+		let result = resize_with::<_, Rgb, u8, Rgb, u8>(&image, 1, 3, Mask::new().protect(&protect));
+		assert_eq!(3, count(&result, red));
+	}
+
+	#[test]
+	fn protected() {
+		// The flat column has the lowest energy by far less than the bias.
+		let values = [0.0, 0.1, 0.2, 0.3, 0.4, 0.5, 0.4, 0.3];
+		let image  = Buffer::<Rgb, f32, _>::from_fn(8, 40, |x, _|
+			Rgb::new(values[x as usize], values[x as usize], values[x as usize]));
+
+		let protect = buffer::Luma::from_pixel(8, 40, &Luma::new(1.0));
+		let result  = resize_with::<_, Rgb, f32, Rgb, f32>(&image, 7, 40, Mask::new().protect(&protect));
+
+		assert!(result.pixels().all(|(_, _, p)| p.get().red != 0.5));
+	}
+
+	#[test]
+	fn empty() {
+		let image = buffer::Rgb::new(0, 4);
+		assert_eq!((3, 4), resize::<_, Rgb, u8, Rgb, u8>(&image, 3, 4).dimensions());
+	}
+}
@@ -0,0 +1,398 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use pixel;
+use view::{self, View};
+use color::Rgba;
+
+/// Porter-Duff operators, deciding which parts of the source and backdrop are
+/// kept.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Operator {
+	/// The source is placed over the backdrop.
+	Over,
+
+	/// The source is kept only where the backdrop is.
+	In,
+
+	/// The source is kept only where the backdrop isn't.
+	Out,
+
+	/// The source is placed over the backdrop only where the backdrop is.
+	Atop,
+
+	/// The source and backdrop are kept only where they don't overlap.
+	Xor,
+}
+
+impl Default for Operator {
+	#[inline]
+	fn default() -> Self {
+		Operator::Over
+	}
+}
+
+/// Separable blend modes, deciding the color where the source and backdrop
+/// overlap.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Blend {
+	/// The source color.
+	Normal,
+
+	/// The product of the colors, always darker.
+	Multiply,
+
+	/// The inverse of the product of the inverse colors, always lighter.
+	Screen,
+
+	/// Multiply or screen depending on the backdrop.
+	Overlay,
+
+	/// Darken or lighten depending on the source, softer than `HardLight`.
+	SoftLight,
+
+	/// Multiply or screen depending on the source.
+	HardLight,
+
+	/// Brighten the backdrop to reflect the source.
+	ColorDodge,
+
+	/// Darken the backdrop to reflect the source.
+	ColorBurn,
+
+	/// The darkest of the colors.
+	Darken,
+
+	/// The lightest of the colors.
+	Lighten,
+
+	/// The absolute difference of the colors.
+	Difference,
+
+	/// Like `Difference` but with lower contrast.
+	Exclusion,
+}
+
+impl Default for Blend {
+	#[inline]
+	fn default() -> Self {
+		Blend::Normal
+	}
+}
+
+impl Blend {
+	/// Blend the source channel over the backdrop channel.
+	#[inline]
+	pub fn apply(&self, backdrop: f32, source: f32) -> f32 {
+		match *self {
+			Blend::Normal =>
+				source,
+
+			Blend::Multiply =>
+				backdrop * source,
+
+			Blend::Screen =>
+				backdrop + source - backdrop * source,
+
+			Blend::Overlay =>
+				Blend::HardLight.apply(source, backdrop),
+
+			Blend::SoftLight =>
+				if source <= 0.5 {
+					backdrop - (1.0 - 2.0 * source) * backdrop * (1.0 - backdrop)
+				}
+				else {
+					let d = if backdrop <= 0.25 {
+						((16.0 * backdrop - 12.0) * backdrop + 4.0) * backdrop
+					}
+					else {
+						backdrop.sqrt()
+					};
+
+					backdrop + (2.0 * source - 1.0) * (d - backdrop)
+				},
+
+			Blend::HardLight =>
+				if source <= 0.5 {
+					Blend::Multiply.apply(backdrop, 2.0 * source)
+				}
+				else {
+					Blend::Screen.apply(backdrop, 2.0 * source - 1.0)
+				},
+
+			Blend::ColorDodge =>
+				if backdrop <= 0.0 {
+					0.0
+				}
+				else if source >= 1.0 {
+					1.0
+				}
+				else {
+					(backdrop / (1.0 - source)).min(1.0)
+				},
+
+			Blend::ColorBurn =>
+				if backdrop >= 1.0 {
+					1.0
+				}
+				else if source <= 0.0 {
+					0.0
+				}
+				else {
+					1.0 - ((1.0 - backdrop) / source).min(1.0)
+				},
+
+			Blend::Darken =>
+				backdrop.min(source),
+
+			Blend::Lighten =>
+				backdrop.max(source),
+
+			Blend::Difference =>
+				(backdrop - source).abs(),
+
+			Blend::Exclusion =>
+				backdrop + source - 2.0 * backdrop * source,
+		}
+	}
+}
+
+/// How the source is composited over the backdrop.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct Mode {
+	operator: Operator,
+	blend:    Blend,
+	opacity:  f32,
+}
+
+impl Default for Mode {
+	#[inline]
+	fn default() -> Self {
+		Mode {
+			operator: Default::default(),
+			blend:    Default::default(),
+			opacity:  1.0,
+		}
+	}
+}
+
+impl Mode {
+	/// Create a new `Mode`, normal blending over the backdrop with full
+	/// opacity.
+	#[inline]
+	pub fn new() -> Self {
+		Default::default()
+	}
+
+	/// Set the Porter-Duff operator.
+	#[inline]
+	pub fn operator(mut self, value: Operator) -> Self {
+		self.operator = value;
+		self
+	}
+
+	/// Set the blend mode.
+	#[inline]
+	pub fn blend(mut self, value: Blend) -> Self {
+		self.blend = value;
+		self
+	}
+
+	/// Set the opacity of the source.
+	#[inline]
+	pub fn opacity(mut self, value: f32) -> Self {
+		self.opacity = value.clamp(0.0, 1.0);
+		self
+	}
+
+	/// Composite the source pixel over the backdrop pixel.
+	#[inline]
+	pub fn apply(&self, backdrop: Rgba, source: Rgba) -> Rgba {
+		let ba = backdrop.alpha.clamp(0.0, 1.0);
+		let sa = source.alpha.clamp(0.0, 1.0) * self.opacity;
+
+		let (fa, fb) = match self.operator {
+			Operator::Over => (1.0, 1.0 - sa),
+			Operator::In   => (ba, 0.0),
+			Operator::Out  => (1.0 - ba, 0.0),
+			Operator::Atop => (ba, 1.0 - sa),
+			Operator::Xor  => (1.0 - ba, 1.0 - sa),
+		};
+
+		let alpha = sa * fa + ba * fb;
+
+		if alpha <= 0.0 {
+			return Rgba::new(0.0, 0.0, 0.0, 0.0);
+		}
+
+		let channel = |b: f32, s: f32| {
+			let s = (1.0 - ba) * s + ba * self.blend.apply(b, s);
+			(sa * fa * s + ba * fb * b) / alpha
+		};
+
+		Rgba::new(
+			channel(backdrop.red, source.red),
+			channel(backdrop.green, source.green),
+			channel(backdrop.blue, source.blue),
+			alpha)
+	}
+}
+
+/// Trait for compositable types.
+pub trait Composite<P, C>
+	where P: Into<Rgba>,
+	      P: pixel::Read<C>,
+	      C: pixel::Channel,
+{
+	/// Composite onto the output with the given `Mode`, placing the upper left
+	/// corner at the given coordinates.
+	///
+	/// Only the area covered by the input is touched, and any part falling
+	/// outside of the output is skipped.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::{read, buffer};
+	/// use picto::color::{Rgb, Rgba};
+	/// use picto::processing::prelude::*;
+	///
+	/// let mut image = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	/// let     mark  = buffer::Rgba::from_pixel(10, 10, &Rgba::new(1.0, 0.0, 0.0, 0.5));
+	///
+	/// mark.composite(&mut image, 5, 5, composite::Mode::new()
+	///     .blend(composite::Blend::Multiply)
+	///     .opacity(0.8));
+	/// ```
+	fn composite<'o, O, PO, CO>(self, output: O, x: i64, y: i64, mode: Mode)
+		where O:  Into<View<'o, PO, CO>>,
+		      PO: From<Rgba> + Into<Rgba>,
+		      PO: pixel::Read<CO> + pixel::Write<CO>,
+		      CO: pixel::Channel;
+}
+
+impl<'i, P, C, I> Composite<P, C> for I
+	where P: Into<Rgba>,
+	      P: pixel::Read<C>,
+	      C: pixel::Channel,
+	      I: Into<view::Read<'i, P, C>>
+{
+	#[inline]
+	fn composite<'o, O, PO, CO>(self, output: O, x: i64, y: i64, mode: Mode)
+		where O:  Into<View<'o, PO, CO>>,
+		      PO: From<Rgba> + Into<Rgba>,
+		      PO: pixel::Read<CO> + pixel::Write<CO>,
+		      CO: pixel::Channel
+	{
+		onto(self, output, x, y, mode)
+	}
+}
+
+/// Composite the input onto the output with the given `Mode`, placing the
+/// upper left corner at the given coordinates.
+pub fn onto<'i, 'o, I, O, PI, CI, PO, CO>(input: I, output: O, x: i64, y: i64, mode: Mode)
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      O:  Into<View<'o, PO, CO>>,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      PO: From<Rgba> + Into<Rgba>,
+	      PO: pixel::Read<CO> + pixel::Write<CO>,
+	      CO: pixel::Channel,
+{
+	let     input  = input.into();
+	let mut output = output.into();
+
+	for (ix, iy, px) in input.pixels() {
+		let ox = x + ix as i64;
+		let oy = y + iy as i64;
+
+		if ox < 0 || oy < 0 || ox >= output.width() as i64 || oy >= output.height() as i64 {
+			continue;
+		}
+
+		let backdrop = output.get(ox as u32, oy as u32).into();
+		output.set(ox as u32, oy as u32, &mode.apply(backdrop, px.get().into()).into());
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use buffer::Buffer;
+	use color::Rgb;
+
+	fn close(a: Rgba, b: Rgba) -> bool {
+		(a.red - b.red).abs() < 0.001 &&
+		(a.green - b.green).abs() < 0.001 &&
+		(a.blue - b.blue).abs() < 0.001 &&
+		(a.alpha - b.alpha).abs() < 0.001
+	}
+
+	#[test]
+	fn over() {
+		let mode = Mode::new().opacity(0.5);
+
+		assert!(close(Rgba::new(0.5, 0.0, 0.5, 1.0),
+			mode.apply(Rgba::new(0.0, 0.0, 1.0, 1.0), Rgba::new(1.0, 0.0, 0.0, 1.0))));
+
+		assert!(close(Rgba::new(1.0, 0.0, 0.0, 0.5),
+			Mode::new().apply(Rgba::new(0.0, 0.0, 0.0, 0.0), Rgba::new(1.0, 0.0, 0.0, 0.5))));
+	}
+
+	#[test]
+	fn operators() {
+		let backdrop = Rgba::new(0.0, 0.0, 1.0, 1.0);
+		let source   = Rgba::new(1.0, 0.0, 0.0, 1.0);
+
+		assert!(close(Rgba::new(1.0, 0.0, 0.0, 1.0),
+			Mode::new().operator(Operator::In).apply(backdrop, source)));
+
+		assert!(close(Rgba::new(0.0, 0.0, 0.0, 0.0),
+			Mode::new().operator(Operator::Out).apply(backdrop, source)));
+
+		assert!(close(Rgba::new(0.0, 0.0, 0.0, 0.0),
+			Mode::new().operator(Operator::Xor).apply(backdrop, source)));
+
+		assert!(close(Rgba::new(0.0, 0.0, 1.0, 1.0),
+			Mode::new().operator(Operator::Atop).apply(backdrop, Rgba::new(1.0, 0.0, 0.0, 0.0))));
+	}
+
+	#[test]
+	fn blend() {
+		let backdrop = Rgba::new(0.5, 0.2, 1.0, 1.0);
+		let source   = Rgba::new(0.5, 1.0, 0.0, 1.0);
+
+		assert!(close(Rgba::new(0.25, 0.2, 0.0, 1.0),
+			Mode::new().blend(Blend::Multiply).apply(backdrop, source)));
+
+		assert!(close(Rgba::new(0.75, 1.0, 1.0, 1.0),
+			Mode::new().blend(Blend::Screen).apply(backdrop, source)));
+
+		assert!(close(Rgba::new(0.0, 0.8, 1.0, 1.0),
+			Mode::new().blend(Blend::Difference).apply(backdrop, source)));
+	}
+
+	#[test]
+	fn offset() {
+		let mut output = Buffer::<Rgb, f32, _>::from_pixel(4, 4, &Rgb::new(0.0, 0.0, 0.0));
+		let     input  = Buffer::<Rgb, f32, _>::from_pixel(2, 2, &Rgb::new(1.0, 1.0, 1.0));
+
+		input.composite(&mut output, -1, 3, Mode::new());
+
+		assert_eq!(Rgb::new(1.0, 1.0, 1.0), output.get(0, 3));
+		assert_eq!(Rgb::new(0.0, 0.0, 0.0), output.get(1, 3));
+		assert_eq!(Rgb::new(0.0, 0.0, 0.0), output.get(0, 2));
+	}
+}
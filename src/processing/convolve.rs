@@ -0,0 +1,301 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use buffer::Buffer;
+use pixel;
+use view;
+use edge::EdgeMode;
+use color::{Rgba, Luma, Limited};
+use util::GetEdge;
+use super::kernel::{Kernel, Weights, Operator};
+
+/// The gradient of an image.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Gradient {
+	/// The magnitude of the gradient.
+	pub magnitude: Buffer<Luma, f32, Vec<f32>>,
+
+	/// The direction of the gradient in radians.
+	pub direction: Buffer<Luma, f32, Vec<f32>>,
+}
+
+/// Trait for convolvable types.
+pub trait Convolve<P, C>
+	where P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	/// Convolve with the given `Kernel`.
+	///
+	/// The color channels are convolved, while the alpha is kept as is.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// image.convolve(&kernel::emboss().with_bias(0.5));
+	/// ```
+	fn convolve(self, kernel: &Kernel) -> Buffer<P, C, Vec<C>>;
+
+	/// Convolve with the given `Kernel`, handling the edges with the given
+	/// `EdgeMode`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::{read, EdgeMode};
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// image.convolve_with(&kernel::box_blur(2), EdgeMode::Mirror);
+	/// ```
+	fn convolve_with(self, kernel: &Kernel, edge: EdgeMode) -> Buffer<P, C, Vec<C>>;
+
+	/// Compute the gradient of the luminance with the given `Operator`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image    = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	/// let gradient = image.gradient(kernel::Operator::Sobel);
+	///
+	/// assert_eq!(gradient.magnitude.dimensions(), gradient.direction.dimensions());
+	/// ```
+	fn gradient(self, operator: Operator) -> Gradient;
+
+	/// Compute the gradient of the luminance with the given `Operator`,
+	/// handling the edges with the given `EdgeMode`.
+	fn gradient_with(self, operator: Operator, edge: EdgeMode) -> Gradient;
+}
+
+impl<'i, P, C, I> Convolve<P, C> for I
+	where P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+	      I: Into<view::Read<'i, P, C>>
+{
+	#[inline]
+	fn convolve(self, kernel: &Kernel) -> Buffer<P, C, Vec<C>> {
+		it::<_, P, C, P, C>(self, kernel, Default::default())
+	}
+
+	#[inline]
+	fn convolve_with(self, kernel: &Kernel, edge: EdgeMode) -> Buffer<P, C, Vec<C>> {
+		it::<_, P, C, P, C>(self, kernel, edge)
+	}
+
+	#[inline]
+	fn gradient(self, operator: Operator) -> Gradient {
+		gradient::<_, P, C>(self, operator, Default::default())
+	}
+
+	#[inline]
+	fn gradient_with(self, operator: Operator, edge: EdgeMode) -> Gradient {
+		gradient::<_, P, C>(self, operator, edge)
+	}
+}
+
+/// Convolve with the given `Kernel`, handling the edges with the given
+/// `EdgeMode`.
+///
+/// The color channels are convolved, while the alpha is kept as is.
+pub fn it<'i, I, PI, CI, PO, CO>(input: I, kernel: &Kernel, edge: EdgeMode) -> Buffer<PO, CO, Vec<CO>>
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	let     input  = input.into();
+	let     input  = &input;
+	let mut output = Buffer::<PO, CO, _>::new(input.width(), input.height());
+
+	let cx = (kernel.width() / 2) as i64;
+	let cy = (kernel.height() / 2) as i64;
+
+	match *kernel.weights() {
+		Weights::Full(ref weights) => {
+			for (x, y) in output.region().absolute() {
+				let mut t = (0.0, 0.0, 0.0);
+
+				for (i, &w) in weights.iter().enumerate() {
+					if w == 0.0 {
+						continue;
+					}
+
+					let kx = (i % kernel.width() as usize) as i64;
+					let ky = (i / kernel.width() as usize) as i64;
+					let p  = input.get_edge(x as i64 + kx - cx, y as i64 + ky - cy, edge);
+
+					t.0 += p.red * w;
+					t.1 += p.green * w;
+					t.2 += p.blue * w;
+				}
+
+				output.set(x, y, &finish(kernel, t, input.get(x, y).into().alpha).into());
+			}
+		}
+
+		Weights::Separable { ref horizontal, ref vertical } => {
+			// Keep the intermediate result as floats, since it's not clamped.
+			let mut tmp = Buffer::<Rgba, f32, _>::new(input.width(), input.height());
+
+			for (x, y) in tmp.region().absolute() {
+				let mut t = (0.0, 0.0, 0.0);
+
+				for (k, &w) in horizontal.iter().enumerate() {
+					let p = input.get_edge(x as i64 + k as i64 - cx, y as i64, edge);
+
+					t.0 += p.red * w;
+					t.1 += p.green * w;
+					t.2 += p.blue * w;
+				}
+
+				tmp.set(x, y, &Rgba::new(t.0, t.1, t.2, 1.0));
+			}
+
+			// A constant edge has to go through the horizontal pass as well.
+			let edge = if let EdgeMode::Constant(color) = edge {
+				let sum = horizontal.iter().sum::<f32>();
+				EdgeMode::Constant(Rgba::new(color.red * sum, color.green * sum, color.blue * sum, color.alpha))
+			}
+			else {
+				edge
+			};
+
+			for (x, y) in output.region().absolute() {
+				let mut t = (0.0, 0.0, 0.0);
+
+				for (k, &w) in vertical.iter().enumerate() {
+					let p = tmp.get_edge(x as i64, y as i64 + k as i64 - cy, edge);
+
+					t.0 += p.red * w;
+					t.1 += p.green * w;
+					t.2 += p.blue * w;
+				}
+
+				output.set(x, y, &finish(kernel, t, input.get(x, y).into().alpha).into());
+			}
+		}
+	}
+
+	output
+}
+
+/// Compute the gradient of the luminance with the given `Operator`, handling
+/// the edges with the given `EdgeMode`.
+pub fn gradient<'i, I, P, C>(input: I, operator: Operator, edge: EdgeMode) -> Gradient
+	where I: Into<view::Read<'i, P, C>>,
+	      P: Into<Rgba>,
+	      P: pixel::Read<C>,
+	      C: pixel::Channel,
+{
+	let     input     = input.into();
+	let     input     = &input;
+	let     (gx, gy)  = operator.kernels();
+	let mut magnitude = Buffer::<Luma, f32, _>::new(input.width(), input.height());
+	let mut direction = Buffer::<Luma, f32, _>::new(input.width(), input.height());
+
+	let luma = |x: i64, y: i64| {
+		let p = input.get_edge(x, y, edge);
+		0.2126 * p.red + 0.7152 * p.green + 0.0722 * p.blue
+	};
+
+	let cx = (gx.width() / 2) as i64;
+	let cy = (gx.height() / 2) as i64;
+
+	for (x, y) in magnitude.region().absolute() {
+		let mut h = 0.0;
+		let mut v = 0.0;
+
+		for ky in 0 .. gx.height() {
+			for kx in 0 .. gx.width() {
+				let l = luma(x as i64 + kx as i64 - cx, y as i64 + ky as i64 - cy);
+
+				h += l * gx.get(kx, ky);
+				v += l * gy.get(kx, ky);
+			}
+		}
+
+		magnitude.set(x, y, &Luma::new((h * h + v * v).sqrt()));
+		direction.set(x, y, &Luma::new(v.atan2(h)));
+	}
+
+	Gradient {
+		magnitude,
+		direction,
+	}
+}
+
+/// Apply divisor and bias to the weighted sum.
+#[inline]
+fn finish(kernel: &Kernel, (r, g, b): (f32, f32, f32), alpha: f32) -> Rgba {
+	let divisor = kernel.divisor();
+	let bias    = kernel.bias();
+
+	Rgba::new(r / divisor + bias, g / divisor + bias, b / divisor + bias, alpha).clamp()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use buffer;
+	use processing::kernel;
+
+	#[test]
+	fn identity() {
+		let input  = buffer::Rgba::from_fn(4, 4, |x, y| Rgba::new(x as f32 / 3.0, y as f32 / 3.0, 0.5, 1.0));
+		let output = input.convolve(&Kernel::from_raw(3, 3, vec![0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0]).unwrap());
+
+		assert_eq!(input, output);
+	}
+
+	#[test]
+	fn separable() {
+		let input = buffer::Rgba::from_fn(8, 8, |x, y| Rgba::new(((x * y) % 5) as f32 / 4.0, x as f32 / 7.0, 0.5, 1.0));
+		let full  = Kernel::from_raw(3, 3, vec![1.0; 9]).unwrap().normalize();
+
+		for &edge in &[EdgeMode::Clamp, EdgeMode::Wrap, EdgeMode::Mirror, EdgeMode::Constant(Rgba::new(1.0, 0.0, 0.0, 1.0))] {
+			let a = input.convolve_with(&full, edge);
+			let b = input.convolve_with(&kernel::box_blur(1), edge);
+
+			for ((_, _, a), (_, _, b)) in a.pixels().zip(b.pixels()) {
+				assert_relative_eq!(a.get(), b.get(), epsilon = 0.01);
+			}
+		}
+	}
+
+	#[test]
+	fn gradient() {
+		let input = buffer::Luma::from_fn(4, 4, |x, _| Luma::new(if x < 2 { 0.0 } else { 1.0 }));
+		let gradient = input.gradient(Operator::Sobel);
+
+		assert_relative_eq!(0.0, gradient.magnitude.get(0, 1).luma, epsilon = 0.001);
+		assert_relative_eq!(4.0, gradient.magnitude.get(1, 1).luma, epsilon = 0.001);
+		assert_relative_eq!(0.0, gradient.direction.get(1, 1).luma, epsilon = 0.001);
+	}
+}
@@ -0,0 +1,285 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use color::Rgba;
+use processing::srgb;
+use util::Shared;
+
+/// A color difference metric.
+///
+/// Colors are first converted to the space the metric works in, so the
+/// conversion can be done once when comparing many colors.
+///
+/// # Example
+///
+/// ```
+/// use picto::color::Rgba;
+/// use picto::processing::distance::{Distance, Euclidean, Ciede2000};
+///
+/// let gray  = Rgba::new(0.5, 0.5, 0.5, 1.0);
+/// let blue  = Rgba::new(0.5, 0.5, 0.6, 1.0);
+/// let green = Rgba::new(0.5, 0.6, 0.5, 1.0);
+///
+/// // The same distance in RGB, but the green is perceived farther.
+/// assert_eq!(Euclidean::distance(gray, blue), Euclidean::distance(gray, green));
+/// assert!(Ciede2000::distance(gray, blue) < Ciede2000::distance(gray, green));
+/// ```
+pub trait Distance {
+	/// The representation of a color in the metric space.
+	type Space: Copy + Shared;
+
+	/// Convert the color to the metric space, alpha is ignored.
+	fn convert(color: Rgba) -> Self::Space;
+
+	/// Get the distance between two converted colors.
+	fn between(a: Self::Space, b: Self::Space) -> f32;
+
+	/// Get the distance between two colors.
+	#[inline]
+	fn distance(a: Rgba, b: Rgba) -> f32 {
+		Self::between(Self::convert(a), Self::convert(b))
+	}
+}
+
+/// Euclidean distance between the RGB channels.
+pub struct Euclidean;
+
+/// Euclidean distance between the RGB channels, weighted by the amount of
+/// red to better match perception.
+pub struct Weighted;
+
+/// CIE 1976 difference, the Euclidean distance in `Lab`.
+pub struct Cie76;
+
+/// CIE 1994 difference, with the graphic arts weights, the first color is
+/// the reference.
+pub struct Cie94;
+
+/// CIEDE2000 difference.
+pub struct Ciede2000;
+
+impl Distance for Euclidean {
+	type Space = [f32; 3];
+
+	#[inline]
+	fn convert(color: Rgba) -> Self::Space {
+		[color.red, color.green, color.blue]
+	}
+
+	#[inline]
+	fn between(a: Self::Space, b: Self::Space) -> f32 {
+		((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+	}
+}
+
+impl Distance for Weighted {
+	type Space = [f32; 3];
+
+	#[inline]
+	fn convert(color: Rgba) -> Self::Space {
+		[color.red, color.green, color.blue]
+	}
+
+	#[inline]
+	fn between(a: Self::Space, b: Self::Space) -> f32 {
+		let red = (a[0] + b[0]) / 2.0;
+
+		((2.0 + red) * (a[0] - b[0]).powi(2) +
+		 4.0 * (a[1] - b[1]).powi(2) +
+		 (3.0 - red) * (a[2] - b[2]).powi(2)).sqrt()
+	}
+}
+
+impl Distance for Cie76 {
+	type Space = [f32; 3];
+
+	#[inline]
+	fn convert(color: Rgba) -> Self::Space {
+		lab(color)
+	}
+
+	#[inline]
+	fn between(a: Self::Space, b: Self::Space) -> f32 {
+		((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+	}
+}
+
+impl Distance for Cie94 {
+	type Space = [f32; 3];
+
+	#[inline]
+	fn convert(color: Rgba) -> Self::Space {
+		lab(color)
+	}
+
+	fn between(a: Self::Space, b: Self::Space) -> f32 {
+		let c1 = (a[1] * a[1] + a[2] * a[2]).sqrt();
+		let c2 = (b[1] * b[1] + b[2] * b[2]).sqrt();
+
+		let dl = a[0] - b[0];
+		let dc = c1 - c2;
+		let dh = ((a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2) - dc * dc).max(0.0);
+
+		let sc = 1.0 + 0.045 * c1;
+		let sh = 1.0 + 0.015 * c1;
+
+		(dl * dl + (dc / sc).powi(2) + dh / (sh * sh)).sqrt()
+	}
+}
+
+impl Distance for Ciede2000 {
+	type Space = [f32; 3];
+
+	#[inline]
+	fn convert(color: Rgba) -> Self::Space {
+		lab(color)
+	}
+
+	fn between(a: Self::Space, b: Self::Space) -> f32 {
+		let (l1, a1, b1) = (a[0] as f64, a[1] as f64, a[2] as f64);
+		let (l2, a2, b2) = (b[0] as f64, b[1] as f64, b[2] as f64);
+
+		let pow7 = |v: f64| v.powi(7);
+		let c    = ((a1 * a1 + b1 * b1).sqrt() + (a2 * a2 + b2 * b2).sqrt()) / 2.0;
+		let g    = 0.5 * (1.0 - (pow7(c) / (pow7(c) + pow7(25.0))).sqrt());
+
+		let (a1, a2) = ((1.0 + g) * a1, (1.0 + g) * a2);
+		let (c1, c2) = ((a1 * a1 + b1 * b1).sqrt(), (a2 * a2 + b2 * b2).sqrt());
+
+		let hue = |b: f64, a: f64| if a == 0.0 && b == 0.0 {
+			0.0
+		}
+		else {
+			let h = b.atan2(a).to_degrees();
+			if h < 0.0 { h + 360.0 } else { h }
+		};
+
+		let (h1, h2) = (hue(b1, a1), hue(b2, a2));
+
+		let dl = l2 - l1;
+		let dc = c2 - c1;
+		let dh = if c1 * c2 == 0.0 {
+			0.0
+		}
+		else if (h2 - h1).abs() <= 180.0 {
+			h2 - h1
+		}
+		else if h2 - h1 > 180.0 {
+			h2 - h1 - 360.0
+		}
+		else {
+			h2 - h1 + 360.0
+		};
+
+		let dh = 2.0 * (c1 * c2).sqrt() * (dh / 2.0).to_radians().sin();
+
+		let l = (l1 + l2) / 2.0;
+		let c = (c1 + c2) / 2.0;
+		let h = if c1 * c2 == 0.0 {
+			h1 + h2
+		}
+		else if (h1 - h2).abs() <= 180.0 {
+			(h1 + h2) / 2.0
+		}
+		else if h1 + h2 < 360.0 {
+			(h1 + h2 + 360.0) / 2.0
+		}
+		else {
+			(h1 + h2 - 360.0) / 2.0
+		};
+
+		let t = 1.0
+			- 0.17 * (h - 30.0).to_radians().cos()
+			+ 0.24 * (2.0 * h).to_radians().cos()
+			+ 0.32 * (3.0 * h + 6.0).to_radians().cos()
+			- 0.20 * (4.0 * h - 63.0).to_radians().cos();
+
+		let theta = 30.0 * (-((h - 275.0) / 25.0).powi(2)).exp();
+		let rc    = 2.0 * (pow7(c) / (pow7(c) + pow7(25.0))).sqrt();
+		let sl    = 1.0 + 0.015 * (l - 50.0).powi(2) / (20.0 + (l - 50.0).powi(2)).sqrt();
+		let sc    = 1.0 + 0.045 * c;
+		let sh    = 1.0 + 0.015 * c * t;
+		let rt    = -(2.0 * theta).to_radians().sin() * rc;
+
+		((dl / sl).powi(2) + (dc / sc).powi(2) + (dh / sh).powi(2) + rt * (dc / sc) * (dh / sh)).sqrt() as f32
+	}
+}
+
+/// Convert an sRGB encoded color to CIE `Lab` with the D65 white point, with
+/// the lightness going from `0.0` to `100.0`.
+pub fn lab(color: Rgba) -> [f32; 3] {
+	let r = srgb::to_linear(color.red);
+	let g = srgb::to_linear(color.green);
+	let b = srgb::to_linear(color.blue);
+
+	let x = (0.412_456_4 * r + 0.357_576_1 * g + 0.180_437_5 * b) / 0.950_47;
+	let y =  0.212_672_9 * r + 0.715_152_2 * g + 0.072_175_0 * b;
+	let z = (0.019_333_9 * r + 0.119_192 * g + 0.950_304_1 * b) / 1.088_83;
+
+	let f = |t: f32| if t > 216.0 / 24389.0 {
+		t.cbrt()
+	}
+	else {
+		t * 841.0 / 108.0 + 4.0 / 29.0
+	};
+
+	[116.0 * f(y) - 16.0, 500.0 * (f(x) - f(y)), 200.0 * (f(y) - f(z))]
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn lab() {
+		let red = super::lab(Rgba::new(1.0, 0.0, 0.0, 1.0));
+
+		assert!((red[0] - 53.24).abs() < 0.01);
+		assert!((red[1] - 80.09).abs() < 0.01);
+		assert!((red[2] - 67.20).abs() < 0.01);
+
+		let white = super::lab(Rgba::new(1.0, 1.0, 1.0, 1.0));
+		assert!((white[0] - 100.0).abs() < 0.01 && white[1].abs() < 0.01 && white[2].abs() < 0.01);
+	}
+
+	#[test]
+	fn ciede2000() {
+		// From the test data by Sharma, Wu and Dalal.
+		for &(a, b, expected) in &[
+			([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485], 2.0425),
+			([50.0, -1.3802, -84.2814], [50.0, 0.0, -82.7485], 1.0),
+			([50.0, 0.0, 0.0], [50.0, -1.0, 2.0], 2.3669),
+			([50.0, 2.49, -0.001], [50.0, -2.49, 0.0011], 7.2195),
+			([60.2574, -34.0099, 36.2677], [60.4626, -34.1751, 39.4387], 1.2644),
+			([22.7233, 20.0904, -46.6940], [23.0331, 14.9730, -42.5619], 2.0373),
+		] {
+			assert!((Ciede2000::between(a, b) - expected).abs() < 0.0001, "{:?} {:?}", a, b);
+			assert!((Ciede2000::between(b, a) - expected).abs() < 0.0001, "{:?} {:?}", b, a);
+		}
+	}
+
+	#[test]
+	fn cie94() {
+		assert!((Cie94::between([50.0, 2.6772, -79.7751], [50.0, 0.0, -82.7485]) - 1.3950).abs() < 0.0001);
+	}
+
+	#[test]
+	fn rgb() {
+		let black = Rgba::new(0.0, 0.0, 0.0, 1.0);
+		let white = Rgba::new(1.0, 1.0, 1.0, 0.0);
+
+		assert!((Euclidean::distance(black, white) - 3.0f32.sqrt()).abs() < 0.0001);
+		assert!((Weighted::distance(black, white) - 3.0).abs() < 0.0001);
+	}
+}
@@ -30,3 +30,6 @@ pub use self::neuquant::Good as NeuQuant;
 
 pub mod palette;
 pub use self::palette::Palette;
+
+pub mod quantizer;
+pub use self::quantizer::{Quantizer, MedianCut, Octree, Wu, KMeans};
@@ -13,7 +13,9 @@
 //  0. You just DO WHAT THE FUCK YOU WANT TO.
 
 use std::marker::PhantomData;
+use std::io::{Read, BufRead, BufReader};
 
+use error::{self, Error};
 use view;
 use buffer::{Buffer, cast};
 use pixel;
@@ -47,27 +49,263 @@ impl<T, M, PI, CI, PO, CO> super::Ditherer<PI, CI, PO, CO> for Palette<T, M>
 {
 	#[inline]
 	fn dither(input: &view::Read<PI, CI>, _colors: u32) -> Buffer<PO, CO, Vec<CO>> {
-		let mut buffer  = input.convert::<Rgba, u8>();
-		let     indices = M::indices(T::table(), &buffer);
+		map::<M, PI, CI, PO, CO>(T::table(), input)
+	}
+}
+
+/// A palette loaded at runtime and mapper pair.
+///
+/// # Example
+///
+/// ```
+/// use picto::read;
+/// use picto::color::{Rgb, Rgba};
+/// use picto::processing::ditherer::palette::{Colors, mapper};
+///
+/// let image   = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+/// let palette = Colors::<mapper::Atkinson>::from_colors(vec![
+///     Rgba::new(0.0, 0.0, 0.0, 1.0),
+///     Rgba::new(1.0, 0.0, 0.0, 1.0),
+///     Rgba::new(1.0, 1.0, 1.0, 1.0)]);
+///
+/// let dithered = palette.dither::<_, Rgb, u8>(&image);
+/// ```
+#[derive(Clone)]
+pub struct Colors<M: Mapper = mapper::FloydSteinberg> {
+	table:  Vec<Color>,
+	mapper: PhantomData<M>,
+}
+
+impl<M: Mapper> Colors<M> {
+	/// Create a palette from a raw table.
+	#[inline]
+	pub fn new(table: Vec<Color>) -> Self {
+		Colors {
+			table,
+			mapper: PhantomData,
+		}
+	}
+
+	/// Create a palette from the given colors.
+	#[inline]
+	pub fn from_colors(colors: Vec<Rgba>) -> Self {
+		Colors {
+			table:  colors.into_iter().map(|c| Color {
+				r: (c.red.clamp(0.0, 1.0) * 255.0).round() as u8,
+				g: (c.green.clamp(0.0, 1.0) * 255.0).round() as u8,
+				b: (c.blue.clamp(0.0, 1.0) * 255.0).round() as u8,
+				a: (c.alpha.clamp(0.0, 1.0) * 255.0).round() as u8,
+			}).collect(),
+
+			mapper: PhantomData,
+		}
+	}
+
+	/// Create a palette from a compile-time `Table`.
+	#[inline]
+	pub fn from_table<T: Table>() -> Self {
+		Colors {
+			table:  T::table().to_vec(),
+			mapper: PhantomData,
+		}
+	}
+
+	/// Load a GIMP palette (`.gpl`).
+	pub fn from_gpl<R: Read>(input: R) -> error::Result<Self> {
+		let mut lines = BufReader::new(input).lines();
+
+		if lines.next().transpose()?.as_ref().map(|l| l.trim()) != Some("GIMP Palette") {
+			return Err(Error::Format("missing GIMP Palette header".into()));
+		}
+
+		let mut table = Vec::new();
+
+		for line in lines {
+			let line = line?;
+			let line = line.trim();
+
+			if line.is_empty() || line.starts_with('#') || line.starts_with("Name:") || line.starts_with("Columns:") {
+				continue;
+			}
+
+			let channels = line.split_whitespace().take(3)
+				.map(|value| value.parse::<u8>())
+				.collect::<Result<Vec<u8>, _>>()
+				.map_err(|_| Error::Format("invalid color".into()))?;
+
+			if channels.len() != 3 {
+				return Err(Error::Format("invalid color".into()));
+			}
+
+			table.push(Color { r: channels[0], g: channels[1], b: channels[2], a: 0xff });
+		}
+
+		Ok(Colors::new(table))
+	}
+
+	/// Load a JASC palette (`.pal`).
+	pub fn from_jasc<R: Read>(input: R) -> error::Result<Self> {
+		let lines = BufReader::new(input).lines().collect::<Result<Vec<String>, _>>()?;
+		let mut lines = lines.iter().map(|l| l.trim()).filter(|l| !l.is_empty());
+
+		if lines.next() != Some("JASC-PAL") || lines.next() != Some("0100") {
+			return Err(Error::Format("missing JASC-PAL header".into()));
+		}
+
+		let count = lines.next()
+			.and_then(|l| l.parse::<usize>().ok())
+			.ok_or_else(|| Error::Format("invalid color count".into()))?;
+
+		let mut table = Vec::with_capacity(count);
+
+		for line in lines.take(count) {
+			let channels = line.split_whitespace()
+				.map(|value| value.parse::<u8>())
+				.collect::<Result<Vec<u8>, _>>()
+				.map_err(|_| Error::Format("invalid color".into()))?;
+
+			if channels.len() != 3 {
+				return Err(Error::Format("invalid color".into()));
+			}
+
+			table.push(Color { r: channels[0], g: channels[1], b: channels[2], a: 0xff });
+		}
+
+		if table.len() != count {
+			return Err(Error::Format("missing colors".into()));
+		}
+
+		Ok(Colors::new(table))
+	}
+
+	/// Load a list of hexadecimal colors, one per line as `RRGGBB` or
+	/// `RRGGBBAA` with an optional leading `#`.
+	///
+	/// Empty lines and lines starting with `;` are skipped.
+	pub fn from_hex<R: Read>(input: R) -> error::Result<Self> {
+		let mut table = Vec::new();
+
+		for line in BufReader::new(input).lines() {
+			let line = line?;
+			let line = line.trim();
+
+			if line.is_empty() || line.starts_with(';') {
+				continue;
+			}
+
+			let digits = line.trim_start_matches('#');
+
+			if (digits.len() != 6 && digits.len() != 8) || !digits.is_ascii() {
+				return Err(Error::Format("invalid color".into()));
+			}
+
+			let channels = (0 .. digits.len() / 2)
+				.map(|i| u8::from_str_radix(&digits[i * 2 .. i * 2 + 2], 16))
+				.collect::<Result<Vec<u8>, _>>()
+				.map_err(|_| Error::Format("invalid color".into()))?;
+
+			table.push(Color {
+				r: channels[0],
+				g: channels[1],
+				b: channels[2],
+				a: channels.get(3).cloned().unwrap_or(0xff),
+			});
+		}
+
+		Ok(Colors::new(table))
+	}
+
+	/// Use a different `Mapper`.
+	#[inline]
+	pub fn mapper<N: Mapper>(self) -> Colors<N> {
+		Colors {
+			table:  self.table,
+			mapper: PhantomData,
+		}
+	}
+
+	/// Get the colors.
+	#[inline]
+	pub fn colors(&self) -> Vec<Rgba> {
+		self.table.iter().map(|c| Rgba::new(
+			c.r as f32 / 255.0,
+			c.g as f32 / 255.0,
+			c.b as f32 / 255.0,
+			c.a as f32 / 255.0)).collect()
+	}
+
+	/// Get the raw table.
+	#[inline]
+	pub fn table(&self) -> &[Color] {
+		&self.table
+	}
 
-		for (output, index) in buffer.chunks_mut(4).zip(indices.into_iter()) {
-			let color = T::table()[index];
+	/// Get the number of colors.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.table.len()
+	}
+
+	/// Check if there are no colors.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.table.is_empty()
+	}
 
-			output[0] = color.r;
-			output[1] = color.g;
-			output[2] = color.b;
-			output[3] = color.a;
+	/// Map the input to the colors of the palette.
+	///
+	/// # Panics
+	///
+	/// Requires that the palette isn't empty, otherwise it will panic.
+	#[inline]
+	pub fn dither<'i, I, P, C>(&self, input: I) -> Buffer<P, C, Vec<C>>
+		where I: Into<view::Read<'i, P, C>>,
+		      P: From<Rgba> + Into<Rgba>,
+		      P: pixel::Read<C> + pixel::Write<C>,
+		      C: pixel::Channel,
+	{
+		if self.table.is_empty() {
+			panic!("empty palette");
 		}
 
-		cast::Into::<PO, CO>::into(buffer)
+		map::<M, P, C, P, C>(&self.table, &input.into())
+	}
+}
+
+/// Map the input to the colors in the table with the given `Mapper`.
+fn map<M, PI, CI, PO, CO>(table: &[Color], input: &view::Read<PI, CI>) -> Buffer<PO, CO, Vec<CO>>
+	where M:  Mapper,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      PO: From<Rgba> + Into<Rgba>,
+	      PO: pixel::Write<CO> + pixel::Read<CO>,
+	      CO: pixel::Channel
+{
+	let mut buffer  = input.convert::<Rgba, u8>();
+	let     indices = M::indices(table, &buffer);
+
+	for (output, index) in buffer.chunks_mut(4).zip(indices) {
+		let color = table[index];
+
+		output[0] = color.r;
+		output[1] = color.g;
+		output[2] = color.b;
+		output[3] = color.a;
 	}
+
+	cast::Into::<PO, CO>::into(buffer)
 }
 
 pub mod mapper {
 	use std::slice;
+	use std::marker::PhantomData;
 	use exoquant::{Color, SimpleColorSpace, Remapper, ditherer};
 	use buffer::Buffer;
 	use color::Rgba;
+	use processing::distance::{Distance, Euclidean};
+	use processing::parallel;
+	use util::Shared;
 
 	pub struct None;
 	pub struct Ordered;
@@ -117,6 +355,424 @@ pub mod mapper {
 			}
 		}
 	}
+
+	/// An error diffusion kernel.
+	pub trait Diffusion {
+		/// The offsets of the pixels the error is spread to, with their weight.
+		fn kernel() -> &'static [(i32, i32, u32)];
+
+		/// The amount the weights are divided by.
+		fn divisor() -> u32;
+	}
+
+	/// Map every pixel to the closest color by the given `Distance`, without
+	/// dithering.
+	pub struct Nearest<D: Distance = Euclidean>(PhantomData<D>);
+
+	impl<D: Distance> super::Mapper for Nearest<D> {
+		fn indices(table: &[Color], input: &Buffer<Rgba, u8, Vec<u8>>) -> Vec<usize> {
+			let table = convert::<D>(table);
+
+			rows(input, |_, _, px|
+				nearest::<D>(&table, [px[0] as f32, px[1] as f32, px[2] as f32, px[3] as f32]))
+		}
+	}
+
+	/// Error diffusion scanning the rows in alternating directions, reducing
+	/// directional artifacts.
+	pub struct Serpentine<K: Diffusion, D: Distance = Euclidean>(PhantomData<(K, D)>);
+
+	impl<K: Diffusion, D: Distance> super::Mapper for Serpentine<K, D> {
+		fn indices(table: &[Color], input: &Buffer<Rgba, u8, Vec<u8>>) -> Vec<usize> {
+			diffuse::<D>(table, input, K::kernel(), K::divisor(), true)
+		}
+	}
+
+	impl Diffusion for FloydSteinberg {
+		fn kernel() -> &'static [(i32, i32, u32)] {
+			&[(1, 0, 7), (-1, 1, 3), (0, 1, 5), (1, 1, 1)]
+		}
+
+		fn divisor() -> u32 {
+			16
+		}
+	}
+
+	macro_rules! diffusion {
+		($(#[$attr:meta])* $name:ident, $divisor:expr, $kernel:expr) => (
+			$(#[$attr])*
+			pub struct $name<D: Distance = Euclidean>(PhantomData<D>);
+
+			impl<D: Distance> Diffusion for $name<D> {
+				fn kernel() -> &'static [(i32, i32, u32)] {
+					&$kernel
+				}
+
+				fn divisor() -> u32 {
+					$divisor
+				}
+			}
+
+			impl<D: Distance> super::Mapper for $name<D> {
+				fn indices(table: &[Color], input: &Buffer<Rgba, u8, Vec<u8>>) -> Vec<usize> {
+					diffuse::<D>(table, input, Self::kernel(), Self::divisor(), false)
+				}
+			}
+		)
+	}
+
+	diffusion!(
+		/// Atkinson, spreading only three quarters of the error for higher
+		/// contrast.
+		Atkinson, 8, [
+			(1, 0, 1), (2, 0, 1),
+			(-1, 1, 1), (0, 1, 1), (1, 1, 1),
+			(0, 2, 1)]);
+
+	diffusion!(
+		/// Jarvis, Judice and Ninke.
+		JarvisJudiceNinke, 48, [
+			(1, 0, 7), (2, 0, 5),
+			(-2, 1, 3), (-1, 1, 5), (0, 1, 7), (1, 1, 5), (2, 1, 3),
+			(-2, 2, 1), (-1, 2, 3), (0, 2, 5), (1, 2, 3), (2, 2, 1)]);
+
+	diffusion!(
+		/// Stucki.
+		Stucki, 42, [
+			(1, 0, 8), (2, 0, 4),
+			(-2, 1, 2), (-1, 1, 4), (0, 1, 8), (1, 1, 4), (2, 1, 2),
+			(-2, 2, 1), (-1, 2, 2), (0, 2, 4), (1, 2, 2), (2, 2, 1)]);
+
+	diffusion!(
+		/// Burkes.
+		Burkes, 32, [
+			(1, 0, 8), (2, 0, 4),
+			(-2, 1, 2), (-1, 1, 4), (0, 1, 8), (1, 1, 4), (2, 1, 2)]);
+
+	diffusion!(
+		/// Sierra, spreading over three rows.
+		Sierra, 32, [
+			(1, 0, 5), (2, 0, 3),
+			(-2, 1, 2), (-1, 1, 4), (0, 1, 5), (1, 1, 4), (2, 1, 2),
+			(-1, 2, 2), (0, 2, 3), (1, 2, 2)]);
+
+	diffusion!(
+		/// Sierra, spreading over two rows.
+		TwoRowSierra, 16, [
+			(1, 0, 4), (2, 0, 3),
+			(-2, 1, 1), (-1, 1, 2), (0, 1, 3), (1, 1, 2), (2, 1, 1)]);
+
+	diffusion!(
+		/// Sierra Lite, spreading to the three closest pixels.
+		SierraLite, 4, [
+			(1, 0, 2),
+			(-1, 1, 1), (0, 1, 1)]);
+
+	/// Ordered dithering with a 2x2 Bayer matrix.
+	pub struct Bayer2<D: Distance = Euclidean>(PhantomData<D>);
+
+	/// Ordered dithering with a 4x4 Bayer matrix.
+	pub struct Bayer4<D: Distance = Euclidean>(PhantomData<D>);
+
+	/// Ordered dithering with an 8x8 Bayer matrix.
+	pub struct Bayer8<D: Distance = Euclidean>(PhantomData<D>);
+
+	/// Ordered dithering with a 32x32 blue noise matrix, avoiding the regular
+	/// patterns of the Bayer matrices.
+	pub struct BlueNoise<D: Distance = Euclidean>(PhantomData<D>);
+
+	impl<D: Distance> super::Mapper for Bayer2<D> {
+		fn indices(table: &[Color], input: &Buffer<Rgba, u8, Vec<u8>>) -> Vec<usize> {
+			ordered::<D>(table, input, &bayer(2), 2)
+		}
+	}
+
+	impl<D: Distance> super::Mapper for Bayer4<D> {
+		fn indices(table: &[Color], input: &Buffer<Rgba, u8, Vec<u8>>) -> Vec<usize> {
+			ordered::<D>(table, input, &bayer(4), 4)
+		}
+	}
+
+	impl<D: Distance> super::Mapper for Bayer8<D> {
+		fn indices(table: &[Color], input: &Buffer<Rgba, u8, Vec<u8>>) -> Vec<usize> {
+			ordered::<D>(table, input, &bayer(8), 8)
+		}
+	}
+
+	impl<D: Distance> super::Mapper for BlueNoise<D> {
+		fn indices(table: &[Color], input: &Buffer<Rgba, u8, Vec<u8>>) -> Vec<usize> {
+			ordered::<D>(table, input, &blue_noise(32), 32)
+		}
+	}
+
+	/// A color table converted to the space of a distance.
+	struct Table<D: Distance> {
+		colors: Vec<(D::Space, f32)>,
+		scale:  f32,
+	}
+
+	/// Convert the table to the space of the distance, keeping the alpha
+	/// aside.
+	///
+	/// The alpha difference is scaled so that going from opaque to transparent
+	/// weighs as much as going from black to white along a single channel.
+	fn convert<D: Distance>(table: &[Color]) -> Table<D> {
+		Table {
+			colors: table.iter().map(|c| (D::convert(rgba([c.r as f32, c.g as f32, c.b as f32, c.a as f32])), c.a as f32 / 255.0)).collect(),
+			scale:  D::distance(Rgba::new(0.0, 0.0, 0.0, 1.0), Rgba::new(1.0, 1.0, 1.0, 1.0)) / 3.0f32.sqrt(),
+		}
+	}
+
+	/// Get the index of the closest color in the converted table.
+	fn nearest<D: Distance>(table: &Table<D>, color: [f32; 4]) -> usize {
+		let     alpha = color[3] / 255.0;
+		let     color = D::convert(rgba(color));
+		let mut best  = (0, f32::MAX);
+
+		for (index, &(entry, a)) in table.colors.iter().enumerate() {
+			let distance = D::between(color, entry).powi(2) + ((alpha - a) * table.scale).powi(2);
+
+			if distance < best.1 {
+				best = (index, distance);
+			}
+		}
+
+		best.0
+	}
+
+	#[inline]
+	fn rgba(color: [f32; 4]) -> Rgba {
+		Rgba::new(color[0] / 255.0, color[1] / 255.0, color[2] / 255.0, color[3] / 255.0)
+	}
+
+	/// Map the pixels spreading the error of every pixel to its neighbours
+	/// with the given kernel.
+	fn diffuse<D: Distance>(colors: &[Color], input: &Buffer<Rgba, u8, Vec<u8>>, kernel: &[(i32, i32, u32)], divisor: u32, serpentine: bool) -> Vec<usize> {
+		let     table   = convert::<D>(colors);
+		let     width   = input.width() as i64;
+		let     height  = input.height() as i64;
+		let mut values  = input.iter().map(|&v| v as f32).collect::<Vec<f32>>();
+		let mut indices = vec![0; (width * height) as usize];
+
+		for y in 0 .. height {
+			let reverse = serpentine && y % 2 == 1;
+
+			for x in 0 .. width {
+				let x      = if reverse { width - 1 - x } else { x };
+				let offset = (y * width + x) as usize;
+				let mut color = [0.0; 4];
+
+				for (c, value) in color.iter_mut().enumerate() {
+					*value = values[offset * 4 + c].clamp(0.0, 255.0);
+				}
+
+				let index = nearest::<D>(&table, color);
+				let entry = colors[index];
+				let error = [
+					color[0] - entry.r as f32,
+					color[1] - entry.g as f32,
+					color[2] - entry.b as f32,
+					color[3] - entry.a as f32];
+
+				indices[offset] = index;
+
+				for &(dx, dy, weight) in kernel {
+					let nx = if reverse { x - dx as i64 } else { x + dx as i64 };
+					let ny = y + dy as i64;
+
+					if nx < 0 || nx >= width || ny >= height {
+						continue;
+					}
+
+					let neighbour = (ny * width + nx) as usize;
+					let factor    = weight as f32 / divisor as f32;
+
+					for (c, error) in error.iter().enumerate() {
+						values[neighbour * 4 + c] += error * factor;
+					}
+				}
+			}
+		}
+
+		indices
+	}
+
+	/// Map the pixels after offsetting them by the threshold matrix, scaled by
+	/// the distance between the colors in the table.
+	fn ordered<D: Distance>(table: &[Color], input: &Buffer<Rgba, u8, Vec<u8>>, matrix: &[f32], size: usize) -> Vec<usize> {
+		let spread    = spread(table);
+		let converted = convert::<D>(table);
+
+		rows(input, |x, y, px| {
+			let threshold = matrix[(y % size) * size + (x % size)] - 0.5;
+
+			nearest::<D>(&converted, [
+				(px[0] as f32 + spread * threshold).clamp(0.0, 255.0),
+				(px[1] as f32 + spread * threshold).clamp(0.0, 255.0),
+				(px[2] as f32 + spread * threshold).clamp(0.0, 255.0),
+				px[3] as f32])
+		})
+	}
+
+	/// Map every pixel independently with its coordinates, a row at a time.
+	fn rows<F>(input: &Buffer<Rgba, u8, Vec<u8>>, func: F) -> Vec<usize>
+		where F: Fn(usize, usize, &[u8]) -> usize + Shared
+	{
+		let width = input.width() as usize;
+
+		if width == 0 {
+			return Vec::new();
+		}
+
+		parallel::map(input.chunks(width * 4).collect(), |y, row: &[u8]|
+			row.chunks(4).enumerate().map(|(x, px)| func(x, y, px)).collect::<Vec<usize>>()).concat()
+	}
+
+	/// Get the average distance between every color in the table and the
+	/// closest other one, as change on every channel.
+	fn spread(table: &[Color]) -> f32 {
+		if table.len() < 2 {
+			return 0.0;
+		}
+
+		let sum = table.iter().enumerate().map(|(i, a)| {
+			table.iter().enumerate().filter(|&(j, _)| i != j).map(|(_, b)| {
+				((a.r as f32 - b.r as f32).powi(2) +
+				 (a.g as f32 - b.g as f32).powi(2) +
+				 (a.b as f32 - b.b as f32).powi(2)).sqrt()
+			}).fold(f32::MAX, f32::min)
+		}).sum::<f32>();
+
+		sum / table.len() as f32 / 3.0f32.sqrt()
+	}
+
+	/// Build the Bayer matrix of the given power of two size, with thresholds
+	/// between `0.0` and `1.0`.
+	fn bayer(size: usize) -> Vec<f32> {
+		let mut matrix = vec![0u32];
+		let mut side   = 1;
+
+		while side < size {
+			let mut next = vec![0; side * side * 4];
+
+			for y in 0 .. side * 2 {
+				for x in 0 .. side * 2 {
+					let base = matrix[(y % side) * side + (x % side)] * 4;
+
+					next[y * side * 2 + x] = base + match (x / side, y / side) {
+						(0, 0) => 0,
+						(1, 0) => 2,
+						(0, _) => 3,
+						_      => 1,
+					};
+				}
+			}
+
+			matrix = next;
+			side  *= 2;
+		}
+
+		let count = (size * size) as f32;
+		matrix.into_iter().map(|rank| (rank as f32 + 0.5) / count).collect()
+	}
+
+	/// Build a blue noise matrix of the given size with the void-and-cluster
+	/// method, with thresholds between `0.0` and `1.0`.
+	fn blue_noise(size: usize) -> Vec<f32> {
+		let count = size * size;
+
+		// The energy contributed by a pixel, wrapping around the edges.
+		let filter = (0 .. count).map(|i| {
+			let dx = (i % size).min(size - i % size) as f32;
+			let dy = (i / size).min(size - i / size) as f32;
+
+			(-(dx * dx + dy * dy) / (2.0 * 1.5 * 1.5)).exp()
+		}).collect::<Vec<f32>>();
+
+		let update = |energy: &mut [f32], at: usize, sign: f32| {
+			let (ax, ay) = (at % size, at / size);
+
+			for (i, value) in energy.iter_mut().enumerate() {
+				let dx = (i % size + size - ax) % size;
+				let dy = (i / size + size - ay) % size;
+
+				*value += sign * filter[dy * size + dx];
+			}
+		};
+
+		// The tightest cluster among the set pixels, or the largest void among
+		// the unset ones.
+		let find = |pattern: &[bool], energy: &[f32], set: bool| {
+			let mut best = (0, if set { f32::MIN } else { f32::MAX });
+
+			for (i, &value) in energy.iter().enumerate() {
+				if pattern[i] == set && ((set && value > best.1) || (!set && value < best.1)) {
+					best = (i, value);
+				}
+			}
+
+			best.0
+		};
+
+		// A deterministic sparse random pattern to start from.
+		let mut pattern = vec![false; count];
+		let mut energy  = vec![0.0; count];
+		let mut state   = 0x2545f491u32;
+
+		for _ in 0 .. count / 10 {
+			state ^= state << 13;
+			state ^= state >> 17;
+			state ^= state << 5;
+
+			let at = state as usize % count;
+
+			if !pattern[at] {
+				pattern[at] = true;
+				update(&mut energy, at, 1.0);
+			}
+		}
+
+		// Spread the starting pattern evenly.
+		loop {
+			let cluster = find(&pattern, &energy, true);
+			pattern[cluster] = false;
+			update(&mut energy, cluster, -1.0);
+
+			let void = find(&pattern, &energy, false);
+			pattern[void] = true;
+			update(&mut energy, void, 1.0);
+
+			if void == cluster {
+				break;
+			}
+		}
+
+		let mut ranks = vec![0; count];
+		let     ones  = pattern.iter().filter(|&&set| set).count();
+
+		// Rank the starting pattern by removing its tightest clusters.
+		{
+			let mut pattern = pattern.clone();
+			let mut energy  = energy.clone();
+
+			for rank in (0 .. ones).rev() {
+				let cluster = find(&pattern, &energy, true);
+				pattern[cluster] = false;
+				update(&mut energy, cluster, -1.0);
+				ranks[cluster] = rank;
+			}
+		}
+
+		// Rank the rest by filling the largest voids.
+		for rank in ones .. count {
+			let void = find(&pattern, &energy, false);
+			pattern[void] = true;
+			update(&mut energy, void, 1.0);
+			ranks[void] = rank;
+		}
+
+		ranks.into_iter().map(|rank| (rank as f32 + 0.5) / count as f32).collect()
+	}
 }
 
 pub mod table {
@@ -751,3 +1407,114 @@ pub mod table {
 		}
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use buffer;
+	use color::{Luma, Rgb, Rgba};
+	use processing::Dither;
+
+	fn whites<M: Mapper>() -> usize {
+		let input  = buffer::Luma::from_pixel(16, 16, &Luma::new(0.5));
+		let output = input.dither::<Palette<table::Gray1, M>>(2);
+
+		output.iter().filter(|&&v| v == 255).count()
+	}
+
+	#[test]
+	fn diffusion() {
+		for &count in &[
+			whites::<mapper::Atkinson>(),
+			whites::<mapper::JarvisJudiceNinke>(),
+			whites::<mapper::Stucki>(),
+			whites::<mapper::Burkes>(),
+			whites::<mapper::Sierra>(),
+			whites::<mapper::TwoRowSierra>(),
+			whites::<mapper::SierraLite>(),
+			whites::<mapper::Serpentine<mapper::FloydSteinberg>>(),
+			whites::<mapper::Serpentine<mapper::Stucki>>(),
+		] {
+			assert!(count > 112 && count < 144, "{} whites", count);
+		}
+	}
+
+	#[test]
+	fn gpl() {
+		let palette = Colors::<mapper::None>::from_gpl(&b"GIMP Palette
+Name: Test
+Columns: 2
+#
+  0   0   0\tBlack
+255 128   0\tOrange
+"[..]).unwrap();
+
+		assert_eq!(vec![Rgba::new(0.0, 0.0, 0.0, 1.0), Rgba::new(1.0, 128.0 / 255.0, 0.0, 1.0)],
+			palette.colors());
+
+		assert!(Colors::<mapper::None>::from_gpl(&b"0 0 0"[..]).is_err());
+	}
+
+	#[test]
+	fn jasc() {
+		let palette = Colors::<mapper::None>::from_jasc(&b"JASC-PAL\r\n0100\r\n2\r\n0 0 0\r\n255 128 0\r\n"[..]).unwrap();
+
+		assert_eq!(vec![Rgba::new(0.0, 0.0, 0.0, 1.0), Rgba::new(1.0, 128.0 / 255.0, 0.0, 1.0)],
+			palette.colors());
+
+		assert!(Colors::<mapper::None>::from_jasc(&b"JASC-PAL\n0100\n3\n0 0 0\n"[..]).is_err());
+	}
+
+	#[test]
+	fn hex() {
+		let palette = Colors::<mapper::None>::from_hex(&b"; comment\n#000000\nff8000\n\nffffff80\n"[..]).unwrap();
+
+		assert_eq!(vec![
+			Rgba::new(0.0, 0.0, 0.0, 1.0),
+			Rgba::new(1.0, 128.0 / 255.0, 0.0, 1.0),
+			Rgba::new(1.0, 1.0, 1.0, 128.0 / 255.0),
+		], palette.colors());
+
+		assert!(Colors::<mapper::None>::from_hex(&b"#fff"[..]).is_err());
+	}
+
+	#[test]
+	fn runtime() {
+		let input   = buffer::Rgb::from_pixel(4, 4, &Rgb::new(0.9, 0.1, 0.1));
+		let palette = Colors::<mapper::Bayer4>::from_colors(vec![
+			Rgba::new(0.0, 0.0, 0.0, 1.0),
+			Rgba::new(1.0, 0.0, 0.0, 1.0)]);
+
+		let output = palette.dither(&input);
+		assert_eq!(Rgb::new(1.0, 0.0, 0.0), output.get(0, 0));
+
+		let output = palette.mapper::<mapper::Atkinson>().dither(&input);
+		assert!(output.pixels().all(|(_, _, p)| p.get().green == 0.0));
+	}
+
+	#[test]
+	fn ordered() {
+		assert_eq!(128, whites::<mapper::Bayer2>());
+		assert_eq!(128, whites::<mapper::Bayer4>());
+		assert_eq!(128, whites::<mapper::Bayer8>());
+
+		// Only a part of the blue noise matrix is covered.
+		let count = whites::<mapper::BlueNoise>();
+		assert!(count > 112 && count < 144, "{} whites", count);
+	}
+
+	#[test]
+	fn distance() {
+		use processing::distance::{Euclidean, Ciede2000};
+
+		// Closer in RGB to the blue, but perceived closer to the gray.
+		let input   = buffer::Rgb::from_raw(1, 1, vec![100, 100, 100]).unwrap();
+		let table   = || vec![Color::new(100, 100, 130, 255), Color::new(120, 120, 120, 255)];
+		let blue    = Rgb::new(100.0 / 255.0, 100.0 / 255.0, 130.0 / 255.0);
+		let gray    = Rgb::new(120.0 / 255.0, 120.0 / 255.0, 120.0 / 255.0);
+
+		assert_eq!(blue, Colors::<mapper::Nearest<Euclidean>>::new(table()).dither(&input).get(0, 0));
+		assert_eq!(gray, Colors::<mapper::Nearest<Ciede2000>>::new(table()).dither(&input).get(0, 0));
+		assert_eq!(gray, Colors::<mapper::Serpentine<mapper::FloydSteinberg, Ciede2000>>::new(table()).dither(&input).get(0, 0));
+	}
+}
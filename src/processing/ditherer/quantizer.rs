@@ -0,0 +1,640 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::collections::HashMap;
+
+use view;
+use buffer::{Buffer, cast};
+use pixel;
+use color::Rgba;
+use super::palette::{Color, Colors, mapper};
+
+/// A color quantizer, extracting a palette representing an image.
+pub trait Quantizer {
+	/// Extract a palette of at most the given number of colors.
+	fn palette(input: &Buffer<Rgba, u8, Vec<u8>>, colors: u32) -> Vec<Color>;
+}
+
+/// Median cut, splitting the box of colors with the widest range at its
+/// median until there are enough boxes.
+pub struct MedianCut;
+
+/// Octree, merging the least used branches of the tree of colors.
+pub struct Octree;
+
+/// Xiaolin Wu's quantizer, splitting boxes of colors to minimize their
+/// variance.
+pub struct Wu;
+
+/// K-means, refining the median cut palette by moving every color to the
+/// mean of the pixels closest to it.
+pub struct KMeans;
+
+macro_rules! ditherer {
+	($name:ident) => (
+		impl<PI, CI, PO, CO> super::Ditherer<PI, CI, PO, CO> for $name
+			where PI: Into<Rgba>,
+			      PI: pixel::Read<CI>,
+			      CI: pixel::Channel,
+			      PO: From<Rgba> + Into<Rgba> + From<PI>,
+			      PO: pixel::Write<CO> + pixel::Read<CO>,
+			      CO: pixel::Channel
+		{
+			#[inline]
+			fn dither(input: &view::Read<PI, CI>, colors: u32) -> Buffer<PO, CO, Vec<CO>> {
+				quantize::<$name, _, PI, CI, PO, CO>(input, colors).0
+			}
+		}
+	)
+}
+
+ditherer!(MedianCut);
+ditherer!(Octree);
+ditherer!(Wu);
+ditherer!(KMeans);
+
+/// Reduce the input to the palette extracted by the quantizer, returning
+/// both.
+///
+/// # Example
+///
+/// ```
+/// use picto::read;
+/// use picto::color::Rgb;
+/// use picto::processing::prelude::*;
+///
+/// let image            = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+/// let (image, palette) = ditherer::quantizer::quantize::<ditherer::quantizer::Wu, _, Rgb, u8, Rgb, u8>(&image, 16);
+///
+/// assert!(palette.len() <= 16);
+/// ```
+pub fn quantize<'i, Q, I, PI, CI, PO, CO>(input: I, colors: u32) -> (Buffer<PO, CO, Vec<CO>>, Colors<mapper::None>)
+	where Q:  Quantizer,
+	      I:  Into<view::Read<'i, PI, CI>>,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      PO: From<Rgba> + Into<Rgba>,
+	      PO: pixel::Write<CO> + pixel::Read<CO>,
+	      CO: pixel::Channel
+{
+	let buffer  = input.into().convert::<Rgba, u8>();
+	let palette = Colors::<mapper::None>::new(Q::palette(&buffer, colors.max(1)));
+	let output  = if palette.is_empty() {
+		buffer
+	}
+	else {
+		palette.dither(&buffer)
+	};
+
+	(cast::Into::<PO, CO>::into(output), palette)
+}
+
+impl Quantizer for MedianCut {
+	fn palette(input: &Buffer<Rgba, u8, Vec<u8>>, colors: u32) -> Vec<Color> {
+		median_cut(&histogram(input), colors as usize)
+	}
+}
+
+impl Quantizer for Octree {
+	fn palette(input: &Buffer<Rgba, u8, Vec<u8>>, colors: u32) -> Vec<Color> {
+		octree(&histogram(input), colors as usize)
+	}
+}
+
+impl Quantizer for Wu {
+	fn palette(input: &Buffer<Rgba, u8, Vec<u8>>, colors: u32) -> Vec<Color> {
+		wu(input, colors as usize)
+	}
+}
+
+impl Quantizer for KMeans {
+	fn palette(input: &Buffer<Rgba, u8, Vec<u8>>, colors: u32) -> Vec<Color> {
+		let histogram = histogram(input);
+		k_means(&histogram, median_cut(&histogram, colors as usize), 16)
+	}
+}
+
+/// A distinct color and how many pixels have it.
+type Entry = ([u8; 4], u64);
+
+/// Get the distinct colors in the image.
+fn histogram(input: &Buffer<Rgba, u8, Vec<u8>>) -> Vec<Entry> {
+	let mut counts = HashMap::new();
+
+	for px in input.chunks(4) {
+		*counts.entry([px[0], px[1], px[2], px[3]]).or_insert(0u64) += 1;
+	}
+
+	let mut entries = counts.into_iter().collect::<Vec<_>>();
+	entries.sort();
+	entries
+}
+
+/// Get the weighted mean of the colors.
+fn mean(entries: &[Entry]) -> Color {
+	let mut sum   = [0u64; 4];
+	let mut total = 0;
+
+	for &(color, count) in entries {
+		for (s, &c) in sum.iter_mut().zip(color.iter()) {
+			*s += c as u64 * count;
+		}
+
+		total += count;
+	}
+
+	average(sum, total)
+}
+
+/// Get the rounded color from the channel sums and their weight.
+fn average(sum: [u64; 4], count: u64) -> Color {
+	let count = count.max(1);
+	let round = |s: u64| ((s + count / 2) / count) as u8;
+
+	Color { r: round(sum[0]), g: round(sum[1]), b: round(sum[2]), a: round(sum[3]) }
+}
+
+fn median_cut(entries: &[Entry], colors: usize) -> Vec<Color> {
+	let mut boxes = vec![entries.to_vec()];
+
+	while boxes.len() < colors {
+		// The box with the widest range on a channel, and the channel.
+		let widest = boxes.iter().enumerate().filter(|&(_, b)| b.len() > 1).map(|(i, b)| {
+			let (channel, range) = (0 .. 4).map(|c| {
+				let min = b.iter().map(|e| e.0[c]).min().unwrap_or(0);
+				let max = b.iter().map(|e| e.0[c]).max().unwrap_or(0);
+
+				(c, max - min)
+			}).max_by_key(|&(c, range)| (range, 4 - c)).unwrap();
+
+			(i, channel, range)
+		}).max_by_key(|&(i, _, range)| (range, boxes.len() - i));
+
+		let (index, channel, _) = match widest {
+			Some(widest) => widest,
+			None         => break,
+		};
+
+		let mut current = boxes.swap_remove(index);
+		current.sort_by_key(|e| e.0[channel]);
+
+		// Split at the weighted median, keeping both halves non-empty.
+		let total  = current.iter().map(|e| e.1).sum::<u64>();
+		let mut at = 0;
+		let mut sum = 0;
+
+		while at < current.len() - 1 && (sum + current[at].1) * 2 <= total {
+			sum += current[at].1;
+			at  += 1;
+		}
+
+		let upper = current.split_off(at.max(1));
+		boxes.push(current);
+		boxes.push(upper);
+	}
+
+	boxes.iter().filter(|b| !b.is_empty()).map(|b| mean(b)).collect()
+}
+
+fn octree(entries: &[Entry], colors: usize) -> Vec<Color> {
+	struct Node {
+		children: [Option<usize>; 8],
+		sum:      [u64; 4],
+		count:    u64,
+		leaf:     bool,
+	}
+
+	let mut nodes  = vec![Node { children: [None; 8], sum: [0; 4], count: 0, leaf: false }];
+	let mut levels = vec![Vec::new(); 8];
+	let mut leaves = 0;
+
+	for &(color, count) in entries {
+		let mut node = 0;
+
+		for level in 0 .. 8 {
+			let branch = (((color[0] >> (7 - level)) & 1) << 2 |
+			              ((color[1] >> (7 - level)) & 1) << 1 |
+			              ((color[2] >> (7 - level)) & 1)) as usize;
+
+			node = match nodes[node].children[branch] {
+				Some(child) => child,
+
+				None => {
+					let child = nodes.len();
+					nodes.push(Node { children: [None; 8], sum: [0; 4], count: 0, leaf: level == 7 });
+					nodes[node].children[branch] = Some(child);
+
+					if level == 7 {
+						leaves += 1;
+					}
+					else {
+						levels[level + 1].push(child);
+					}
+
+					child
+				}
+			};
+		}
+
+		for (s, &c) in nodes[node].sum.iter_mut().zip(color.iter()) {
+			*s += c as u64 * count;
+		}
+
+		nodes[node].count += count;
+	}
+
+	// Merge the deepest and least used branches into their parent, the root
+	// is never merged since it would leave a single color.
+	for level in (1 .. 8).rev() {
+		if leaves <= colors {
+			break;
+		}
+
+		let mut candidates = levels[level].iter().map(|&index| {
+			let count = nodes[index].children.iter().filter_map(|&c| c).map(|c| nodes[c].count).sum::<u64>();
+			(count, index)
+		}).collect::<Vec<_>>();
+
+		candidates.sort();
+
+		for (_, index) in candidates {
+			if leaves <= colors {
+				break;
+			}
+
+			let children = nodes[index].children;
+			let mut merged = 0;
+
+			for child in children.iter().filter_map(|&c| c) {
+				let (sum, count) = (nodes[child].sum, nodes[child].count);
+
+				for (s, c) in nodes[index].sum.iter_mut().zip(sum.iter()) {
+					*s += c;
+				}
+
+				nodes[index].count += count;
+				merged += 1;
+			}
+
+			nodes[index].children = [None; 8];
+			nodes[index].leaf     = true;
+			leaves = leaves + 1 - merged;
+		}
+	}
+
+	// Collect the leaves still reachable from the root, keeping the most used
+	// ones if there are still too many.
+	let mut found = Vec::new();
+	let mut stack = vec![0];
+
+	while let Some(index) = stack.pop() {
+		let node = &nodes[index];
+
+		if node.leaf {
+			if node.count > 0 {
+				found.push(index);
+			}
+		}
+		else {
+			stack.extend(node.children.iter().filter_map(|&c| c));
+		}
+	}
+
+	found.sort_by_key(|&index| (u64::MAX - nodes[index].count, index));
+	found.truncate(colors);
+
+	found.into_iter().map(|index| average(nodes[index].sum, nodes[index].count)).collect()
+}
+
+/// The size of the moment tables, a bin for every 5 bit value plus one.
+const SIDE: usize = 33;
+
+/// A box within the moment tables, exclusive of the lower bounds.
+#[derive(Copy, Clone, Default)]
+struct Cube {
+	r0: usize, r1: usize,
+	g0: usize, g1: usize,
+	b0: usize, b1: usize,
+}
+
+/// The moments of a set of colors.
+struct Moments {
+	weight: Vec<f64>,
+	red:    Vec<f64>,
+	green:  Vec<f64>,
+	blue:   Vec<f64>,
+	alpha:  Vec<f64>,
+	square: Vec<f64>,
+}
+
+#[inline]
+fn at(r: usize, g: usize, b: usize) -> usize {
+	(r * SIDE + g) * SIDE + b
+}
+
+/// Sum the moment over the cube.
+fn volume(cube: &Cube, m: &[f64]) -> f64 {
+	m[at(cube.r1, cube.g1, cube.b1)] - m[at(cube.r1, cube.g1, cube.b0)] -
+	m[at(cube.r1, cube.g0, cube.b1)] + m[at(cube.r1, cube.g0, cube.b0)] -
+	m[at(cube.r0, cube.g1, cube.b1)] + m[at(cube.r0, cube.g1, cube.b0)] +
+	m[at(cube.r0, cube.g0, cube.b1)] - m[at(cube.r0, cube.g0, cube.b0)]
+}
+
+/// Sum the moment over the lower part of the cube, up to the given position
+/// on the axis.
+fn top(cube: &Cube, axis: usize, position: usize, m: &[f64]) -> f64 {
+	let mut half = *cube;
+
+	match axis {
+		0 => half.r1 = position,
+		1 => half.g1 = position,
+		_ => half.b1 = position,
+	}
+
+	volume(&half, m)
+}
+
+fn variance(cube: &Cube, moments: &Moments) -> f64 {
+	let r = volume(cube, &moments.red);
+	let g = volume(cube, &moments.green);
+	let b = volume(cube, &moments.blue);
+	let w = volume(cube, &moments.weight);
+
+	if w <= 0.0 {
+		0.0
+	}
+	else {
+		volume(cube, &moments.square) - (r * r + g * g + b * b) / w
+	}
+}
+
+/// Find the position on the axis maximizing the variance between the two
+/// halves of the cube.
+fn maximize(cube: &Cube, axis: usize, moments: &Moments, whole: [f64; 4]) -> (f64, Option<usize>) {
+	let (first, last) = match axis {
+		0 => (cube.r0 + 1, cube.r1),
+		1 => (cube.g0 + 1, cube.g1),
+		_ => (cube.b0 + 1, cube.b1),
+	};
+
+	let mut best = (0.0, None);
+
+	for position in first .. last {
+		let half = [
+			top(cube, axis, position, &moments.red),
+			top(cube, axis, position, &moments.green),
+			top(cube, axis, position, &moments.blue),
+			top(cube, axis, position, &moments.weight)];
+
+		if half[3] <= 0.0 || whole[3] - half[3] <= 0.0 {
+			continue;
+		}
+
+		let rest  = [whole[0] - half[0], whole[1] - half[1], whole[2] - half[2], whole[3] - half[3]];
+		let value = (half[0] * half[0] + half[1] * half[1] + half[2] * half[2]) / half[3] +
+		            (rest[0] * rest[0] + rest[1] * rest[1] + rest[2] * rest[2]) / rest[3];
+
+		if value > best.0 {
+			best = (value, Some(position));
+		}
+	}
+
+	best
+}
+
+/// Split the cube in two along the best axis, returning the upper half.
+fn cut(cube: &mut Cube, moments: &Moments) -> Option<Cube> {
+	let whole = [
+		volume(cube, &moments.red),
+		volume(cube, &moments.green),
+		volume(cube, &moments.blue),
+		volume(cube, &moments.weight)];
+
+	let (axis, (_, position)) = (0 .. 3)
+		.map(|axis| (axis, maximize(cube, axis, moments, whole)))
+		.fold((0, (0.0, None)), |best, current|
+			if (current.1).1.is_some() && (current.1).0 > (best.1).0 { current } else { best });
+
+	let position  = position?;
+	let mut upper = *cube;
+
+	match axis {
+		0 => { upper.r0 = position; cube.r1 = position; }
+		1 => { upper.g0 = position; cube.g1 = position; }
+		_ => { upper.b0 = position; cube.b1 = position; }
+	}
+
+	Some(upper)
+}
+
+fn wu(input: &Buffer<Rgba, u8, Vec<u8>>, colors: usize) -> Vec<Color> {
+	let size        = SIDE * SIDE * SIDE;
+	let mut moments = Moments {
+		weight: vec![0.0; size],
+		red:    vec![0.0; size],
+		green:  vec![0.0; size],
+		blue:   vec![0.0; size],
+		alpha:  vec![0.0; size],
+		square: vec![0.0; size],
+	};
+
+	for px in input.chunks(4) {
+		let index = at((px[0] >> 3) as usize + 1, (px[1] >> 3) as usize + 1, (px[2] >> 3) as usize + 1);
+		let (r, g, b, a) = (px[0] as f64, px[1] as f64, px[2] as f64, px[3] as f64);
+
+		moments.weight[index] += 1.0;
+		moments.red[index]    += r;
+		moments.green[index]  += g;
+		moments.blue[index]   += b;
+		moments.alpha[index]  += a;
+		moments.square[index] += r * r + g * g + b * b;
+	}
+
+	// Turn the moments cumulative, so any box can be summed from its corners.
+	for m in &mut [&mut moments.weight, &mut moments.red, &mut moments.green,
+	               &mut moments.blue, &mut moments.alpha, &mut moments.square]
+	{
+		for r in 1 .. SIDE {
+			let mut area = [0.0; SIDE];
+
+			for g in 1 .. SIDE {
+				let mut line = 0.0;
+
+				for b in 1 .. SIDE {
+					line    += m[at(r, g, b)];
+					area[b] += line;
+					m[at(r, g, b)] = m[at(r - 1, g, b)] + area[b];
+				}
+			}
+		}
+	}
+
+	let mut cubes     = vec![Cube { r1: SIDE - 1, g1: SIDE - 1, b1: SIDE - 1, .. Default::default() }];
+	let mut variances = vec![0.0];
+	let mut next      = 0;
+
+	while cubes.len() < colors {
+		match cut(&mut cubes[next], &moments) {
+			Some(upper) => {
+				let lower = cubes[next];
+
+				variances[next] = if volume(&lower, &moments.weight) > 1.0 { variance(&lower, &moments) } else { 0.0 };
+				variances.push(if volume(&upper, &moments.weight) > 1.0 { variance(&upper, &moments) } else { 0.0 });
+				cubes.push(upper);
+			}
+
+			None =>
+				variances[next] = 0.0,
+		}
+
+		let (index, &value) = variances.iter().enumerate()
+			.fold((0, &0.0), |best, current| if current.1 > best.1 { current } else { best });
+
+		if value <= 0.0 {
+			break;
+		}
+
+		next = index;
+	}
+
+	cubes.iter().filter_map(|cube| {
+		let weight = volume(cube, &moments.weight);
+
+		if weight <= 0.0 {
+			return None;
+		}
+
+		let channel = |m: &[f64]| (volume(cube, m) / weight).round().clamp(0.0, 255.0) as u8;
+
+		Some(Color {
+			r: channel(&moments.red),
+			g: channel(&moments.green),
+			b: channel(&moments.blue),
+			a: channel(&moments.alpha),
+		})
+	}).collect()
+}
+
+fn k_means(entries: &[Entry], mut palette: Vec<Color>, iterations: usize) -> Vec<Color> {
+	let mut assignment = vec![usize::MAX; entries.len()];
+
+	for _ in 0 .. iterations {
+		let mut changed = false;
+
+		for (i, &(color, _)) in entries.iter().enumerate() {
+			let nearest = palette.iter().enumerate().map(|(j, p)| {
+				let distance =
+					(color[0] as i32 - p.r as i32).pow(2) +
+					(color[1] as i32 - p.g as i32).pow(2) +
+					(color[2] as i32 - p.b as i32).pow(2) +
+					(color[3] as i32 - p.a as i32).pow(2);
+
+				(distance, j)
+			}).min().map(|(_, j)| j).unwrap_or(0);
+
+			if assignment[i] != nearest {
+				assignment[i] = nearest;
+				changed       = true;
+			}
+		}
+
+		if !changed {
+			break;
+		}
+
+		let mut sums   = vec![[0u64; 4]; palette.len()];
+		let mut totals = vec![0u64; palette.len()];
+
+		for (&(color, count), &j) in entries.iter().zip(assignment.iter()) {
+			for (s, &c) in sums[j].iter_mut().zip(color.iter()) {
+				*s += c as u64 * count;
+			}
+
+			totals[j] += count;
+		}
+
+		for ((color, &sum), &total) in palette.iter_mut().zip(sums.iter()).zip(totals.iter()) {
+			// Empty clusters keep their color.
+			if total != 0 {
+				*color = average(sum, total);
+			}
+		}
+	}
+
+	palette
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use buffer;
+	use color::Rgb;
+
+	/// Four flat areas of slightly noisy colors.
+	fn image() -> buffer::Rgb {
+		buffer::Rgb::from_fn(16, 16, |x, y| {
+			let noise = ((x * 7 + y * 13) % 5) as f32 / 255.0;
+
+			match (x / 8, y / 8) {
+				(0, 0) => Rgb::new(1.0 - noise, noise, noise),
+				(1, 0) => Rgb::new(noise, 1.0 - noise, noise),
+				(0, _) => Rgb::new(noise, noise, 1.0 - noise),
+				_      => Rgb::new(0.5 + noise, 0.5 + noise, 0.5 + noise),
+			}
+		})
+	}
+
+	fn check<Q: Quantizer>() {
+		let (output, palette) = quantize::<Q, _, Rgb, u8, Rgb, u8>(&image(), 4);
+
+		assert_eq!(4, palette.len(), "palette size");
+
+		for &(x, y, color) in &[(2, 2, Rgb::new(1.0, 0.0, 0.0)), (10, 2, Rgb::new(0.0, 1.0, 0.0)),
+		                        (2, 10, Rgb::new(0.0, 0.0, 1.0)), (10, 10, Rgb::new(0.5, 0.5, 0.5))] {
+			let px = output.get(x, y);
+
+			assert!((px.red - color.red).abs() < 0.05 &&
+			        (px.green - color.green).abs() < 0.05 &&
+			        (px.blue - color.blue).abs() < 0.05, "{:?} instead of {:?}", px, color);
+		}
+
+		// Every pixel of the output is from the palette.
+		let colors = palette.colors();
+		assert!(output.pixels().all(|(_, _, px)| {
+			let px = px.get();
+			colors.iter().any(|c| c.red == px.red && c.green == px.green && c.blue == px.blue)
+		}));
+	}
+
+	#[test]
+	fn median_cut() {
+		check::<MedianCut>();
+	}
+
+	#[test]
+	fn octree() {
+		check::<Octree>();
+	}
+
+	#[test]
+	fn wu() {
+		check::<Wu>();
+	}
+
+	#[test]
+	fn k_means() {
+		check::<KMeans>();
+	}
+}
@@ -0,0 +1,278 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use buffer::Buffer;
+use pixel;
+use view;
+use color::{Rgba, Rgb, Lab, Limited};
+use processing::Histogram;
+
+/// The amount of bins used for the lightness.
+const BINS: u32 = 256;
+
+/// Trait for equalizable types.
+///
+/// The equalization works on the `Lab` lightness, so hues and saturations are
+/// left alone.
+pub trait Equalize<P, C>
+	where P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	/// Equalize the histogram of the whole image.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// image.equalize();
+	/// ```
+	fn equalize(self) -> Buffer<P, C, Vec<C>>;
+
+	/// Equalize the histogram adaptively (CLAHE), over a grid of the given
+	/// amount of columns and rows of tiles, clipping the histogram of every
+	/// tile at the given multiple of its average bin count.
+	///
+	/// A clip limit lower or equal to `1.0` disables the clipping.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// image.equalize_adaptive(8, 8, 3.0);
+	/// ```
+	fn equalize_adaptive(self, columns: u32, rows: u32, clip: f32) -> Buffer<P, C, Vec<C>>;
+}
+
+impl<'i, P, C, I> Equalize<P, C> for I
+	where P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+	      I: Into<view::Read<'i, P, C>>
+{
+	#[inline]
+	fn equalize(self) -> Buffer<P, C, Vec<C>> {
+		it::<_, P, C, P, C>(self)
+	}
+
+	#[inline]
+	fn equalize_adaptive(self, columns: u32, rows: u32, clip: f32) -> Buffer<P, C, Vec<C>> {
+		adaptive::<_, P, C, P, C>(self, columns, rows, clip)
+	}
+}
+
+/// Equalize the histogram of the whole image.
+pub fn it<'i, I, PI, CI, PO, CO>(input: I) -> Buffer<PO, CO, Vec<CO>>
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	let     input     = input.into();
+	let mut histogram = Histogram::new(BINS, (0.0, 1.0), 1);
+
+	for (_, _, px) in input.pixels() {
+		histogram.add(0, lightness(px.get().into()));
+	}
+
+	let table = mapping(histogram.get(0));
+
+	let mut output = Buffer::<PO, CO, _>::new(input.width(), input.height());
+	for (x, y) in output.region().absolute() {
+		output.set(x, y, &apply(input.get(x, y).into(), |l| table[histogram.bin(l)]).into());
+	}
+
+	output
+}
+
+/// Equalize the histogram adaptively (CLAHE), over a grid of the given amount
+/// of columns and rows of tiles, clipping the histogram of every tile at the
+/// given multiple of its average bin count.
+pub fn adaptive<'i, I, PI, CI, PO, CO>(input: I, columns: u32, rows: u32, clip: f32) -> Buffer<PO, CO, Vec<CO>>
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	let input   = input.into();
+	let width   = input.width();
+	let height  = input.height();
+	let columns = columns.max(1).min(width.max(1));
+	let rows    = rows.max(1).min(height.max(1));
+
+	// Build the mapping table of every tile.
+	let mut tables = Vec::with_capacity((columns * rows) as usize);
+	for row in 0 .. rows {
+		for column in 0 .. columns {
+			let mut histogram = Histogram::new(BINS, (0.0, 1.0), 1);
+
+			for y in row * height / rows .. (row + 1) * height / rows {
+				for x in column * width / columns .. (column + 1) * width / columns {
+					histogram.add(0, lightness(input.get(x, y).into()));
+				}
+			}
+
+			if clip > 1.0 {
+				tables.push(mapping(&limit(histogram.get(0), clip)));
+			}
+			else {
+				tables.push(mapping(histogram.get(0)));
+			}
+
+		}
+	}
+
+	// Interpolate between the tables of the nearest tiles.
+	let layout      = Histogram::new(BINS, (0.0, 1.0), 0);
+	let tile_width  = width as f32 / columns as f32;
+	let tile_height = height as f32 / rows as f32;
+
+	let locate = |position: u32, size: f32, amount: u32| {
+		let offset = ((position as f32 + 0.5) / size - 0.5).max(0.0);
+		let first  = (offset as u32).min(amount - 1);
+		let second = (first + 1).min(amount - 1);
+
+		(first as usize, second as usize, (offset - first as f32).min(1.0))
+	};
+
+	let mut output = Buffer::<PO, CO, _>::new(width, height);
+	for (x, y) in output.region().absolute() {
+		let (x0, x1, fx) = locate(x, tile_width, columns);
+		let (y0, y1, fy) = locate(y, tile_height, rows);
+
+		let table = |column: usize, row: usize| &tables[row * columns as usize + column];
+
+		output.set(x, y, &apply(input.get(x, y).into(), |l| {
+			let bin = layout.bin(l);
+
+			let top    = table(x0, y0)[bin] * (1.0 - fx) + table(x1, y0)[bin] * fx;
+			let bottom = table(x0, y1)[bin] * (1.0 - fx) + table(x1, y1)[bin] * fx;
+
+			top * (1.0 - fy) + bottom * fy
+		}).into());
+	}
+
+	output
+}
+
+/// Get the `Lab` lightness of the pixel.
+#[inline]
+fn lightness(px: Rgba) -> f32 {
+	Lab::from(px.color).l
+}
+
+/// Replace the lightness of the pixel with the mapped one.
+#[inline]
+fn apply<F>(px: Rgba, map: F) -> Rgba
+	where F: FnOnce(f32) -> f32
+{
+	let mut lab = Lab::from(px.color);
+	lab.l = map(lab.l);
+
+	Rgba { color: Rgb::from(lab).clamp(), alpha: px.alpha }
+}
+
+/// Build the mapping table from the cumulative distribution of the counts.
+fn mapping(counts: &[u32]) -> Vec<f32> {
+	let cumulative = counts.iter().scan(0, |sum, &count| {
+		*sum += count;
+		Some(*sum)
+	}).collect::<Vec<u32>>();
+
+	let total   = *cumulative.last().unwrap_or(&0);
+	let minimum = cumulative.iter().cloned().find(|&count| count > 0).unwrap_or(0);
+
+	if total == minimum {
+		return (0 .. counts.len()).map(|bin| bin as f32 / (counts.len() - 1) as f32).collect();
+	}
+
+	cumulative.iter().map(|&count|
+		count.saturating_sub(minimum) as f32 / (total - minimum) as f32).collect()
+}
+
+/// Clip the counts at the given multiple of the average and redistribute the
+/// excess evenly.
+fn limit(counts: &[u32], clip: f32) -> Vec<u32> {
+	let bins   = counts.len() as u32;
+	let total  = counts.iter().sum::<u32>();
+	let limit  = ((clip * total as f32 / bins as f32) as u32).max(1);
+	let excess = counts.iter().map(|&count| count.saturating_sub(limit)).sum::<u32>();
+
+	counts.iter().enumerate().map(|(bin, &count)|
+		count.min(limit) + excess / bins + if (bin as u32) < excess % bins { 1 } else { 0 }).collect()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use buffer;
+	use color::Rgb;
+
+	#[test]
+	fn stretch() {
+		let image  = buffer::Rgb::from_fn(16, 16, |x, _| {
+			let v = 0.4 + x as f32 * 0.01;
+			Rgb::new(v, v, v)
+		});
+
+		let output = image.equalize();
+		let first  = output.get(0, 0);
+		let last   = output.get(15, 0);
+
+		assert!(first.red < 0.05);
+		assert!(last.red > 0.95);
+		assert!((first.red - first.blue).abs() < 0.01);
+		assert!((last.red - last.green).abs() < 0.01);
+	}
+
+	#[test]
+	fn adaptive() {
+		let image  = buffer::Rgb::from_fn(32, 32, |x, y| {
+			let v = 0.4 + ((x + y) % 8) as f32 * 0.02;
+			Rgb::new(v, v, v)
+		});
+
+		let input   = image.convert::<Rgb, f32>();
+		let output  = image.equalize_adaptive(4, 4, 1.0).convert::<Rgb, f32>();
+		let clipped = image.equalize_adaptive(4, 4, 4.0).convert::<Rgb, f32>();
+
+		let spread = |buffer: &buffer::Buffer<Rgb, f32, Vec<f32>>| {
+			let values = buffer.pixels().map(|(_, _, p)| p.get().red).collect::<Vec<_>>();
+			let min    = values.iter().cloned().fold(1.0, f32::min);
+			let max    = values.iter().cloned().fold(0.0, f32::max);
+
+			max - min
+		};
+
+		assert!(spread(&output) > spread(&input) * 2.0);
+		assert!(spread(&clipped) > spread(&input));
+		assert!(spread(&clipped) < spread(&output));
+	}
+}
@@ -0,0 +1,183 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use pixel;
+use view::View;
+use color::Rgba;
+
+/// Which neighbours of a pixel are connected to it.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Connectivity {
+	/// The pixels sharing an edge.
+	Four,
+
+	/// The pixels sharing an edge or a corner.
+	Eight,
+}
+
+impl Default for Connectivity {
+	#[inline]
+	fn default() -> Self {
+		Connectivity::Four
+	}
+}
+
+impl Connectivity {
+	/// Get the offsets of the neighbours.
+	#[inline]
+	pub fn offsets(&self) -> &'static [(i64, i64)] {
+		match *self {
+			Connectivity::Four =>
+				&[(0, -1), (-1, 0), (1, 0), (0, 1)],
+
+			Connectivity::Eight =>
+				&[(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)],
+		}
+	}
+}
+
+/// Trait for flood fillable types.
+pub trait Fill<P, C>
+	where P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	/// Fill the area connected to the given pixel and of similar color with
+	/// the given pixel, returning how many pixels were filled.
+	///
+	/// The tolerance is the maximum distance from the color of the starting
+	/// pixel, going from `0.0` for the exact same color to `1.0` for any
+	/// color.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let mut image = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// (&mut image).flood_fill(0, 0, &Rgb::new(1.0, 0.0, 0.0), fill::Connectivity::Four, 0.1);
+	/// ```
+	fn flood_fill(self, x: u32, y: u32, pixel: &P, connectivity: Connectivity, tolerance: f32) -> u32;
+}
+
+impl<'o, P, C, O> Fill<P, C> for O
+	where P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+	      O: Into<View<'o, P, C>>,
+{
+	#[inline]
+	fn flood_fill(self, x: u32, y: u32, pixel: &P, connectivity: Connectivity, tolerance: f32) -> u32 {
+		flood(self, x, y, pixel, connectivity, tolerance)
+	}
+}
+
+/// Fill the area connected to the given pixel and of similar color with the
+/// given pixel, returning how many pixels were filled.
+///
+/// # Panics
+///
+/// Requires that `x < width` and `y < height`, otherwise it will panic.
+pub fn flood<'o, O, P, C>(output: O, x: u32, y: u32, pixel: &P, connectivity: Connectivity, tolerance: f32) -> u32
+	where O: Into<View<'o, P, C>>,
+	      P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	let mut output = output.into();
+	let     width  = output.width();
+	let     height = output.height();
+
+	if x >= width || y >= height {
+		panic!("out of bounds");
+	}
+
+	let     seed    = output.get(x, y).into();
+	let mut visited = vec![false; width as usize * height as usize];
+	let mut stack   = vec![(x, y)];
+	let mut filled  = 0;
+
+	visited[(y * width + x) as usize] = true;
+
+	while let Some((x, y)) = stack.pop() {
+		if distance(seed, output.get(x, y).into()) > tolerance {
+			continue;
+		}
+
+		output.set(x, y, pixel);
+		filled += 1;
+
+		for &(dx, dy) in connectivity.offsets() {
+			let nx = x as i64 + dx;
+			let ny = y as i64 + dy;
+
+			if nx < 0 || ny < 0 || nx >= width as i64 || ny >= height as i64 {
+				continue;
+			}
+
+			let index = (ny as u32 * width + nx as u32) as usize;
+
+			if !visited[index] {
+				visited[index] = true;
+				stack.push((nx as u32, ny as u32));
+			}
+		}
+	}
+
+	filled
+}
+
+/// Get the distance between the colors, scaled between `0.0` and `1.0`.
+#[inline]
+fn distance(a: Rgba, b: Rgba) -> f32 {
+	((a.red - b.red).powi(2) + (a.green - b.green).powi(2) +
+	 (a.blue - b.blue).powi(2) + (a.alpha - b.alpha).powi(2)).sqrt() / 2.0
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use buffer::Buffer;
+	use color::Luma;
+
+	fn diagonal() -> Buffer<Luma, u8, Vec<u8>> {
+		Buffer::from_raw(3, 3, vec![
+			0,   255, 255,
+			255, 0,   255,
+			255, 255, 10,
+		]).unwrap()
+	}
+
+	#[test]
+	fn connectivity() {
+		let mut image = diagonal();
+		assert_eq!(1, (&mut image).flood_fill(0, 0, &Luma::new(1.0), Connectivity::Four, 0.0));
+		assert_eq!(Luma::new(0.0), image.get(1, 1));
+
+		let mut image = diagonal();
+		assert_eq!(2, (&mut image).flood_fill(0, 0, &Luma::new(1.0), Connectivity::Eight, 0.0));
+		assert_eq!(Luma::new(1.0), image.get(1, 1));
+		assert_eq!(Luma::new(10.0 / 255.0), image.get(2, 2));
+	}
+
+	#[test]
+	fn tolerance() {
+		let mut image = diagonal();
+		assert_eq!(3, (&mut image).flood_fill(0, 0, &Luma::new(1.0), Connectivity::Eight, 0.1));
+		assert_eq!(Luma::new(1.0), image.get(2, 2));
+	}
+}
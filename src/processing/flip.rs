@@ -16,6 +16,7 @@ use buffer::Buffer;
 use pixel;
 use view::View;
 use orientation::Orientation;
+use processing::parallel;
 
 /// Trait for flippable types.
 pub trait Flip<P, C>
@@ -74,9 +75,9 @@ pub fn it<'a, P, C, T>(value: T, mode: Orientation)
 	      C: pixel::Channel,
 	      T: Into<View<'a, P, C>>
 {
-	let mut view   = value.into();
-	let     width  = view.width();
-	let     height = view.height();
+	let view   = value.into();
+	let width  = view.width();
+	let height = view.height();
 
 	match mode {
 		Orientation::Vertical => {
@@ -84,21 +85,21 @@ pub fn it<'a, P, C, T>(value: T, mode: Orientation)
 				return;
 			}
 
-			for y in 0 .. height {
-				let reverse = height - y - 1;
-
-				if y >= reverse {
-					break;
-				}
+			// Pair every row of the top half with its mirror in the bottom half,
+			// the middle row of odd heights stays in place.
+			let mut top    = view.rows();
+			let     bottom = top.split_off((height as usize).div_ceil(2));
+			let     pairs  = top.into_iter().zip(bottom.into_iter().rev()).collect::<Vec<_>>();
 
+			parallel::each(pairs, |_, (mut top, mut bottom)| {
 				for x in 0 .. width {
-					let top    = view.get(x, y);
-					let bottom = view.get(x, reverse);
+					let above = top.get(x, 0);
+					let below = bottom.get(x, 0);
 
-					view.set(x, y, &bottom);
-					view.set(x, reverse, &top);
+					top.set(x, 0, &below);
+					bottom.set(x, 0, &above);
 				}
-			}
+			});
 		}
 
 		Orientation::Horizontal => {
@@ -106,21 +107,16 @@ pub fn it<'a, P, C, T>(value: T, mode: Orientation)
 				return;
 			}
 
-			for x in 0 .. width {
-				let reverse = width - x - 1;
+			parallel::each(view.rows(), |_, mut row| {
+				for x in 0 .. width / 2 {
+					let reverse = width - x - 1;
+					let left    = row.get(x, 0);
+					let right   = row.get(reverse, 0);
 
-				if x >= reverse {
-					break;
+					row.set(x, 0, &right);
+					row.set(reverse, 0, &left);
 				}
-
-				for y in 0 .. height {
-					let left  = view.get(x, y);
-					let right = view.get(reverse, y);
-
-					view.set(x, y, &right);
-					view.set(reverse, y, &left);
-				}
-			}
+			});
 		}
 	}
 }
@@ -0,0 +1,221 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use pixel;
+use view;
+use color::{Rgba, Luma};
+
+/// A histogram of the values within an image.
+///
+/// Values are counted in evenly sized bins over the given range, values
+/// outside of the range are counted in the first or last bin.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Histogram {
+	bins:     u32,
+	range:    (f32, f32),
+	channels: Vec<Vec<u32>>,
+}
+
+impl Histogram {
+	/// Create an empty `Histogram` with the given amount of bins, range and
+	/// channels.
+	///
+	/// # Panics
+	///
+	/// Requires that `bins > 0` and that the range isn't empty, otherwise it
+	/// will panic.
+	#[inline]
+	pub fn new(bins: u32, range: (f32, f32), channels: usize) -> Self {
+		if bins == 0 || range.0 >= range.1 {
+			panic!("invalid histogram");
+		}
+
+		Histogram {
+			bins,
+			range,
+			channels: vec![vec![0; bins as usize]; channels],
+		}
+	}
+
+	/// Compute the `Histogram` of the luminance of the input.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Rgb;
+	/// use picto::processing::Histogram;
+	///
+	/// let image     = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	/// let histogram = Histogram::luma(&image, 256, (0.0, 1.0));
+	///
+	/// assert_eq!(image.width() * image.height(), histogram.total(0));
+	/// ```
+	#[inline]
+	pub fn luma<'i, P, C, I>(input: I, bins: u32, range: (f32, f32)) -> Self
+		where P: Into<Rgba> + pixel::Read<C>,
+		      C: pixel::Channel,
+		      I: Into<view::Read<'i, P, C>>
+	{
+		let mut histogram = Histogram::new(bins, range, 1);
+
+		for (_, _, px) in input.into().pixels() {
+			histogram.add(0, Luma::from(px.get().into().color).luma);
+		}
+
+		histogram
+	}
+
+	/// Compute the `Histogram` of the red, green, blue and alpha channels of
+	/// the input, in that order.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Rgb;
+	/// use picto::processing::Histogram;
+	///
+	/// let image     = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	/// let histogram = Histogram::channels(&image, 64, (0.0, 1.0));
+	///
+	/// assert_eq!(4, histogram.len());
+	/// ```
+	#[inline]
+	pub fn channels<'i, P, C, I>(input: I, bins: u32, range: (f32, f32)) -> Self
+		where P: Into<Rgba> + pixel::Read<C>,
+		      C: pixel::Channel,
+		      I: Into<view::Read<'i, P, C>>
+	{
+		let mut histogram = Histogram::new(bins, range, 4);
+
+		for (_, _, px) in input.into().pixels() {
+			let px = px.get().into();
+
+			histogram.add(0, px.red);
+			histogram.add(1, px.green);
+			histogram.add(2, px.blue);
+			histogram.add(3, px.alpha);
+		}
+
+		histogram
+	}
+
+	/// Count the value in the given channel.
+	///
+	/// # Panics
+	///
+	/// Requires that `channel < self.len()`, otherwise it will panic.
+	#[inline]
+	pub fn add(&mut self, channel: usize, value: f32) {
+		let bin = self.bin(value);
+		self.channels[channel][bin] += 1;
+	}
+
+	/// Get the amount of bins.
+	#[inline]
+	pub fn bins(&self) -> u32 {
+		self.bins
+	}
+
+	/// Get the range.
+	#[inline]
+	pub fn range(&self) -> (f32, f32) {
+		self.range
+	}
+
+	/// Get the amount of channels.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.channels.len()
+	}
+
+	/// Check if there are no channels.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.channels.is_empty()
+	}
+
+	/// Get the bin the value falls in.
+	#[inline]
+	pub fn bin(&self, value: f32) -> usize {
+		let position = (value - self.range.0) / (self.range.1 - self.range.0) * self.bins as f32;
+
+		if position.is_nan() || position < 0.0 {
+			0
+		}
+		else {
+			(position as usize).min(self.bins as usize - 1)
+		}
+	}
+
+	/// Get the counts of the given channel.
+	///
+	/// # Panics
+	///
+	/// Requires that `channel < self.len()`, otherwise it will panic.
+	#[inline]
+	pub fn get(&self, channel: usize) -> &[u32] {
+		&self.channels[channel]
+	}
+
+	/// Get the total count of the given channel.
+	///
+	/// # Panics
+	///
+	/// Requires that `channel < self.len()`, otherwise it will panic.
+	#[inline]
+	pub fn total(&self, channel: usize) -> u32 {
+		self.channels[channel].iter().sum()
+	}
+
+	/// Get the cumulative counts of the given channel.
+	///
+	/// # Panics
+	///
+	/// Requires that `channel < self.len()`, otherwise it will panic.
+	#[inline]
+	pub fn cumulative(&self, channel: usize) -> Vec<u32> {
+		self.channels[channel].iter().scan(0, |sum, &count| {
+			*sum += count;
+			Some(*sum)
+		}).collect()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use buffer;
+
+	#[test]
+	fn luma() {
+		let image = buffer::Luma::from_raw(2, 2, vec![0, 0, 128, 255]).unwrap();
+		let histogram = Histogram::luma(&image, 4, (0.0, 1.0));
+
+		assert_eq!(&[2, 0, 1, 1], histogram.get(0));
+		assert_eq!(vec![2, 2, 3, 4], histogram.cumulative(0));
+	}
+
+	#[test]
+	fn channels() {
+		let image = buffer::Rgb::from_raw(2, 1, vec![0, 255, 128, 255, 0, 128]).unwrap();
+		let histogram = Histogram::channels(&image, 2, (0.0, 1.0));
+
+		assert_eq!(&[1, 1], histogram.get(0));
+		assert_eq!(&[1, 1], histogram.get(1));
+		assert_eq!(&[0, 2], histogram.get(2));
+		assert_eq!(&[0, 2], histogram.get(3));
+	}
+}
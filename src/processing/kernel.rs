@@ -0,0 +1,300 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+/// A convolution kernel.
+///
+/// The kernel is anchored on its center, each weighted sum is divided by the
+/// divisor and then the bias is added.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Kernel {
+	width:   u32,
+	height:  u32,
+	weights: Weights,
+
+	divisor: f32,
+	bias:    f32,
+}
+
+/// The weights of a `Kernel`.
+#[derive(Clone, PartialEq, Debug)]
+pub enum Weights {
+	/// A full matrix of weights, stored row-major.
+	Full(Vec<f32>),
+
+	/// A separable kernel, the matrix is the outer product of the vertical and
+	/// horizontal weights.
+	Separable {
+		horizontal: Vec<f32>,
+		vertical:   Vec<f32>,
+	},
+}
+
+impl Kernel {
+	/// Create a `Kernel` from a row-major matrix of weights, returns `None` if
+	/// the size doesn't match.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::processing::Kernel;
+	///
+	/// Kernel::from_raw(3, 3, vec![
+	///      0.0, -1.0,  0.0,
+	///     -1.0,  5.0, -1.0,
+	///      0.0, -1.0,  0.0,
+	/// ]).unwrap();
+	/// ```
+	#[inline]
+	pub fn from_raw(width: u32, height: u32, weights: Vec<f32>) -> Option<Self> {
+		if width == 0 || height == 0 || weights.len() != width as usize * height as usize {
+			return None;
+		}
+
+		Some(Kernel {
+			width,
+			height,
+			weights: Weights::Full(weights),
+
+			divisor: 1.0,
+			bias:    0.0,
+		})
+	}
+
+	/// Create a separable `Kernel` from the horizontal and vertical weights.
+	///
+	/// Separable kernels are applied in two passes, which is a lot faster for
+	/// big kernels.
+	#[inline]
+	pub fn separable(horizontal: Vec<f32>, vertical: Vec<f32>) -> Option<Self> {
+		if horizontal.is_empty() || vertical.is_empty() {
+			return None;
+		}
+
+		Some(Kernel {
+			width:   horizontal.len() as u32,
+			height:  vertical.len() as u32,
+			weights: Weights::Separable { horizontal, vertical },
+
+			divisor: 1.0,
+			bias:    0.0,
+		})
+	}
+
+	/// Set the divisor to the sum of the weights, unless they sum to zero.
+	#[inline]
+	pub fn normalize(mut self) -> Self {
+		let sum = self.sum();
+
+		if sum != 0.0 {
+			self.divisor = sum;
+		}
+
+		self
+	}
+
+	/// Set the divisor.
+	#[inline]
+	pub fn with_divisor(mut self, value: f32) -> Self {
+		self.divisor = value;
+		self
+	}
+
+	/// Set the bias.
+	#[inline]
+	pub fn with_bias(mut self, value: f32) -> Self {
+		self.bias = value;
+		self
+	}
+
+	/// Get the width.
+	#[inline]
+	pub fn width(&self) -> u32 {
+		self.width
+	}
+
+	/// Get the height.
+	#[inline]
+	pub fn height(&self) -> u32 {
+		self.height
+	}
+
+	/// Get the weights.
+	#[inline]
+	pub fn weights(&self) -> &Weights {
+		&self.weights
+	}
+
+	/// Get the divisor.
+	#[inline]
+	pub fn divisor(&self) -> f32 {
+		self.divisor
+	}
+
+	/// Get the bias.
+	#[inline]
+	pub fn bias(&self) -> f32 {
+		self.bias
+	}
+
+	/// Get the weight at the given coordinates.
+	///
+	/// # Panics
+	///
+	/// Requires that `x < self.width()` and `y < self.height()`, otherwise it
+	/// will panic.
+	#[inline]
+	pub fn get(&self, x: u32, y: u32) -> f32 {
+		if x >= self.width || y >= self.height {
+			panic!("out of bounds");
+		}
+
+		match self.weights {
+			Weights::Full(ref weights) =>
+				weights[(y * self.width + x) as usize],
+
+			Weights::Separable { ref horizontal, ref vertical } =>
+				horizontal[x as usize] * vertical[y as usize],
+		}
+	}
+
+	/// Get the sum of the weights.
+	#[inline]
+	pub fn sum(&self) -> f32 {
+		match self.weights {
+			Weights::Full(ref weights) =>
+				weights.iter().sum(),
+
+			Weights::Separable { ref horizontal, ref vertical } =>
+				horizontal.iter().sum::<f32>() * vertical.iter().sum::<f32>(),
+		}
+	}
+}
+
+/// A gradient operator, made of an horizontal and a vertical kernel.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Operator {
+	Sobel,
+	Prewitt,
+	Scharr,
+}
+
+impl Operator {
+	/// Get the horizontal and vertical kernels.
+	#[inline]
+	pub fn kernels(&self) -> (Kernel, Kernel) {
+		match *self {
+			Operator::Sobel   => (sobel_x(), sobel_y()),
+			Operator::Prewitt => (prewitt_x(), prewitt_y()),
+			Operator::Scharr  => (scharr_x(), scharr_y()),
+		}
+	}
+}
+
+/// The horizontal Sobel operator.
+#[inline]
+pub fn sobel_x() -> Kernel {
+	Kernel::from_raw(3, 3, vec![
+		-1.0, 0.0, 1.0,
+		-2.0, 0.0, 2.0,
+		-1.0, 0.0, 1.0,
+	]).unwrap()
+}
+
+/// The vertical Sobel operator.
+#[inline]
+pub fn sobel_y() -> Kernel {
+	Kernel::from_raw(3, 3, vec![
+		-1.0, -2.0, -1.0,
+		 0.0,  0.0,  0.0,
+		 1.0,  2.0,  1.0,
+	]).unwrap()
+}
+
+/// The horizontal Prewitt operator.
+#[inline]
+pub fn prewitt_x() -> Kernel {
+	Kernel::from_raw(3, 3, vec![
+		-1.0, 0.0, 1.0,
+		-1.0, 0.0, 1.0,
+		-1.0, 0.0, 1.0,
+	]).unwrap()
+}
+
+/// The vertical Prewitt operator.
+#[inline]
+pub fn prewitt_y() -> Kernel {
+	Kernel::from_raw(3, 3, vec![
+		-1.0, -1.0, -1.0,
+		 0.0,  0.0,  0.0,
+		 1.0,  1.0,  1.0,
+	]).unwrap()
+}
+
+/// The horizontal Scharr operator.
+#[inline]
+pub fn scharr_x() -> Kernel {
+	Kernel::from_raw(3, 3, vec![
+		 -3.0, 0.0,  3.0,
+		-10.0, 0.0, 10.0,
+		 -3.0, 0.0,  3.0,
+	]).unwrap()
+}
+
+/// The vertical Scharr operator.
+#[inline]
+pub fn scharr_y() -> Kernel {
+	Kernel::from_raw(3, 3, vec![
+		-3.0, -10.0, -3.0,
+		 0.0,   0.0,  0.0,
+		 3.0,  10.0,  3.0,
+	]).unwrap()
+}
+
+/// The Laplacian operator.
+#[inline]
+pub fn laplacian() -> Kernel {
+	Kernel::from_raw(3, 3, vec![
+		0.0,  1.0, 0.0,
+		1.0, -4.0, 1.0,
+		0.0,  1.0, 0.0,
+	]).unwrap()
+}
+
+/// The Laplacian operator including the diagonals.
+#[inline]
+pub fn laplacian_diagonal() -> Kernel {
+	Kernel::from_raw(3, 3, vec![
+		1.0,  1.0, 1.0,
+		1.0, -8.0, 1.0,
+		1.0,  1.0, 1.0,
+	]).unwrap()
+}
+
+/// The emboss kernel, lighting from the top left.
+#[inline]
+pub fn emboss() -> Kernel {
+	Kernel::from_raw(3, 3, vec![
+		-2.0, -1.0, 0.0,
+		-1.0,  1.0, 1.0,
+		 0.0,  1.0, 2.0,
+	]).unwrap()
+}
+
+/// A normalized box kernel with the given radius.
+#[inline]
+pub fn box_blur(radius: u32) -> Kernel {
+	let size = (radius * 2 + 1) as usize;
+
+	Kernel::separable(vec![1.0; size], vec![1.0; size]).unwrap().normalize()
+}
@@ -0,0 +1,283 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use pixel;
+use view;
+use buffer::Buffer;
+use region::Region;
+use color::{Rgba, Luma};
+use processing::fill::Connectivity;
+
+/// A connected component.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct Component {
+	/// The label of the component, starting from `1`.
+	pub label: u32,
+
+	/// The number of pixels.
+	pub area: u32,
+
+	/// The bounding box.
+	pub region: Region,
+
+	/// The mean position of the pixels.
+	pub centroid: (f32, f32),
+}
+
+/// The connected components within an image, and the label of every pixel.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Labels {
+	labels:     Buffer<Luma, u32, Vec<u32>>,
+	components: Vec<Component>,
+}
+
+impl Labels {
+	/// Get the width.
+	#[inline]
+	pub fn width(&self) -> u32 {
+		self.labels.width()
+	}
+
+	/// Get the height.
+	#[inline]
+	pub fn height(&self) -> u32 {
+		self.labels.height()
+	}
+
+	/// Get the label of the pixel at the given coordinates, `0` for the
+	/// background.
+	///
+	/// # Panics
+	///
+	/// Requires that `x < self.width()` and `y < self.height()`, otherwise it
+	/// will panic.
+	#[inline]
+	pub fn get(&self, x: u32, y: u32) -> u32 {
+		if x >= self.width() || y >= self.height() {
+			panic!("out of bounds");
+		}
+
+		self.labels[(y * self.width() + x) as usize]
+	}
+
+	/// Get the labels as a `Buffer`, the raw channels are the labels.
+	#[inline]
+	pub fn buffer(&self) -> &Buffer<Luma, u32, Vec<u32>> {
+		&self.labels
+	}
+
+	/// Take the labels as a `Buffer`, the raw channels are the labels.
+	#[inline]
+	pub fn into_buffer(self) -> Buffer<Luma, u32, Vec<u32>> {
+		self.labels
+	}
+
+	/// Get the components, in the order they're first met scanning the image
+	/// by rows.
+	#[inline]
+	pub fn components(&self) -> &[Component] {
+		&self.components
+	}
+
+	/// Get the mask of the component with the given label within its bounding
+	/// box.
+	///
+	/// # Panics
+	///
+	/// Requires that the label belongs to a component, otherwise it will
+	/// panic.
+	pub fn mask(&self, label: u32) -> Buffer<Luma, u8, Vec<u8>> {
+		if label == 0 || label as usize > self.components.len() {
+			panic!("unknown label");
+		}
+
+		let region = self.components[label as usize - 1].region;
+
+		Buffer::from_fn(region.width, region.height, |x, y|
+			if self.get(region.x + x, region.y + y) == label {
+				Luma::new(1.0)
+			}
+			else {
+				Luma::new(0.0)
+			})
+	}
+}
+
+/// Trait for labelable types.
+pub trait Label<P, C>
+	where P: Into<Rgba>,
+	      P: pixel::Read<C>,
+	      C: pixel::Channel,
+{
+	/// Find the connected components of foreground pixels, those with a
+	/// luminance of at least `0.5`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Luma;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image  = read::from_path::<Luma, u8, _>("tests/rainbow.png").unwrap();
+	/// let labels = image.label_components(fill::Connectivity::Eight);
+	///
+	/// assert!(!labels.components().is_empty());
+	/// assert_eq!(image.dimensions(), labels.buffer().dimensions());
+	/// ```
+	fn label_components(self, connectivity: Connectivity) -> Labels;
+}
+
+impl<'i, P, C, I> Label<P, C> for I
+	where P: Into<Rgba>,
+	      P: pixel::Read<C>,
+	      C: pixel::Channel,
+	      I: Into<view::Read<'i, P, C>>
+{
+	#[inline]
+	fn label_components(self, connectivity: Connectivity) -> Labels {
+		components(self, connectivity)
+	}
+}
+
+/// Find the connected components of foreground pixels, those with a
+/// luminance of at least `0.5`.
+pub fn components<'i, P, C, I>(input: I, connectivity: Connectivity) -> Labels
+	where P: Into<Rgba>,
+	      P: pixel::Read<C>,
+	      C: pixel::Channel,
+	      I: Into<view::Read<'i, P, C>>
+{
+	let input  = input.into();
+	let width  = input.width();
+	let height = input.height();
+
+	let foreground = input.pixels()
+		.map(|(_, _, px)| Luma::from(px.get().into().color).luma >= 0.5)
+		.collect::<Vec<bool>>();
+
+	let mut labels     = vec![0u32; foreground.len()];
+	let mut components = Vec::new();
+	let mut stack      = Vec::new();
+
+	for start in 0 .. foreground.len() {
+		if !foreground[start] || labels[start] != 0 {
+			continue;
+		}
+
+		let label = components.len() as u32 + 1;
+		let mut area = 0u32;
+		let mut sum  = (0.0f64, 0.0f64);
+		let mut min  = (width, height);
+		let mut max  = (0, 0);
+
+		labels[start] = label;
+		stack.push(start);
+
+		while let Some(index) = stack.pop() {
+			let x = index as u32 % width;
+			let y = index as u32 / width;
+
+			area  += 1;
+			sum.0 += x as f64;
+			sum.1 += y as f64;
+			min    = (min.0.min(x), min.1.min(y));
+			max    = (max.0.max(x), max.1.max(y));
+
+			for &(dx, dy) in connectivity.offsets() {
+				let nx = x as i64 + dx;
+				let ny = y as i64 + dy;
+
+				if nx < 0 || ny < 0 || nx >= width as i64 || ny >= height as i64 {
+					continue;
+				}
+
+				let neighbour = (ny * width as i64 + nx) as usize;
+
+				if foreground[neighbour] && labels[neighbour] == 0 {
+					labels[neighbour] = label;
+					stack.push(neighbour);
+				}
+			}
+		}
+
+		components.push(Component {
+			label,
+			area,
+			region:   Region::from(min.0, min.1, max.0 - min.0 + 1, max.1 - min.1 + 1),
+			centroid: ((sum.0 / area as f64) as f32, (sum.1 / area as f64) as f32),
+		});
+	}
+
+	Labels {
+		labels: Buffer::from_raw(width, height, labels).unwrap(),
+		components,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use processing::fill::Connectivity;
+
+	fn blobs() -> Buffer<Luma, u8, Vec<u8>> {
+		Buffer::from_raw(5, 4, vec![
+			255, 255, 0,   0,   0,
+			255, 255, 0,   0,   255,
+			0,   0,   255, 0,   255,
+			0,   0,   0,   0,   255,
+		]).unwrap()
+	}
+
+	#[test]
+	fn four() {
+		let labels = blobs().label_components(Connectivity::Four);
+
+		assert_eq!(3, labels.components().len());
+		assert_eq!(Component {
+			label:    1,
+			area:     4,
+			region:   Region::from(0, 0, 2, 2),
+			centroid: (0.5, 0.5),
+		}, labels.components()[0]);
+
+		assert_eq!(2, labels.get(4, 3));
+		assert_eq!(3, labels.get(2, 2));
+		assert_eq!(0, labels.get(3, 3));
+
+		assert_eq!(&[
+			1, 1, 0, 0, 0,
+			1, 1, 0, 0, 2,
+			0, 0, 3, 0, 2,
+			0, 0, 0, 0, 2,
+		][..], &labels.buffer()[..]);
+
+		assert_eq!(Region::from(4, 1, 1, 3), labels.components()[1].region);
+		assert_eq!((4.0, 2.0), labels.components()[1].centroid);
+	}
+
+	#[test]
+	fn eight() {
+		let labels = blobs().label_components(Connectivity::Eight);
+
+		assert_eq!(2, labels.components().len());
+		assert_eq!(5, labels.components()[0].area);
+		assert_eq!(Region::from(0, 0, 3, 3), labels.components()[0].region);
+
+		let mask = labels.mask(1);
+		assert_eq!((3, 3), (mask.width(), mask.height()));
+		assert_eq!(Luma::new(1.0), mask.get(2, 2));
+		assert_eq!(Luma::new(0.0), mask.get(2, 0));
+	}
+}
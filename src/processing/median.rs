@@ -0,0 +1,254 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::any::TypeId;
+
+use buffer::Buffer;
+use pixel;
+use view;
+use edge::EdgeMode;
+use color::Rgba;
+use util::GetEdge;
+
+/// Trait for median filterable types.
+pub trait Median<P, C>
+	where P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	/// Replace every pixel with the median of its neighborhood within the
+	/// given radius.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// image.median(1);
+	/// ```
+	fn median(self, radius: u32) -> Buffer<P, C, Vec<C>>;
+
+	/// Replace every pixel with the median of its neighborhood within the
+	/// given radius, handling the edges with the given `EdgeMode`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::{read, EdgeMode};
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// image.median_with(1, EdgeMode::Mirror);
+	/// ```
+	fn median_with(self, radius: u32, edge: EdgeMode) -> Buffer<P, C, Vec<C>>;
+}
+
+impl<'i, P, C, I> Median<P, C> for I
+	where P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+	      I: Into<view::Read<'i, P, C>>
+{
+	#[inline]
+	fn median(self, radius: u32) -> Buffer<P, C, Vec<C>> {
+		by::<_, P, C, P, C>(self, radius, Default::default())
+	}
+
+	#[inline]
+	fn median_with(self, radius: u32, edge: EdgeMode) -> Buffer<P, C, Vec<C>> {
+		by::<_, P, C, P, C>(self, radius, edge)
+	}
+}
+
+/// Replace every pixel with the median of its neighborhood within the given
+/// radius, handling the edges with the given `EdgeMode`.
+///
+/// Every channel, including the alpha, is filtered independently; inputs with
+/// `u8` channels use a sliding histogram, so the cost doesn't grow with the
+/// area of the neighborhood.
+#[inline]
+pub fn by<'i, I, PI, CI, PO, CO>(input: I, radius: u32, edge: EdgeMode) -> Buffer<PO, CO, Vec<CO>>
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	let input = input.into();
+
+	if TypeId::of::<CI>() == TypeId::of::<u8>() {
+		histogram(&input, radius, edge)
+	}
+	else {
+		select(&input, radius, edge)
+	}
+}
+
+/// Median through a sliding histogram, exact for `u8` channels.
+fn histogram<'i, I, PI, CI, PO, CO>(input: I, radius: u32, edge: EdgeMode) -> Buffer<PO, CO, Vec<CO>>
+	where I:  Into<view::Read<'i, PI, CI>> + Copy,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	fn update(bins: &mut [[u32; 256]; 4], pixel: Rgba, add: bool) {
+		let (r, g, b, a): (u8, u8, u8, u8) = pixel.to_pixel();
+
+		for (bin, value) in bins.iter_mut().zip(&[r, g, b, a]) {
+			if add {
+				bin[*value as usize] += 1;
+			}
+			else {
+				bin[*value as usize] -= 1;
+			}
+		}
+	}
+
+	fn median(bin: &[u32; 256], half: u32) -> f32 {
+		let mut count = 0;
+
+		for (value, &amount) in bin.iter().enumerate() {
+			count += amount;
+
+			if count >= half {
+				return value as f32 / 255.0;
+			}
+		}
+
+		1.0
+	}
+
+	let (width, height) = input.into().dimensions();
+	let     radius      = radius as i64;
+	let     half        = ((radius * 2 + 1) * (radius * 2 + 1) / 2 + 1) as u32;
+	let mut output      = Buffer::<PO, CO, _>::new(width, height);
+
+	for y in 0 .. height as i64 {
+		let mut bins = [[0u32; 256]; 4];
+
+		for dy in -radius .. radius + 1 {
+			for dx in -radius .. radius + 1 {
+				update(&mut bins, input.get_edge(dx, y + dy, edge), true);
+			}
+		}
+
+		for x in 0 .. width as i64 {
+			if x > 0 {
+				for dy in -radius .. radius + 1 {
+					update(&mut bins, input.get_edge(x - radius - 1, y + dy, edge), false);
+					update(&mut bins, input.get_edge(x + radius, y + dy, edge), true);
+				}
+			}
+
+			output.set(x as u32, y as u32, &Rgba::new(
+				median(&bins[0], half),
+				median(&bins[1], half),
+				median(&bins[2], half),
+				median(&bins[3], half)).into());
+		}
+	}
+
+	output
+}
+
+/// Median through selection on the whole neighborhood.
+fn select<'i, I, PI, CI, PO, CO>(input: I, radius: u32, edge: EdgeMode) -> Buffer<PO, CO, Vec<CO>>
+	where I:  Into<view::Read<'i, PI, CI>> + Copy,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	let (width, height) = input.into().dimensions();
+	let     radius      = radius as i64;
+	let     area        = ((radius * 2 + 1) * (radius * 2 + 1)) as usize;
+	let mut output      = Buffer::<PO, CO, _>::new(width, height);
+	let mut channels    = [Vec::with_capacity(area), Vec::with_capacity(area),
+	                       Vec::with_capacity(area), Vec::with_capacity(area)];
+
+	for (x, y) in output.region().absolute() {
+		for channel in &mut channels {
+			channel.clear();
+		}
+
+		for dy in -radius .. radius + 1 {
+			for dx in -radius .. radius + 1 {
+				let p = input.get_edge(x as i64 + dx, y as i64 + dy, edge);
+
+				channels[0].push(p.red);
+				channels[1].push(p.green);
+				channels[2].push(p.blue);
+				channels[3].push(p.alpha);
+			}
+		}
+
+		let mut p = [0.0; 4];
+
+		for (value, channel) in p.iter_mut().zip(&mut channels) {
+			*value = *channel.select_nth_unstable_by(area / 2, |a, b|
+				a.partial_cmp(b).unwrap_or(::std::cmp::Ordering::Equal)).1;
+		}
+
+		output.set(x, y, &Rgba::new(p[0], p[1], p[2], p[3]).into());
+	}
+
+	output
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use buffer;
+	use color::Luma;
+
+	fn noisy() -> buffer::Luma {
+		Buffer::from_raw(5, 5, vec![
+			 10,  10,  10,  10,  10,
+			 10, 255,  10,  10,  10,
+			 10,  10,  10,   0,  10,
+			 10,  10,  10,  10,  10,
+			200, 200, 200, 200, 200,
+		]).unwrap()
+	}
+
+	#[test]
+	fn histogram() {
+		let output = noisy().median(1);
+
+		assert_eq!(10, output[6]);
+		assert_eq!(10, output[13]);
+		assert_eq!(200, output[22]);
+	}
+
+	#[test]
+	fn select() {
+		let input  = noisy().convert::<Luma, f32>();
+		let output = by::<_, Luma, f32, Luma, u8>(&input, 1, EdgeMode::Clamp);
+
+		assert_eq!(noisy().median(1), output);
+	}
+}
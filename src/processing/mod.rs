@@ -46,10 +46,27 @@ pub use self::flip::Flip;
 pub mod rotate;
 pub use self::rotate::Rotate;
 
+/// Image transformation.
+pub mod transform;
+pub use self::transform::Transform;
+
 /// Image scaling.
 pub mod scale;
 pub use self::scale::Scale;
 
+/// sRGB encoding and decoding.
+pub mod srgb;
+
+/// Color difference metrics.
+pub mod distance;
+pub use self::distance::Distance;
+
+/// Multi-threaded processing.
+pub mod parallel;
+
+/// Separable resampling.
+pub mod resample;
+
 /// Image sampling.
 pub mod sample;
 pub use self::sample::Sample;
@@ -62,6 +79,62 @@ pub use self::blur::Blur;
 pub mod sharpen;
 pub use self::sharpen::Sharpen;
 
+/// Median filtering.
+pub mod median;
+pub use self::median::Median;
+
+/// Bilateral filtering.
+pub mod bilateral;
+pub use self::bilateral::Bilateral;
+
+/// Image histograms.
+pub mod histogram;
+pub use self::histogram::Histogram;
+
+/// Histogram equalization.
+pub mod equalize;
+pub use self::equalize::Equalize;
+
+/// Tonal and color adjustments.
+pub mod adjust;
+pub use self::adjust::Adjust;
+
+/// Alpha premultiplication.
+pub mod premultiply;
+pub use self::premultiply::Premultiply;
+
+/// Alpha compositing and blending.
+pub mod composite;
+pub use self::composite::Composite;
+
 /// Image dithering.
 pub mod dither;
 pub use self::dither::Dither;
+
+/// Convolution kernels.
+pub mod kernel;
+pub use self::kernel::Kernel;
+
+/// Image convolution.
+pub mod convolve;
+pub use self::convolve::Convolve;
+
+/// Morphological operations.
+pub mod morphology;
+pub use self::morphology::Morphology;
+
+/// Flood filling.
+pub mod fill;
+pub use self::fill::Fill;
+
+/// Connected-component labelling.
+pub mod label;
+pub use self::label::Label;
+
+/// Image thresholding.
+pub mod threshold;
+pub use self::threshold::Threshold;
+
+/// Content-aware resizing.
+pub mod carve;
+pub use self::carve::Carve;
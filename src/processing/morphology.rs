@@ -0,0 +1,528 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use buffer::Buffer;
+use pixel;
+use view;
+use color::{Rgba, Luma, Limited};
+
+/// A structuring element, anchored on its center.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Element {
+	width:  u32,
+	height: u32,
+	mask:   Vec<bool>,
+}
+
+impl Element {
+	/// Create a square `Element` with the given radius.
+	#[inline]
+	pub fn square(radius: u32) -> Self {
+		Element::from_fn(radius * 2 + 1, radius * 2 + 1, |_, _| true)
+	}
+
+	/// Create a cross shaped `Element` with the given radius.
+	#[inline]
+	pub fn cross(radius: u32) -> Self {
+		Element::from_fn(radius * 2 + 1, radius * 2 + 1, |x, y| x == radius || y == radius)
+	}
+
+	/// Create a disk shaped `Element` with the given radius.
+	#[inline]
+	pub fn disk(radius: u32) -> Self {
+		let r = radius as i64;
+
+		Element::from_fn(radius * 2 + 1, radius * 2 + 1, |x, y| {
+			let dx = x as i64 - r;
+			let dy = y as i64 - r;
+
+			dx * dx + dy * dy <= r * r
+		})
+	}
+
+	/// Create an `Element` from a mask, any pixel brighter than half is part of
+	/// the `Element`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::buffer;
+	/// use picto::color::Luma;
+	/// use picto::processing::morphology::Element;
+	///
+	/// // A diagonal line.
+	/// let mask = buffer::Luma::from_fn(3, 3, |x, y|
+	///     Luma::new(if x == y { 1.0 } else { 0.0 }));
+	///
+	/// Element::from_mask(&mask);
+	/// ```
+	#[inline]
+	pub fn from_mask<'i, C, I>(mask: I) -> Self
+		where C: pixel::Channel,
+		      Luma: pixel::Read<C>,
+		      I: Into<view::Read<'i, Luma, C>>
+	{
+		let mask = mask.into();
+		Element::from_fn(mask.width(), mask.height(), |x, y| mask.get(x, y).luma > 0.5)
+	}
+
+	/// Create an `Element` with the given function deciding which coordinates
+	/// are part of it.
+	#[inline]
+	pub fn from_fn<F>(width: u32, height: u32, mut func: F) -> Self
+		where F: FnMut(u32, u32) -> bool
+	{
+		let mut mask = Vec::with_capacity(width as usize * height as usize);
+
+		for y in 0 .. height {
+			for x in 0 .. width {
+				mask.push(func(x, y));
+			}
+		}
+
+		Element {
+			width,
+			height,
+			mask,
+		}
+	}
+
+	/// Get the width.
+	#[inline]
+	pub fn width(&self) -> u32 {
+		self.width
+	}
+
+	/// Get the height.
+	#[inline]
+	pub fn height(&self) -> u32 {
+		self.height
+	}
+
+	/// Check if the given coordinates are part of the `Element`.
+	///
+	/// # Panics
+	///
+	/// Requires that `x < self.width()` and `y < self.height()`, otherwise it
+	/// will panic.
+	#[inline]
+	pub fn get(&self, x: u32, y: u32) -> bool {
+		if x >= self.width || y >= self.height {
+			panic!("out of bounds");
+		}
+
+		self.mask[(y * self.width + x) as usize]
+	}
+
+	/// Get an `Iterator` over the offsets from the center that are part of the
+	/// `Element`.
+	#[inline]
+	fn offsets<'a>(&'a self) -> impl Iterator<Item = (i64, i64)> + 'a {
+		let cx = (self.width / 2) as i64;
+		let cy = (self.height / 2) as i64;
+		let w  = self.width as usize;
+
+		self.mask.iter().enumerate().filter(|&(_, &set)| set).map(move |(i, _)|
+			((i % w) as i64 - cx, (i / w) as i64 - cy))
+	}
+}
+
+/// Trait for types supporting morphological operations.
+///
+/// Color images are handled per channel, including the alpha.
+pub trait Morphology<P, C>
+	where P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	/// Erode with the given `Element`, shrinking bright areas.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Luma;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = read::from_path::<Luma, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// image.erode(&morphology::Element::square(1));
+	/// ```
+	fn erode(self, element: &Element) -> Buffer<P, C, Vec<C>>;
+
+	/// Dilate with the given `Element`, growing bright areas.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// image.dilate(&morphology::Element::disk(2));
+	/// ```
+	fn dilate(self, element: &Element) -> Buffer<P, C, Vec<C>>;
+
+	/// Open with the given `Element`, removing bright specks smaller than it.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Luma;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = read::from_path::<Luma, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// image.open(&morphology::Element::cross(1));
+	/// ```
+	fn open(self, element: &Element) -> Buffer<P, C, Vec<C>>;
+
+	/// Close with the given `Element`, filling dark holes smaller than it.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Luma;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = read::from_path::<Luma, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// image.close(&morphology::Element::cross(1));
+	/// ```
+	fn close(self, element: &Element) -> Buffer<P, C, Vec<C>>;
+
+	/// Get the morphological gradient, the difference between the dilation and
+	/// the erosion.
+	fn morphological_gradient(self, element: &Element) -> Buffer<P, C, Vec<C>>;
+
+	/// Get the white top-hat, the difference between the input and its opening.
+	fn top_hat(self, element: &Element) -> Buffer<P, C, Vec<C>>;
+
+	/// Get the black top-hat, the difference between the closing and the input.
+	fn black_hat(self, element: &Element) -> Buffer<P, C, Vec<C>>;
+}
+
+impl<'i, P, C, I> Morphology<P, C> for I
+	where P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+	      I: Into<view::Read<'i, P, C>>
+{
+	#[inline]
+	fn erode(self, element: &Element) -> Buffer<P, C, Vec<C>> {
+		erode::<_, P, C, P, C>(self, element)
+	}
+
+	#[inline]
+	fn dilate(self, element: &Element) -> Buffer<P, C, Vec<C>> {
+		dilate::<_, P, C, P, C>(self, element)
+	}
+
+	#[inline]
+	fn open(self, element: &Element) -> Buffer<P, C, Vec<C>> {
+		open::<_, P, C, P, C>(self, element)
+	}
+
+	#[inline]
+	fn close(self, element: &Element) -> Buffer<P, C, Vec<C>> {
+		close::<_, P, C, P, C>(self, element)
+	}
+
+	#[inline]
+	fn morphological_gradient(self, element: &Element) -> Buffer<P, C, Vec<C>> {
+		gradient::<_, P, C, P, C>(self, element)
+	}
+
+	#[inline]
+	fn top_hat(self, element: &Element) -> Buffer<P, C, Vec<C>> {
+		top_hat::<_, P, C, P, C>(self, element)
+	}
+
+	#[inline]
+	fn black_hat(self, element: &Element) -> Buffer<P, C, Vec<C>> {
+		black_hat::<_, P, C, P, C>(self, element)
+	}
+}
+
+/// Erode with the given `Element`.
+#[inline]
+pub fn erode<'i, I, PI, CI, PO, CO>(input: I, element: &Element) -> Buffer<PO, CO, Vec<CO>>
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	apply(&input.into(), element, f32::min)
+}
+
+/// Dilate with the given `Element`.
+#[inline]
+pub fn dilate<'i, I, PI, CI, PO, CO>(input: I, element: &Element) -> Buffer<PO, CO, Vec<CO>>
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	apply(&input.into(), element, f32::max)
+}
+
+/// Open with the given `Element`.
+#[inline]
+pub fn open<'i, I, PI, CI, PO, CO>(input: I, element: &Element) -> Buffer<PO, CO, Vec<CO>>
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	let tmp: Buffer<Rgba, f32, _> = apply(&input.into(), element, f32::min);
+	apply(&tmp.readable(Default::default()), element, f32::max)
+}
+
+/// Close with the given `Element`.
+#[inline]
+pub fn close<'i, I, PI, CI, PO, CO>(input: I, element: &Element) -> Buffer<PO, CO, Vec<CO>>
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	let tmp: Buffer<Rgba, f32, _> = apply(&input.into(), element, f32::max);
+	apply(&tmp.readable(Default::default()), element, f32::min)
+}
+
+/// Get the morphological gradient, the difference between the dilation and
+/// the erosion.
+#[inline]
+pub fn gradient<'i, I, PI, CI, PO, CO>(input: I, element: &Element) -> Buffer<PO, CO, Vec<CO>>
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	let input = input.into();
+	let a     = dilate::<_, PI, CI, Rgba, f32>(&input, element);
+	let b     = erode::<_, PI, CI, Rgba, f32>(&input, element);
+
+	difference(&a, &b)
+}
+
+/// Get the white top-hat, the difference between the input and its opening.
+#[inline]
+pub fn top_hat<'i, I, PI, CI, PO, CO>(input: I, element: &Element) -> Buffer<PO, CO, Vec<CO>>
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	let input = input.into();
+	let a     = input.convert::<Rgba, f32>();
+	let b     = open::<_, PI, CI, Rgba, f32>(&input, element);
+
+	difference(&a, &b)
+}
+
+/// Get the black top-hat, the difference between the closing and the input.
+#[inline]
+pub fn black_hat<'i, I, PI, CI, PO, CO>(input: I, element: &Element) -> Buffer<PO, CO, Vec<CO>>
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	let input = input.into();
+	let a     = close::<_, PI, CI, Rgba, f32>(&input, element);
+	let b     = input.convert::<Rgba, f32>();
+
+	difference(&a, &b)
+}
+
+/// Pick the pixel from the neighborhood with the given function, per channel.
+fn apply<PI, CI, PO, CO, F>(input: &view::Read<PI, CI>, element: &Element, func: F) -> Buffer<PO, CO, Vec<CO>>
+	where PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      F:  Fn(f32, f32) -> f32,
+{
+	let     width   = input.width() as i64;
+	let     height  = input.height() as i64;
+	let     offsets = element.offsets().collect::<Vec<_>>();
+	let mut output  = Buffer::<PO, CO, _>::new(input.width(), input.height());
+
+	for (x, y) in output.region().absolute() {
+		let mut result: Option<(f32, f32, f32, f32)> = None;
+
+		for &(dx, dy) in &offsets {
+			let sx = x as i64 + dx;
+			let sy = y as i64 + dy;
+
+			// Pixels outside of the input don't take part.
+			if sx < 0 || sy < 0 || sx >= width || sy >= height {
+				continue;
+			}
+
+			let p: (f32, f32, f32, f32) = input.get(sx as u32, sy as u32).into().to_pixel();
+
+			result = Some(if let Some(r) = result {
+				(func(r.0, p.0), func(r.1, p.1), func(r.2, p.2), func(r.3, p.3))
+			}
+			else {
+				p
+			});
+		}
+
+		let p = if let Some(p) = result {
+			Rgba::new(p.0, p.1, p.2, p.3)
+		}
+		else {
+			input.get(x, y).into()
+		};
+
+		output.set(x, y, &p.into());
+	}
+
+	output
+}
+
+/// Get the difference between the two buffers, keeping the alpha of the
+/// first.
+fn difference<PO, CO>(a: &Buffer<Rgba, f32, Vec<f32>>, b: &Buffer<Rgba, f32, Vec<f32>>) -> Buffer<PO, CO, Vec<CO>>
+	where PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+{
+	let mut output = Buffer::<PO, CO, _>::new(a.width(), a.height());
+
+	for (x, y) in a.region().absolute() {
+		let a = a.get(x, y);
+		let b = b.get(x, y);
+
+		output.set(x, y, &Rgba::new(a.red - b.red, a.green - b.green, a.blue - b.blue, a.alpha).clamp().into());
+	}
+
+	output
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use buffer;
+
+	fn mask(data: &[u8], width: u32) -> buffer::Luma {
+		Buffer::from_raw(width, data.len() as u32 / width, data.iter().map(|&v| v * 255).collect()).unwrap()
+	}
+
+	#[test]
+	fn element() {
+		let disk = Element::disk(2);
+
+		assert!(disk.get(2, 0));
+		assert!(!disk.get(0, 0));
+		assert!(disk.get(1, 1));
+
+		let cross = Element::cross(1);
+
+		assert!(cross.get(1, 0));
+		assert!(!cross.get(0, 0));
+	}
+
+	#[test]
+	fn erode_dilate() {
+		let input = mask(&[
+			0, 0, 0, 0, 0,
+			0, 1, 1, 1, 0,
+			0, 1, 1, 1, 0,
+			0, 1, 1, 1, 0,
+			0, 0, 0, 0, 0,
+		], 5);
+
+		assert_eq!(mask(&[
+			0, 0, 0, 0, 0,
+			0, 0, 0, 0, 0,
+			0, 0, 1, 0, 0,
+			0, 0, 0, 0, 0,
+			0, 0, 0, 0, 0,
+		], 5), input.erode(&Element::square(1)));
+
+		assert_eq!(mask(&[
+			0, 1, 1, 1, 0,
+			1, 1, 1, 1, 1,
+			1, 1, 1, 1, 1,
+			1, 1, 1, 1, 1,
+			0, 1, 1, 1, 0,
+		], 5), input.dilate(&Element::cross(1)));
+	}
+
+	#[test]
+	fn open_close() {
+		let input = mask(&[
+			1, 0, 0, 0, 0, 0, 0,
+			0, 0, 0, 0, 0, 0, 0,
+			0, 0, 1, 1, 1, 0, 0,
+			0, 0, 1, 0, 1, 0, 0,
+			0, 0, 1, 1, 1, 0, 0,
+			0, 0, 0, 0, 0, 0, 0,
+			0, 0, 0, 0, 0, 0, 0,
+		], 7);
+
+		let closed = input.close(&Element::square(1));
+
+		assert_eq!(mask(&[
+			0, 0, 0, 0, 0, 0, 0,
+			0, 0, 0, 0, 0, 0, 0,
+			0, 0, 1, 1, 1, 0, 0,
+			0, 0, 1, 1, 1, 0, 0,
+			0, 0, 1, 1, 1, 0, 0,
+			0, 0, 0, 0, 0, 0, 0,
+			0, 0, 0, 0, 0, 0, 0,
+		], 7), closed.open(&Element::square(1)));
+
+		assert_eq!(mask(&[
+			1, 0, 0, 0, 0, 0, 0,
+			0, 0, 0, 0, 0, 0, 0,
+			0, 0, 0, 0, 0, 0, 0,
+			0, 0, 0, 0, 0, 0, 0,
+			0, 0, 0, 0, 0, 0, 0,
+			0, 0, 0, 0, 0, 0, 0,
+			0, 0, 0, 0, 0, 0, 0,
+		], 7), closed.top_hat(&Element::square(1)));
+	}
+}
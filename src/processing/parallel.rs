@@ -0,0 +1,171 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+//! Spreading the processing over multiple threads.
+//!
+//! With the `parallel` feature enabled the independent rows of sampling,
+//! scaling, blurring, sharpening, flipping, rotating and ordered dithering
+//! are processed on a shared thread pool, the results are identical to the
+//! sequential ones. Error diffusion and seam carving depend on the previous
+//! pixels, so they always run on the calling thread.
+//!
+//! The feature requires `pixel::Channel` types and `Distance` spaces to be
+//! `Send` and `Sync`, without it everything runs on the calling thread.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(feature = "parallel")]
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "parallel")]
+use rayon::{ThreadPool, ThreadPoolBuilder};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+use pixel;
+use view;
+use util::Shared;
+
+/// The requested amount of threads, `0` picks one per CPU.
+static THREADS: AtomicUsize = AtomicUsize::new(0);
+
+/// The pool, along with the amount of threads it was built for.
+#[cfg(feature = "parallel")]
+static POOL: Mutex<Option<(usize, Arc<ThreadPool>)>> = Mutex::new(None);
+
+/// Set the amount of threads used for processing, `0` picks one per CPU.
+///
+/// # Example
+///
+/// ```
+/// use picto::processing::parallel;
+///
+/// parallel::set_threads(2);
+/// ```
+pub fn set_threads(amount: usize) {
+	THREADS.store(amount, Ordering::SeqCst);
+}
+
+/// Get the amount of threads used for processing.
+#[cfg(feature = "parallel")]
+pub fn threads() -> usize {
+	pool().current_num_threads()
+}
+
+/// Get the amount of threads used for processing.
+#[cfg(not(feature = "parallel"))]
+pub fn threads() -> usize {
+	1
+}
+
+/// Get the pool, building it again if the amount of threads changed.
+#[cfg(feature = "parallel")]
+fn pool() -> Arc<ThreadPool> {
+	let     amount = THREADS.load(Ordering::SeqCst);
+	let mut pool   = POOL.lock().unwrap_or_else(|e| e.into_inner());
+
+	if let Some((current, ref pool)) = *pool {
+		if current == amount {
+			return pool.clone();
+		}
+	}
+
+	let built = Arc::new(ThreadPoolBuilder::new().num_threads(amount).build()
+		.expect("failed to build the thread pool"));
+
+	*pool = Some((amount, built.clone()));
+	built
+}
+
+/// Map every item with its index, keeping the order.
+#[cfg(feature = "parallel")]
+pub(crate) fn map<T, R, F>(items: Vec<T>, func: F) -> Vec<R>
+	where T: Shared,
+	      R: Shared,
+	      F: Fn(usize, T) -> R + Shared
+{
+	pool().install(||
+		items.into_par_iter().enumerate().map(|(i, item)| func(i, item)).collect())
+}
+
+/// Map every item with its index, keeping the order.
+#[cfg(not(feature = "parallel"))]
+pub(crate) fn map<T, R, F>(items: Vec<T>, func: F) -> Vec<R>
+	where T: Shared,
+	      R: Shared,
+	      F: Fn(usize, T) -> R + Shared
+{
+	items.into_iter().enumerate().map(|(i, item)| func(i, item)).collect()
+}
+
+/// Call the function with every item and its index.
+#[inline]
+pub(crate) fn each<T, F>(items: Vec<T>, func: F)
+	where T: Shared,
+	      F: Fn(usize, T) + Shared
+{
+	map(items, func);
+}
+
+/// Call the function with every row of the output and its index.
+#[inline]
+pub(crate) fn rows<'o, P, C, O, F>(output: O, func: F)
+	where P: pixel::Write<C>,
+	      C: pixel::Channel,
+	      O: Into<view::Write<'o, P, C>>,
+	      F: Fn(u32, &mut view::Write<'o, P, C>) + Shared
+{
+	each(output.into().rows(), |y, mut row| func(y as u32, &mut row));
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use buffer::Buffer;
+	use color::Rgba;
+	use orientation::Orientation;
+	use processing::prelude::*;
+	use processing::ditherer::palette::{Palette, table, mapper};
+
+	fn image() -> Buffer<Rgba, u8, Vec<u8>> {
+		Buffer::from_fn(37, 23, |x, y|
+			Rgba::new((x * 7 % 256) as f32 / 255.0, (y * 11 % 256) as f32 / 255.0,
+				((x ^ y) * 13 % 256) as f32 / 255.0, 1.0 - (x + y) as f32 / 60.0))
+	}
+
+	#[test]
+	fn identical() {
+		let image = image();
+		let run   = |threads| {
+			set_threads(threads);
+
+			(image.scale_to::<scaler::Lanczos3>(53, 17).into_raw(),
+			 image.scale_to::<scaler::Nearest>(71, 29).into_raw(),
+			 image.scale_by::<scaler::Xbrz>(3.0).into_raw(),
+			 image.blur(2.5).into_raw(),
+			 image.sharpen(1.5, 0.7).into_raw(),
+			 (&mut image.clone()).flipped(Orientation::Vertical).into_raw(),
+			 (&mut image.clone()).flipped(Orientation::Horizontal).into_raw(),
+			 image.rotate(90.0).into_raw(),
+			 image.rotate(270.0).into_raw(),
+			 image.rotate_with::<sampler::Linear>(33.0, rotate::Expand, &Rgba::new(0.0, 0.0, 0.0, 0.0)).into_raw(),
+			 image.dither::<Palette<table::Gray4, mapper::Bayer8>>(4).into_raw(),
+			 image.dither::<Palette<table::Gray4, mapper::Nearest>>(4).into_raw())
+		};
+
+		let one = run(1);
+		assert_eq!(one, run(4));
+		assert_eq!(one, run(0));
+	}
+}
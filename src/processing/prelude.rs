@@ -15,14 +15,37 @@
 pub use processing::sampler;
 pub use processing::scaler;
 pub use processing::ditherer;
+pub use processing::kernel;
+pub use processing::morphology;
+pub use processing::adjust;
+pub use processing::composite;
+pub use processing::srgb;
+pub use processing::distance;
+pub use processing::fill;
+pub use processing::label;
+pub use processing::threshold;
+pub use processing::carve;
 
 pub use processing::Flip;
 pub use processing::Rotate;
+pub use processing::Transform;
 pub use processing::Scale;
 pub use processing::Sample;
 pub use processing::Blur;
 pub use processing::Sharpen;
+pub use processing::Median;
+pub use processing::Bilateral;
+pub use processing::Equalize;
+pub use processing::Adjust;
+pub use processing::Premultiply;
+pub use processing::Composite;
 pub use processing::Dither;
+pub use processing::Convolve;
+pub use processing::Morphology;
+pub use processing::Fill;
+pub use processing::Label;
+pub use processing::Threshold;
+pub use processing::Carve;
 
 /// Flipping orientation.
 pub mod flip {
@@ -35,3 +58,9 @@ pub mod sample {
 	pub use orientation::Orientation::Vertical as Vertically;
 	pub use orientation::Orientation::Horizontal as Horizontally;
 }
+
+/// Rotation canvas mode.
+pub mod rotate {
+	pub use processing::rotate::Mode::Expand;
+	pub use processing::rotate::Mode::Crop;
+}
@@ -0,0 +1,154 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use buffer::Buffer;
+use pixel;
+use view;
+use color::Rgba;
+
+/// Trait for types with a premultipliable alpha.
+pub trait Premultiply<P, C>
+	where P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	/// Multiply the color channels by the alpha.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::Buffer;
+	/// use picto::color::Rgba;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = Buffer::<Rgba, f32, _>::from_pixel(1, 1, &Rgba::new(1.0, 1.0, 1.0, 0.5));
+	///
+	/// assert_eq!(Rgba::new(0.5, 0.5, 0.5, 0.5), image.premultiply().get(0, 0));
+	/// ```
+	fn premultiply(self) -> Buffer<P, C, Vec<C>>;
+
+	/// Divide the color channels by the alpha, fully transparent pixels end up
+	/// black.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::Buffer;
+	/// use picto::color::Rgba;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = Buffer::<Rgba, f32, _>::from_pixel(1, 1, &Rgba::new(0.5, 0.5, 0.5, 0.5));
+	///
+	/// assert_eq!(Rgba::new(1.0, 1.0, 1.0, 0.5), image.unpremultiply().get(0, 0));
+	/// ```
+	fn unpremultiply(self) -> Buffer<P, C, Vec<C>>;
+}
+
+impl<'i, P, C, I> Premultiply<P, C> for I
+	where P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+	      I: Into<view::Read<'i, P, C>>
+{
+	#[inline]
+	fn premultiply(self) -> Buffer<P, C, Vec<C>> {
+		it::<_, P, C, P, C>(self)
+	}
+
+	#[inline]
+	fn unpremultiply(self) -> Buffer<P, C, Vec<C>> {
+		undo::<_, P, C, P, C>(self)
+	}
+}
+
+/// Multiply the color channels by the alpha.
+#[inline]
+pub fn it<'i, I, PI, CI, PO, CO>(input: I) -> Buffer<PO, CO, Vec<CO>>
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	input.into().convert_with(|p| {
+		let p: Rgba = p.into();
+		Rgba::new(p.red * p.alpha, p.green * p.alpha, p.blue * p.alpha, p.alpha).into()
+	})
+}
+
+/// Divide the color channels by the alpha.
+#[inline]
+pub fn undo<'i, I, PI, CI, PO, CO>(input: I) -> Buffer<PO, CO, Vec<CO>>
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	input.into().convert_with(|p| {
+		let p: Rgba = p.into();
+
+		if p.alpha <= 0.0 {
+			Rgba::new(0.0, 0.0, 0.0, 0.0).into()
+		}
+		else {
+			Rgba::new(
+				(p.red / p.alpha).min(1.0),
+				(p.green / p.alpha).min(1.0),
+				(p.blue / p.alpha).min(1.0),
+				p.alpha).into()
+		}
+	})
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use buffer;
+	use color::Rgba;
+	use processing::prelude::*;
+
+	#[test]
+	fn roundtrip() {
+		let image = buffer::Rgba::from_pixel(1, 1, &Rgba::new(0.2, 0.4, 0.6, 1.0));
+		assert_eq!(image, image.premultiply().unpremultiply());
+	}
+
+	#[test]
+	fn halo() {
+		// Opaque white next to transparent black.
+		let image = buffer::Rgba::from_fn(8, 8, |x, _|
+			if x < 4 { Rgba::new(1.0, 1.0, 1.0, 1.0) } else { Rgba::new(0.0, 0.0, 0.0, 0.0) });
+
+		for (_, _, px) in image.resize::<scaler::Lanczos3>(5, 5).pixels() {
+			let px = px.get();
+
+			if px.alpha > 0.0 {
+				assert!(px.red > 0.99 && px.green > 0.99 && px.blue > 0.99);
+			}
+		}
+
+		for (_, _, px) in image.blur(1.5).pixels() {
+			let px = px.get();
+
+			if px.alpha > 0.0 {
+				assert!(px.red > 0.99);
+			}
+		}
+	}
+}
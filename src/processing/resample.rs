@@ -0,0 +1,705 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::any::TypeId;
+use std::slice;
+
+use buffer::Buffer;
+use pixel;
+use view;
+use color::{Rgba, Rgb, Luma, Lumaa};
+use edge::EdgeMode;
+use super::{Sampler, parallel};
+
+/// The precision of the weights used for `u8` channels.
+const PRECISION: u32 = 14;
+
+/// The extra precision of the `u8` channels between the two passes, small
+/// enough for the second pass to fit in 32 bits.
+const INTERMEDIATE: u32 = 6;
+
+/// The kernel weights for every output coordinate along one direction.
+///
+/// The weights are normalized and the edges are already resolved, so every
+/// tap points within the input and the taps falling on a constant edge are
+/// merged into a single weight.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Weights {
+	bounds:  Vec<(usize, usize)>,
+	taps:    Vec<(usize, f32)>,
+	outside: Vec<f32>,
+}
+
+impl Weights {
+	/// Compute the weights to resample from the input length to the output
+	/// length with the given `Sampler`.
+	#[inline]
+	pub fn new<A: Sampler>(input: u32, output: u32, edge: EdgeMode) -> Self {
+		Self::with(input, output, edge, A::support(), A::kernel)
+	}
+
+	/// Compute the weights to resample from the input length to the output
+	/// length with the given support and kernel function.
+	///
+	/// The kernel is called once per tap of every output coordinate, an empty
+	/// input gives every output coordinate entirely to the constant edge color.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::EdgeMode;
+	/// use picto::processing::resample::Weights;
+	///
+	/// let weights = Weights::with(4, 2, EdgeMode::Clamp, 1.0, |x| (1.0 - x.abs()).max(0.0));
+	///
+	/// assert_eq!(2, weights.len());
+	/// assert_eq!(&[(0, 0.5), (1, 0.375), (2, 0.125)], weights.get(0).0);
+	/// assert_eq!(&[(1, 0.125), (2, 0.375), (3, 0.5)], weights.get(1).0);
+	/// ```
+	pub fn with<F>(input: u32, output: u32, edge: EdgeMode, support: f32, mut kernel: F) -> Self
+		where F: FnMut(f32) -> f32
+	{
+		let mut weights = Weights {
+			bounds:  Vec::with_capacity(output as usize),
+			taps:    Vec::new(),
+			outside: Vec::with_capacity(output as usize),
+		};
+
+		if input == 0 {
+			for _ in 0 .. output {
+				weights.bounds.push((0, 0));
+				weights.outside.push(1.0);
+			}
+
+			return weights;
+		}
+
+		let ratio  = input as f32 / output as f32;
+		let scale  = if ratio > 1.0 { ratio } else { 1.0 };
+		let radius = (support * scale).ceil();
+
+		for out in 0 .. output {
+			let center = (out as f32 + 0.5) * ratio;
+			let left   = (center - 0.5 - radius).floor() as i64;
+			let right  = (center - 0.5 + radius).ceil() as i64;
+			let start  = weights.taps.len();
+
+			let mut sum     = 0.0;
+			let mut outside = 0.0;
+
+			for i in left .. right + 1 {
+				// Taps sit at the center of their pixel.
+				let w = kernel((i as f32 + 0.5 - center) / scale);
+
+				if w == 0.0 {
+					continue;
+				}
+
+				sum += w;
+
+				match edge.index(i, input) {
+					Some(index) => {
+						let index = index as usize;
+
+						// Clamped edges repeat the same pixel.
+						match weights.taps[start ..].last_mut() {
+							Some(&mut (last, ref mut weight)) if last == index =>
+								*weight += w,
+
+							_ =>
+								weights.taps.push((index, w)),
+						}
+					}
+
+					None =>
+						outside += w,
+				}
+			}
+
+			if sum == 0.0 {
+				weights.taps.truncate(start);
+
+				match edge.index((center - 0.5).floor() as i64, input) {
+					Some(index) =>
+						weights.taps.push((index as usize, 1.0)),
+
+					None =>
+						outside = 1.0,
+				}
+			}
+			else {
+				for tap in &mut weights.taps[start ..] {
+					tap.1 /= sum;
+				}
+
+				outside /= sum;
+			}
+
+			weights.bounds.push((start, weights.taps.len()));
+			weights.outside.push(outside);
+		}
+
+		weights
+	}
+
+	/// Get the amount of output coordinates.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.bounds.len()
+	}
+
+	/// Check if there are no output coordinates.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.bounds.is_empty()
+	}
+
+	/// Get the input coordinates with their weight for the given output
+	/// coordinate, along with the weight of the constant edge color.
+	#[inline]
+	pub fn get(&self, index: usize) -> (&[(usize, f32)], f32) {
+		let (start, end) = self.bounds[index];
+		(&self.taps[start .. end], self.outside[index])
+	}
+
+	/// Convert the weights to fixed point, keeping their sum exact.
+	fn fixed(&self) -> Fixed {
+		let     one     = 1i32 << PRECISION;
+		let mut taps    = Vec::with_capacity(self.taps.len());
+		let mut outside = Vec::with_capacity(self.outside.len());
+
+		for index in 0 .. self.len() {
+			let (weights, rest) = self.get(index);
+			let start           = taps.len();
+
+			taps.extend(weights.iter().map(|&(i, w)| (i, (w * one as f32).round() as i32)));
+			outside.push((rest * one as f32).round() as i32);
+
+			// Give the rounding error to the largest weight.
+			let error = one - taps[start ..].iter().map(|t| t.1).sum::<i32>() - outside[index];
+
+			match taps[start ..].iter_mut().max_by_key(|t| t.1.abs()) {
+				Some(tap) if tap.1.abs() >= outside[index].abs() =>
+					tap.1 += error,
+
+				_ =>
+					outside[index] += error,
+			}
+		}
+
+		Fixed {
+			bounds: self.bounds.clone(),
+			taps,
+			outside,
+		}
+	}
+}
+
+/// The weights in fixed point.
+struct Fixed {
+	bounds:  Vec<(usize, usize)>,
+	taps:    Vec<(usize, i32)>,
+	outside: Vec<i32>,
+}
+
+impl Fixed {
+	#[inline]
+	fn get(&self, index: usize) -> (&[(usize, i32)], i32) {
+		let (start, end) = self.bounds[index];
+		(&self.taps[start .. end], self.outside[index])
+	}
+}
+
+/// Resample with the given `Sampler`, handling the edges with the given
+/// `EdgeMode`.
+#[inline]
+pub fn scale<'i, A, I, PI, CI, PO, CO>(input: I, width: u32, height: u32, edge: EdgeMode) -> Buffer<PO, CO, Vec<CO>>
+	where A:  Sampler,
+	      I:  Into<view::Read<'i, PI, CI>>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	scale_with(input, width, height, edge, A::support(), A::kernel)
+}
+
+/// Resample with the given support and kernel function, handling the edges
+/// with the given `EdgeMode`.
+///
+/// The weights are computed once for every output row and column, then the
+/// input is resampled horizontally and vertically working on the raw
+/// channels. Images with `u8` channels of `Luma`, `Lumaa`, `Rgb` or `Rgba`
+/// pixels without any transparency go through fixed point arithmetic, the
+/// others are resampled with premultiplied alpha.
+///
+/// # Example
+///
+/// ```
+/// use picto::{read, EdgeMode};
+/// use picto::color::Rgb;
+/// use picto::processing::resample;
+/// use picto::processing::sampler::{self, Sampler};
+///
+/// let image  = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+/// let scaled = resample::scale_with::<_, Rgb, u8, Rgb, u8, _>(&image, 100, 50, EdgeMode::Clamp,
+///     sampler::Lanczos3::support(), sampler::Lanczos3::kernel);
+///
+/// assert_eq!((100, 50), scaled.dimensions());
+/// ```
+pub fn scale_with<'i, I, PI, CI, PO, CO, F>(input: I, width: u32, height: u32, edge: EdgeMode, support: f32, mut kernel: F) -> Buffer<PO, CO, Vec<CO>>
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      F:  FnMut(f32) -> f32
+{
+	let input      = input.into();
+	let horizontal = Weights::with(input.width(), width, edge, support, &mut kernel);
+	let vertical   = Weights::with(input.height(), height, edge, support, &mut kernel);
+
+	if let Some(output) = fixed::<PI, CI, PO, CO>(&input, &horizontal, &vertical, edge) {
+		return output;
+	}
+
+	let mut output = Buffer::<PO, CO, _>::new(width, height);
+	Plane::new(&input)
+		.horizontal(&horizontal, constant(edge))
+		.vertical(&vertical, constant(edge))
+		.write(&mut output);
+
+	output
+}
+
+/// Resample vertically with the given weights, handling the constant edge
+/// with the given `EdgeMode`.
+pub fn vertically<'i, 'o, I, O, PI, CI, PO, CO>(input: I, output: O, weights: &Weights, edge: EdgeMode)
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      O:  Into<view::Write<'o, PO, CO>>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	Plane::new(&input.into()).vertical(weights, constant(edge)).write(output);
+}
+
+/// Resample horizontally with the given weights, handling the constant edge
+/// with the given `EdgeMode`.
+pub fn horizontally<'i, 'o, I, O, PI, CI, PO, CO>(input: I, output: O, weights: &Weights, edge: EdgeMode)
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      O:  Into<view::Write<'o, PO, CO>>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	Plane::new(&input.into()).horizontal(weights, constant(edge)).write(output);
+}
+
+/// Get the premultiplied constant edge color.
+#[inline]
+fn constant(edge: EdgeMode) -> [f32; 4] {
+	match edge {
+		EdgeMode::Constant(c) =>
+			[c.red * c.alpha, c.green * c.alpha, c.blue * c.alpha, c.alpha],
+
+		_ =>
+			[0.0; 4]
+	}
+}
+
+/// Pixels with premultiplied alpha stored as raw channels.
+struct Plane {
+	width:  usize,
+	height: usize,
+	data:   Vec<f32>,
+}
+
+impl Plane {
+	/// Convert the input, every pixel is converted only once.
+	fn new<P, C>(input: &view::Read<P, C>) -> Self
+		where P: Into<Rgba> + pixel::Read<C>,
+		      C: pixel::Channel,
+	{
+		let mut data = Vec::with_capacity(input.width() as usize * input.height() as usize * 4);
+
+		for (_, _, px) in input.pixels() {
+			let p = px.get().into();
+			data.extend_from_slice(&[p.red * p.alpha, p.green * p.alpha, p.blue * p.alpha, p.alpha]);
+		}
+
+		Plane {
+			width:  input.width() as usize,
+			height: input.height() as usize,
+			data,
+		}
+	}
+
+	/// Resample every row.
+	fn horizontal(&self, weights: &Weights, constant: [f32; 4]) -> Plane {
+		let mut data = vec![0.0; weights.len() * self.height * 4];
+
+		if !data.is_empty() {
+			parallel::each(data.chunks_mut(weights.len() * 4).collect(), |y, row: &mut [f32]| {
+				let input = &self.data[y * self.width * 4 .. (y + 1) * self.width * 4];
+
+				for (x, output) in row.chunks_mut(4).enumerate() {
+					let (taps, outside) = weights.get(x);
+					let mut t = [constant[0] * outside, constant[1] * outside, constant[2] * outside, constant[3] * outside];
+
+					for &(i, w) in taps {
+						let p = &input[i * 4 .. i * 4 + 4];
+
+						t[0] += p[0] * w;
+						t[1] += p[1] * w;
+						t[2] += p[2] * w;
+						t[3] += p[3] * w;
+					}
+
+					output.copy_from_slice(&t);
+					limit(output);
+				}
+			});
+		}
+
+		Plane { width: weights.len(), height: self.height, data }
+	}
+
+	/// Resample every column, a whole row at a time.
+	fn vertical(&self, weights: &Weights, constant: [f32; 4]) -> Plane {
+		let mut data = vec![0.0; self.width * weights.len() * 4];
+
+		if !data.is_empty() {
+			parallel::each(data.chunks_mut(self.width * 4).collect(), |y, row: &mut [f32]| {
+				let (taps, outside) = weights.get(y);
+
+				for (c, value) in row.iter_mut().enumerate() {
+					*value = constant[c % 4] * outside;
+				}
+
+				for &(i, w) in taps {
+					for (value, p) in row.iter_mut().zip(&self.data[i * self.width * 4 .. (i + 1) * self.width * 4]) {
+						*value += p * w;
+					}
+				}
+
+				for output in row.chunks_mut(4) {
+					limit(output);
+				}
+			});
+		}
+
+		Plane { width: self.width, height: weights.len(), data }
+	}
+
+	/// Write the pixels back with straight alpha.
+	fn write<'o, O, P, C>(&self, output: O)
+		where O: Into<view::Write<'o, P, C>>,
+		      P: From<Rgba> + pixel::Write<C>,
+		      C: pixel::Channel,
+	{
+		let output = output.into();
+
+		debug_assert_eq!(self.width, output.width() as usize);
+		debug_assert_eq!(self.height, output.height() as usize);
+
+		parallel::rows(output, |y, row| {
+			let input = &self.data[y as usize * self.width * 4 .. (y as usize + 1) * self.width * 4];
+
+			for (x, p) in input.chunks(4).enumerate() {
+				let px = if p[3] == 0.0 {
+					Rgba::new(0.0, 0.0, 0.0, 0.0)
+				}
+				else {
+					Rgba::new(p[0] / p[3], p[1] / p[3], p[2] / p[3], p[3])
+				};
+
+				row.set(x as u32, 0, &px.into());
+			}
+		});
+	}
+}
+
+/// Keep the premultiplied pixel within the valid range, the same as clamping
+/// its straight color.
+#[inline]
+fn limit(p: &mut [f32]) {
+	p[3] = p[3].clamp(0.0, 1.0);
+	p[0] = p[0].clamp(0.0, p[3]);
+	p[1] = p[1].clamp(0.0, p[3]);
+	p[2] = p[2].clamp(0.0, p[3]);
+}
+
+/// Get the amount of channels and whether the last one is the alpha for the
+/// pixels whose `u8` channels can be interpolated directly.
+fn layout<P: 'static>() -> Option<(usize, bool)> {
+	let id = TypeId::of::<P>();
+
+	if id == TypeId::of::<Luma>() {
+		Some((1, false))
+	}
+	else if id == TypeId::of::<Lumaa>() {
+		Some((2, true))
+	}
+	else if id == TypeId::of::<Rgb>() {
+		Some((3, false))
+	}
+	else if id == TypeId::of::<Rgba>() {
+		Some((4, true))
+	}
+	else {
+		None
+	}
+}
+
+/// Resample in fixed point, if the input can be.
+///
+/// The channels are interpolated directly, so it's only possible for opaque
+/// pixels where they would be the same premultiplied.
+fn fixed<PI, CI, PO, CO>(input: &view::Read<PI, CI>, horizontal: &Weights, vertical: &Weights, edge: EdgeMode) -> Option<Buffer<PO, CO, Vec<CO>>>
+	where PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	if TypeId::of::<CI>() != TypeId::of::<u8>() || TypeId::of::<CO>() != TypeId::of::<u8>() || TypeId::of::<PI>() != TypeId::of::<PO>() {
+		return None;
+	}
+
+	let (channels, alpha) = layout::<PI>()?;
+
+	// The channels were checked to be `u8`.
+	let row = |y: u32| -> &[u8] {
+		let row = input.row(y);
+		unsafe { slice::from_raw_parts(row.as_ptr() as *const u8, row.len()) }
+	};
+
+	if alpha && (0 .. input.height()).any(|y| row(y).chunks(channels).any(|p| p[channels - 1] != 255)) {
+		return None;
+	}
+
+	// The constant color is converted to the pixel so its channels can be used
+	// directly.
+	let mut constant = [0i32; 4];
+
+	if let EdgeMode::Constant(color) = edge {
+		if color.alpha < 1.0 {
+			return None;
+		}
+
+		let pixel = Buffer::<PO, CO, _>::from_pixel(1, 1, &color.into());
+
+		for (c, value) in constant.iter_mut().zip(pixel.iter()) {
+			*c = unsafe { *(value as *const CO as *const u8) } as i32;
+		}
+	}
+
+	let width  = horizontal.len();
+	let height = vertical.len();
+
+	// Resample every row keeping some of the precision.
+	let     horizontal = horizontal.fixed();
+	let mut middle     = vec![0u16; width * input.height() as usize * channels];
+
+	if !middle.is_empty() {
+		parallel::each(middle.chunks_mut(width * channels).collect(), |y, output: &mut [u16]| {
+			let input = row(y as u32);
+
+			match channels {
+				1 => horizontally_fixed::<1>(input, output, &horizontal, &constant),
+				2 => horizontally_fixed::<2>(input, output, &horizontal, &constant),
+				3 => horizontally_fixed::<3>(input, output, &horizontal, &constant),
+				_ => horizontally_fixed::<4>(input, output, &horizontal, &constant),
+			}
+		});
+	}
+
+	// Resample every column, a whole row at a time.
+	let     vertical = vertical.fixed();
+	let mut output   = Buffer::<PO, CO, _>::new(width as u32, height as u32);
+
+	if width > 0 && height > 0 {
+		let data = unsafe { slice::from_raw_parts_mut(output.as_mut_ptr() as *mut u8, width * height * channels) };
+
+		parallel::each(data.chunks_mut(width * channels).collect(), |y, output: &mut [u8]| {
+			let (taps, outside) = vertical.get(y);
+			let mut t = vec![0i32; output.len()];
+
+			if outside != 0 {
+				for px in t.chunks_mut(channels) {
+					for (value, c) in px.iter_mut().zip(&constant) {
+						*value = (c << INTERMEDIATE) * outside;
+					}
+				}
+			}
+
+			for &(i, w) in taps {
+				for (value, &p) in t.iter_mut().zip(&middle[i * width * channels .. (i + 1) * width * channels]) {
+					*value += p as i32 * w;
+				}
+			}
+
+			for (output, value) in output.iter_mut().zip(t) {
+				*output = (value >> (PRECISION + INTERMEDIATE)).clamp(0, 255) as u8;
+			}
+		});
+	}
+
+	Some(output)
+}
+
+/// Resample a row of pixels with the given amount of channels in fixed
+/// point, keeping some of the precision.
+#[inline]
+fn horizontally_fixed<const N: usize>(input: &[u8], output: &mut [u16], weights: &Fixed, constant: &[i32; 4]) {
+	for (x, output) in output.chunks_exact_mut(N).enumerate() {
+		let (taps, outside) = weights.get(x);
+		let mut t = [0i32; N];
+
+		for (t, c) in t.iter_mut().zip(constant) {
+			*t = c * outside;
+		}
+
+		for &(i, w) in taps {
+			let p = &input[i * N .. i * N + N];
+
+			for c in 0 .. N {
+				t[c] += p[c] as i32 * w;
+			}
+		}
+
+		for (value, t) in output.iter_mut().zip(&t) {
+			*value = (t >> (PRECISION - INTERMEDIATE)).clamp(0, 255 << INTERMEDIATE) as u16;
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use buffer;
+	use processing::sampler::{Linear, Lanczos3};
+	use processing::prelude::*;
+
+	#[test]
+	fn weights() {
+		let weights = Weights::new::<Linear>(4, 8, EdgeMode::Clamp);
+
+		for index in 0 .. weights.len() {
+			let (taps, outside) = weights.get(index);
+			assert!((taps.iter().map(|t| t.1).sum::<f32>() + outside - 1.0).abs() < 0.0001);
+		}
+
+		// The taps sit at the center of their pixel.
+		let weights = Weights::new::<Linear>(2, 4, EdgeMode::Clamp);
+		assert_eq!((&[(0, 1.0)][..], 0.0), weights.get(0));
+		assert_eq!((&[(0, 0.75), (1, 0.25)][..], 0.0), weights.get(1));
+
+		let weights = Weights::new::<Linear>(8, 4, EdgeMode::Clamp);
+		assert_eq!((&[(1, 0.125), (2, 0.375), (3, 0.375), (4, 0.125)][..], 0.0), weights.get(1));
+
+		// The clamped taps are merged.
+		let weights = Weights::new::<Lanczos3>(4, 2, EdgeMode::Clamp);
+		assert_eq!(vec![0, 1, 2, 3], weights.get(0).0.iter().map(|t| t.0).collect::<Vec<_>>());
+
+		let weights = Weights::new::<Lanczos3>(4, 2, EdgeMode::Constant(Rgba::new(0.0, 0.0, 0.0, 1.0)));
+		assert!(weights.get(0).1 != 0.0);
+	}
+
+	#[test]
+	fn fixed() {
+		let image = buffer::Rgb::from_fn(37, 23, |x, y|
+			Rgb::new((x * 7 % 256) as f32 / 255.0, (y * 11 % 256) as f32 / 255.0, ((x ^ y) * 13 % 256) as f32 / 255.0));
+
+		let fixed = scale::<Lanczos3, _, Rgb, u8, Rgb, u8>(&image, 53, 17, EdgeMode::Clamp);
+		let float = scale::<Lanczos3, _, Rgb, u8, Rgb, f32>(&image, 53, 17, EdgeMode::Clamp)
+			.convert::<Rgb, u8>();
+
+		for (a, b) in fixed.iter().zip(float.iter()) {
+			assert!((*a as i32 - *b as i32).abs() <= 1);
+		}
+
+		// Mirrored images give mirrored results.
+		let mirror = buffer::Rgb::from_fn(37, 23, |x, y| image.get(36 - x, y));
+		let a      = scale::<Lanczos3, _, Rgb, u8, Rgb, u8>(&image, 53, 17, EdgeMode::Clamp);
+		let b      = scale::<Lanczos3, _, Rgb, u8, Rgb, u8>(&mirror, 53, 17, EdgeMode::Clamp);
+
+		for (x, y, px) in a.pixels() {
+			let (p, q) = (px.get(), b.get(52 - x, y));
+
+			assert!((p.red - q.red).abs() <= 1.0 / 255.0 + 0.0001);
+			assert!((p.green - q.green).abs() <= 1.0 / 255.0 + 0.0001);
+			assert!((p.blue - q.blue).abs() <= 1.0 / 255.0 + 0.0001);
+		}
+
+		// Heavy downscales accumulate a lot of taps.
+		let image = buffer::Rgb::from_fn(1000, 1000, |x, y|
+			Rgb::new((x * 7 % 256) as f32 / 255.0, (y * 11 % 256) as f32 / 255.0, ((x ^ y) * 13 % 256) as f32 / 255.0));
+
+		let fixed = scale::<Lanczos3, _, Rgb, u8, Rgb, u8>(&image, 9, 9, EdgeMode::Clamp);
+		let float = scale::<Lanczos3, _, Rgb, u8, Rgb, f32>(&image, 9, 9, EdgeMode::Clamp)
+			.convert::<Rgb, u8>();
+
+		for (a, b) in fixed.iter().zip(float.iter()) {
+			assert!((*a as i32 - *b as i32).abs() <= 1);
+		}
+
+		// Constant images stay constant.
+		let flat = buffer::Rgb::from_pixel(7, 5, &Rgb::new(0.2, 0.4, 0.6));
+		assert_eq!(flat.get(0, 0), scale::<Lanczos3, _, Rgb, u8, Rgb, u8>(&flat, 19, 3, EdgeMode::Clamp).get(9, 1));
+	}
+
+	#[test]
+	fn empty() {
+		let image = buffer::Rgb::new(0, 0);
+
+		let result = scale::<Lanczos3, _, Rgb, u8, Rgb, u8>(&image, 3, 2, EdgeMode::Clamp);
+		assert_eq!((3, 2), result.dimensions());
+		assert!(result.iter().all(|&c| c == 0));
+
+		let result = scale::<Lanczos3, _, Rgb, u8, Rgb, u8>(&image, 3, 2, EdgeMode::Constant(Rgba::new(1.0, 0.0, 0.0, 1.0)));
+		assert_eq!(Rgb::new(1.0, 0.0, 0.0), result.get(2, 1));
+
+		let result = scale::<Lanczos3, _, Rgb, u8, Rgba, f32>(&image, 3, 2, EdgeMode::Clamp);
+		assert_eq!(Rgba::new(0.0, 0.0, 0.0, 0.0), result.get(2, 1));
+
+		let result = image.scale_to::<Lanczos3>(3, 2);
+		assert_eq!((3, 2), result.dimensions());
+	}
+
+	#[test]
+	fn transparent() {
+		// The color of transparent pixels doesn't bleed.
+		let image = buffer::Rgba::from_fn(4, 1, |x, _|
+			if x < 2 { Rgba::new(1.0, 0.0, 0.0, 1.0) } else { Rgba::new(0.0, 1.0, 0.0, 0.0) });
+
+		let result = scale::<Linear, _, Rgba, u8, Rgba, u8>(&image, 8, 1, EdgeMode::Clamp);
+		let middle = result.get(3, 0);
+
+		assert_eq!(1.0, middle.red);
+		assert_eq!(0.0, middle.green);
+		assert!(middle.alpha > 0.0 && middle.alpha < 1.0);
+	}
+}
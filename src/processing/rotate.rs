@@ -15,6 +15,19 @@
 use buffer::Buffer;
 use pixel;
 use view;
+use color::Rgba;
+use processing::{Sampler, parallel};
+use processing::transform::{self, Affine};
+
+/// How the canvas is handled when rotating by an arbitrary angle.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Mode {
+	/// Expand the canvas so the whole rotated image fits.
+	Expand,
+
+	/// Keep the original dimensions, cropping anything outside of them.
+	Crop,
+}
 
 /// Trait for rotatable types.
 pub trait Rotate<P, C>
@@ -23,6 +36,9 @@ pub trait Rotate<P, C>
 {
 	/// Rotate by the given degree, negative degrees will turn counter-clockwise.
 	///
+	/// Only multiples of 90 degrees are supported, use `rotate_with` for any
+	/// other angle.
+	///
 	/// # Example
 	///
 	/// ```
@@ -37,6 +53,29 @@ pub trait Rotate<P, C>
 	/// assert_eq!(rotated.height(), 320);
 	/// ```
 	fn rotate(self, by: f32) -> Buffer<P, C, Vec<C>>;
+
+	/// Rotate by the given degree with the given `Sampler`, negative degrees
+	/// will turn counter-clockwise.
+	///
+	/// Any area not covered by the input is filled with the given pixel.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Rgba;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image   = read::from_path::<Rgba, u8, _>("tests/boat.xyz").unwrap();
+	/// let rotated = image.rotate_with::<sampler::Linear>(3.5, rotate::Crop,
+	///     &Rgba::new(1.0, 1.0, 1.0, 1.0));
+	///
+	/// assert_eq!(rotated.width(), 320);
+	/// assert_eq!(rotated.height(), 240);
+	/// ```
+	fn rotate_with<A>(self, by: f32, mode: Mode, fill: &P) -> Buffer<P, C, Vec<C>>
+		where A: Sampler,
+		      P: From<Rgba> + Into<Rgba>;
 }
 
 impl<'i, P, C, I> Rotate<P, C> for I
@@ -48,9 +87,19 @@ impl<'i, P, C, I> Rotate<P, C> for I
 	fn rotate(self, by: f32) -> Buffer<P, C, Vec<C>> {
 		it::<_, P, C, P, C>(self, by)
 	}
+
+	#[inline]
+	fn rotate_with<A>(self, by: f32, mode: Mode, fill: &P) -> Buffer<P, C, Vec<C>>
+		where A: Sampler,
+		      P: From<Rgba> + Into<Rgba>
+	{
+		with::<A, _, P, C, P, C>(self, by, mode, fill)
+	}
 }
 
 /// Rotate by the given degree, negative degrees will turn counter-clockwise.
+///
+/// Only multiples of 90 degrees are supported.
 pub fn it<'i, I, PI, CI, PO, CO>(input: I, by: f32) -> Buffer<PO, CO, Vec<CO>>
 	where I:  Into<view::Read<'i, PI, CI>>,
 	      PO: From<PI>,
@@ -60,12 +109,7 @@ pub fn it<'i, I, PI, CI, PO, CO>(input: I, by: f32) -> Buffer<PO, CO, Vec<CO>>
 	      CI: pixel::Channel,
 {
 	let input = input.into();
-	let by    = if by.is_sign_positive() {
-		by % 360.0
-	}
-	else {
-		360.0 - (by % 360.0)
-	} as u32;
+	let by    = normalize(by) as u32;
 
 	debug_assert!(by % 90 == 0);
 
@@ -73,35 +117,140 @@ pub fn it<'i, I, PI, CI, PO, CO>(input: I, by: f32) -> Buffer<PO, CO, Vec<CO>>
 		return input.convert::<PO, CO>();
 	}
 
-	let mut output: Buffer<PO, CO, _>;
+	let (width, height) = if by == 180 {
+		(input.width(), input.height())
+	}
+	else {
+		(input.height(), input.width())
+	};
+
+	let mut output = Buffer::<PO, CO, _>::new(width, height);
 
-	match by {
-		90 => {
-			output = Buffer::new(input.height(), input.width());
+	parallel::rows(&mut output, |y, row| {
+		for x in 0 .. width {
+			let px = match by {
+				90  => input.get(y, input.height() - 1 - x),
+				180 => input.get(input.width() - 1 - x, input.height() - 1 - y),
+				270 => input.get(input.width() - 1 - y, x),
+				_   => unreachable!(),
+			};
 
-			for (x, y, px) in input.pixels() {
-				output.set(input.height() - 1 - y, x, &px.get().into());
-			}
+			row.set(x, 0, &px.into());
 		}
+	});
 
-		180 => {
-			output = Buffer::new(input.width(), input.height());
+	output
+}
 
-			for (x, y, px) in input.pixels() {
-				output.set(input.width() - 1 - x, input.height() - 1 - y, &px.get().into());
-			}
-		}
+/// Rotate by the given degree with the given `Sampler`, negative degrees will
+/// turn counter-clockwise.
+///
+/// Any area not covered by the input is filled with the given pixel.
+pub fn with<'i, A, I, PI, CI, PO, CO>(input: I, by: f32, mode: Mode, fill: &PO) -> Buffer<PO, CO, Vec<CO>>
+	where A:  Sampler,
+	      I:  Into<view::Read<'i, PI, CI>>,
+	      PO: From<Rgba> + Into<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+{
+	let input  = input.into();
+	let rotate = Affine::rotate(by);
+	let cos    = rotate.0[0][0];
+	let sin    = rotate.0[1][0];
 
-		270 => {
-			output = Buffer::new(input.height(), input.width());
+	let (width, height) = match mode {
+		Mode::Crop =>
+			(input.width(), input.height()),
 
-			for (x, y, px) in input.pixels() {
-				output.set(y, input.width() - 1 - x, &px.get().into());
-			}
+		Mode::Expand => {
+			let width  = input.width() as f32 * cos.abs() + input.height() as f32 * sin.abs();
+			let height = input.width() as f32 * sin.abs() + input.height() as f32 * cos.abs();
+
+			// Avoid growing by a pixel because of rounding errors.
+			((width - 0.001).ceil() as u32, (height - 0.001).ceil() as u32)
 		}
+	};
 
-		_ => unreachable!()
-	}
+	// Rotate around the center of the input, and move it to the center of the
+	// output.
+	let matrix = Affine::translate(input.width() as f32 / -2.0, input.height() as f32 / -2.0)
+		.then(&rotate)
+		.then(&Affine::translate(width as f32 / 2.0, height as f32 / 2.0));
+
+	let mut output = Buffer::<PO, CO, _>::new(width, height);
+	transform::it::<A, _, _, PI, CI, PO, CO, _>(&input, &mut output, matrix, fill);
 
 	output
 }
+
+/// Normalize the degrees to the `[0, 360)` range.
+#[inline]
+fn normalize(by: f32) -> f32 {
+	let by = by % 360.0;
+
+	if by < 0.0 {
+		by + 360.0
+	}
+	else {
+		by
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use processing::sampler::{Nearest, Linear};
+	use buffer;
+	use color::Rgba;
+
+	#[test]
+	fn negative() {
+		let buffer = buffer::Rgba::from_fn(2, 1, |x, _|
+			Rgba::new(x as f32, 0.0, 0.0, 1.0));
+
+		assert_eq!(buffer.rotate(270.0), buffer.rotate(-90.0));
+	}
+
+	#[test]
+	fn right_angle() {
+		let buffer = buffer::Rgba::from_fn(3, 2, |x, y|
+			Rgba::new(x as f32 / 2.0, y as f32, 0.0, 1.0));
+
+		let fill = Rgba::new(0.0, 0.0, 0.0, 0.0);
+
+		assert_eq!(buffer.rotate(90.0),
+			buffer.rotate_with::<Nearest>(90.0, Mode::Expand, &fill));
+
+		assert_eq!(buffer.rotate(-90.0),
+			buffer.rotate_with::<Linear>(-90.0, Mode::Expand, &fill));
+	}
+
+	#[test]
+	fn expand() {
+		let buffer = buffer::Rgba::from_pixel(10, 10, &Rgba::new(1.0, 1.0, 1.0, 1.0));
+		let fill   = Rgba::new(0.0, 0.0, 0.0, 0.0);
+		let result = buffer.rotate_with::<Linear>(45.0, Mode::Expand, &fill);
+
+		assert_eq!(15, result.width());
+		assert_eq!(15, result.height());
+
+		assert_eq!(fill, result.get(0, 0));
+		assert_eq!(Rgba::new(1.0, 1.0, 1.0, 1.0), result.get(7, 7));
+	}
+
+	#[test]
+	fn crop() {
+		let buffer = buffer::Rgba::from_pixel(10, 4, &Rgba::new(1.0, 1.0, 1.0, 1.0));
+		let fill   = Rgba::new(0.0, 0.0, 0.0, 1.0);
+		let result = buffer.rotate_with::<Nearest>(90.0, Mode::Crop, &fill);
+
+		assert_eq!(10, result.width());
+		assert_eq!(4, result.height());
+
+		assert_eq!(fill, result.get(0, 0));
+		assert_eq!(Rgba::new(1.0, 1.0, 1.0, 1.0), result.get(5, 2));
+	}
+}
@@ -12,12 +12,15 @@
 //
 //  0. You just DO WHAT THE FUCK YOU WANT TO.
 
+use buffer::Buffer;
 use pixel;
 use view;
 use orientation::Orientation;
 use color::{Limited, Rgba};
-use super::Sampler;
-use util::GetClamped;
+use super::{Sampler, srgb};
+use super::resample::{self, Weights};
+use edge::EdgeMode;
+use util::GetEdge;
 
 /// Trait for samplable types.
 pub trait Sample<PI, CI, PO, CO>
@@ -31,8 +34,7 @@ pub trait Sample<PI, CI, PO, CO>
 	/// # Example
 	///
 	/// ```
-	/// use picto::read;
-	/// use picto::buffer;
+	/// use picto::{read, buffer};
 	/// use picto::color::Rgb;
 	/// use picto::processing::prelude::*;
 	///
@@ -47,13 +49,13 @@ pub trait Sample<PI, CI, PO, CO>
 		where A: Sampler,
 		      O: Into<view::Write<'o, PO, CO>>;
 
-	/// Sample in the given direction with the given support and kernel function.
+	/// Sample in the given direction with the given support and kernel
+	/// function.
 	///
 	/// # Example
 	///
 	/// ```
-	/// use picto::read;
-	/// use picto::buffer;
+	/// use picto::{read, buffer};
 	/// use picto::color::Rgb;
 	/// use picto::processing::prelude::*;
 	///
@@ -62,12 +64,75 @@ pub trait Sample<PI, CI, PO, CO>
 	/// let mut resized  = buffer::Rgb::new(image.width() * 2, image.height() * 2);
 	///
 	/// // Nearest neighbor sampling.
-	/// image.sample_with(&mut vertical, sample::Vertically, 0.5, |x| if x.abs() <= 0.5 { 1.0 } else { 0.0 });
-	/// vertical.sample_with(&mut resized, sample::Horizontally, 0.5, |x| if x.abs() <= 0.5 { 1.0 } else { 0.0 });
+	/// image.sample_with(&mut vertical, sample::Vertically,
+	///     0.5, |x| if x.abs() <= 0.5 { 1.0 } else { 0.0 });
+	/// vertical.sample_with(&mut resized, sample::Horizontally,
+	///     0.5, |x| if x.abs() <= 0.5 { 1.0 } else { 0.0 });
 	/// ```
 	fn sample_with<'o, F, O>(self, output: O, mode: Orientation, support: f32, kernel: F)
 		where F: FnMut(f32) -> f32,
 		      O: Into<view::Write<'o, PO, CO>>;
+
+	/// Sample in the given direction, handling the edges with the given
+	/// `EdgeMode`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::{read, buffer, EdgeMode};
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let     image    = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	/// let mut vertical = buffer::Rgb::new(image.width(), image.height() * 2);
+	/// let mut resized  = buffer::Rgb::new(image.width() * 2, image.height() * 2);
+	///
+	/// image.sample_edge::<sampler::Gaussian, _>(&mut vertical, sample::Vertically, EdgeMode::Wrap);
+	/// vertical.sample_edge::<sampler::Gaussian, _>(&mut resized, sample::Horizontally, EdgeMode::Wrap);
+	/// ```
+	fn sample_edge<'o, A, O>(self, output: O, mode: Orientation, edge: EdgeMode)
+		where A: Sampler,
+		      O: Into<view::Write<'o, PO, CO>>;
+
+	/// Sample in the given direction with the given support and kernel
+	/// function, handling the edges with the given `EdgeMode`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::{read, buffer, EdgeMode};
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let     image    = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	/// let mut vertical = buffer::Rgb::new(image.width(), image.height() * 2);
+	///
+	/// // Nearest neighbor sampling.
+	/// image.sample_with_edge(&mut vertical, sample::Vertically, EdgeMode::Mirror,
+	///     0.5, |x| if x.abs() <= 0.5 { 1.0 } else { 0.0 });
+	/// ```
+	fn sample_with_edge<'o, F, O>(self, output: O, mode: Orientation, edge: EdgeMode, support: f32, kernel: F)
+		where F: FnMut(f32) -> f32,
+		      O: Into<view::Write<'o, PO, CO>>;
+
+	/// Sample in the given direction in linear light, decoding the sRGB input
+	/// before sampling and encoding it back after.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::{read, buffer, EdgeMode};
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let     image    = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	/// let mut vertical = buffer::Rgb::new(image.width(), image.height() / 2);
+	///
+	/// image.sample_linear::<sampler::Gaussian, _>(&mut vertical, sample::Vertically, EdgeMode::Clamp);
+	/// ```
+	fn sample_linear<'o, A, O>(self, output: O, mode: Orientation, edge: EdgeMode)
+		where A: Sampler,
+		      O: Into<view::Write<'o, PO, CO>>;
 }
 
 impl<'i, PI, CI, PO, CO, I> Sample<PI, CI, PO, CO> for I
@@ -79,31 +144,54 @@ impl<'i, PI, CI, PO, CO, I> Sample<PI, CI, PO, CO> for I
 	      CO: pixel::Channel,
 	      I:  Into<view::Read<'i, PI, CI>>
 {
+	#[inline]
 	fn sample<'o, A, O>(self, output: O, mode: Orientation)
 		where A: Sampler,
 		      O: Into<view::Write<'o, PO, CO>>
+	{
+		self.sample_edge::<A, _>(output, mode, Default::default())
+	}
+
+	#[inline]
+	fn sample_with<'o, F, O>(self, output: O, mode: Orientation, support: f32, kernel: F)
+		where F: FnMut(f32) -> f32,
+		      O: Into<view::Write<'o, PO, CO>>
+	{
+		self.sample_with_edge(output, mode, Default::default(), support, kernel)
+	}
+
+	fn sample_edge<'o, A, O>(self, output: O, mode: Orientation, edge: EdgeMode)
+		where A: Sampler,
+		      O: Into<view::Write<'o, PO, CO>>
 	{
 		match mode {
 			Orientation::Vertical =>
-				vertically::<A, PO, CO, PI, CI, _, _>(self, output),
+				vertically_edge::<A, PO, CO, PI, CI, _, _>(self, output, edge),
 
 			Orientation::Horizontal =>
-				horizontally::<A, PO, CO, PI, CI, _, _>(self, output)
+				horizontally_edge::<A, PO, CO, PI, CI, _, _>(self, output, edge)
 		}
 	}
 
-	fn sample_with<'o, F, O>(self, output: O, mode: Orientation, support: f32, kernel: F)
+	fn sample_with_edge<'o, F, O>(self, output: O, mode: Orientation, edge: EdgeMode, support: f32, kernel: F)
 		where F: FnMut(f32) -> f32,
 		      O: Into<view::Write<'o, PO, CO>>
 	{
 		match mode {
 			Orientation::Vertical =>
-				vertically_with::<PO, CO, PI, CI, _, _, _>(self, output, support, kernel),
+				vertically_with_edge::<PO, CO, PI, CI, _, _, _>(self, output, edge, support, kernel),
 
 			Orientation::Horizontal =>
-				horizontally_with::<PO, CO, PI, CI, _, _, _>(self, output, support, kernel)
+				horizontally_with_edge::<PO, CO, PI, CI, _, _, _>(self, output, edge, support, kernel)
 		}
 	}
+
+	fn sample_linear<'o, A, O>(self, output: O, mode: Orientation, edge: EdgeMode)
+		where A: Sampler,
+		      O: Into<view::Write<'o, PO, CO>>
+	{
+		linear::<A, PO, CO, PI, CI, _, _>(self, output, mode, edge)
+	}
 }
 
 /// Sample vertically with the given `Sampler`.
@@ -119,11 +207,12 @@ pub fn vertically<'i, 'o, A, PO, CO, PI, CI, I, O>(input: I, output: O)
 	      I:  Into<view::Read<'i, PI, CI>>,
 	      O:  Into<view::Write<'o, PO, CO>>
 {
-	vertically_with(input, output, A::support(), A::kernel)
+	vertically_edge::<A, _, _, _, _, _, _>(input, output, Default::default())
 }
 
 /// Sample vertically with the given support and kernel function.
-pub fn vertically_with<'i, 'o, PO, CO, PI, CI, I, O, F>(input: I, output: O, support: f32, mut kernel: F)
+#[inline]
+pub fn vertically_with<'i, 'o, PO, CO, PI, CI, I, O, F>(input: I, output: O, support: f32, kernel: F)
 	where PO: From<Rgba>,
 	      PO: pixel::Write<CO>,
 	      CO: pixel::Channel,
@@ -134,46 +223,44 @@ pub fn vertically_with<'i, 'o, PO, CO, PI, CI, I, O, F>(input: I, output: O, sup
 	      O:  Into<view::Write<'o, PO, CO>>,
 	      F:  FnMut(f32) -> f32
 {
-	let     input  = input.into();
-	let     input  = &input;
-	let mut output = output.into();
-
-	debug_assert_eq!(input.width(), output.width());
-
-	let ratio  = input.height() as f32 / output.height() as f32;
-	let scale  = if ratio > 1.0 { ratio } else { 1.0 };
-	let radius = (support * scale).ceil();
-
-	for x in 0 .. input.width() as i64 {
-		for y_out in 0 .. output.height() {
-			let y_in = (y_out as f32 + 0.5) * ratio;
-
-			let left  = (y_in - radius) as i64;
-			let right = (y_in + radius) as i64;
-
-			let mut sum = (0.0, 0.0, 0.0, 0.0);
-			let mut t   = (0.0, 0.0, 0.0, 0.0);
-
-			for i in left .. right + 1 {
-				let w = kernel((i as f32 - y_in) / scale);
-				let w = (w, w, w, w);
+	vertically_with_edge(input, output, Default::default(), support, kernel)
+}
 
-				sum.0 += w.0;
-				sum.1 += w.1;
-				sum.2 += w.2;
-				sum.3 += w.3;
+/// Sample vertically with the given `Sampler` and `EdgeMode`.
+#[inline]
+pub fn vertically_edge<'i, 'o, A, PO, CO, PI, CI, I, O>(input: I, output: O, edge: EdgeMode)
+	where A:  Sampler,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      I:  Into<view::Read<'i, PI, CI>>,
+	      O:  Into<view::Write<'o, PO, CO>>
+{
+	vertically_with_edge(input, output, edge, A::support(), A::kernel)
+}
 
-				let p: (f32, f32, f32, f32) = input.get_clamped(x, i).into().to_pixel();
+/// Sample vertically with the given `EdgeMode`, support and kernel function.
+pub fn vertically_with_edge<'i, 'o, PO, CO, PI, CI, I, O, F>(input: I, output: O, edge: EdgeMode, support: f32, mut kernel: F)
+	where PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      I:  Into<view::Read<'i, PI, CI>>,
+	      O:  Into<view::Write<'o, PO, CO>>,
+	      F:  FnMut(f32) -> f32
+{
+	let input  = input.into();
+	let output = output.into();
 
-				t.0 += p.0 * w.0;
-				t.1 += p.1 * w.1;
-				t.2 += p.2 * w.2;
-				t.3 += p.3 * w.3;
-			}
+	debug_assert_eq!(input.width(), output.width());
 
-			output.set(x as u32, y_out, &Rgba::new(t.0 / sum.0, t.1 / sum.1, t.2 / sum.2, t.3 / sum.3).clamp().into());
-		}
-	}
+	let weights = Weights::with(input.height(), output.height(), edge, support, &mut kernel);
+	resample::vertically(&input, output, &weights, edge);
 }
 
 /// Sample horizontally with the given `Sampler`.
@@ -189,11 +276,12 @@ pub fn horizontally<'i, 'o, A, PO, CO, PI, CI, I, O>(input: I, output: O)
 	      I:  Into<view::Read<'i, PI, CI>>,
 	      O:  Into<view::Write<'o, PO, CO>>
 {
-	horizontally_with(input, output, A::support(), A::kernel)
+	horizontally_edge::<A, _, _, _, _, _, _>(input, output, Default::default())
 }
 
 /// Sample horizontally with the given support and kernel function.
-pub fn horizontally_with<'i, 'o, PO, CO, PI, CI, I, O, F>(input: I, output: O, support: f32, mut kernel: F)
+#[inline]
+pub fn horizontally_with<'i, 'o, PO, CO, PI, CI, I, O, F>(input: I, output: O, support: f32, kernel: F)
 	where PO: From<Rgba>,
 	      PO: pixel::Write<CO>,
 	      CO: pixel::Channel,
@@ -204,44 +292,171 @@ pub fn horizontally_with<'i, 'o, PO, CO, PI, CI, I, O, F>(input: I, output: O, s
 	      O:  Into<view::Write<'o, PO, CO>>,
 	      F:  FnMut(f32) -> f32
 {
-	let     input  = input.into();
-	let     input  = &input;
-	let mut output = output.into();
+	horizontally_with_edge(input, output, Default::default(), support, kernel)
+}
+
+/// Sample horizontally with the given `Sampler` and `EdgeMode`.
+#[inline]
+pub fn horizontally_edge<'i, 'o, A, PO, CO, PI, CI, I, O>(input: I, output: O, edge: EdgeMode)
+	where A:  Sampler,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      I:  Into<view::Read<'i, PI, CI>>,
+	      O:  Into<view::Write<'o, PO, CO>>
+{
+	horizontally_with_edge(input, output, edge, A::support(), A::kernel)
+}
+
+/// Sample horizontally with the given `EdgeMode`, support and kernel function.
+pub fn horizontally_with_edge<'i, 'o, PO, CO, PI, CI, I, O, F>(input: I, output: O, edge: EdgeMode, support: f32, mut kernel: F)
+	where PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      I:  Into<view::Read<'i, PI, CI>>,
+	      O:  Into<view::Write<'o, PO, CO>>,
+	      F:  FnMut(f32) -> f32
+{
+	let input  = input.into();
+	let output = output.into();
 
 	debug_assert_eq!(input.height(), output.height());
 
-	let ratio  = input.width() as f32 / output.width() as f32;
-	let scale  = if ratio > 1.0 { ratio } else { 1.0 };
-	let radius = (support * scale).ceil();
+	let weights = Weights::with(input.width(), output.width(), edge, support, &mut kernel);
+	resample::horizontally(&input, output, &weights, edge);
+}
 
-	for y in 0 .. input.height() as i64 {
-		for x_out in 0 .. output.width() {
-			let x_in = (x_out as f32 + 0.5) * ratio;
+/// Sample in the given direction in linear light with the given `Sampler` and
+/// `EdgeMode`.
+pub fn linear<'i, 'o, A, PO, CO, PI, CI, I, O>(input: I, output: O, mode: Orientation, edge: EdgeMode)
+	where A:  Sampler,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      I:  Into<view::Read<'i, PI, CI>>,
+	      O:  Into<view::Write<'o, PO, CO>>
+{
+	let     input  = srgb::decode(input);
+	let mut output = output.into();
+	let mut tmp    = Buffer::<Rgba, f32, _>::new(output.width(), output.height());
+
+	match mode {
+		Orientation::Vertical =>
+			vertically_edge::<A, _, _, _, _, _, _>(&input, &mut tmp, edge),
 
-			let left  = (x_in - radius) as i64;
-			let right = (x_in + radius) as i64;
+		Orientation::Horizontal =>
+			horizontally_edge::<A, _, _, _, _, _, _>(&input, &mut tmp, edge),
+	}
 
-			let mut sum = (0.0, 0.0, 0.0, 0.0);
-			let mut t   = (0.0, 0.0, 0.0, 0.0);
+	srgb::encode_into(&tmp, &mut output);
+}
+
+/// Sample at the given point with the given `Sampler`.
+///
+/// The coordinates are continuous, so the center of the pixel at `(0, 0)` is
+/// at `(0.5, 0.5)`, and any sample falling outside of the input is handled by
+/// the given `EdgeMode`.
+#[inline]
+pub fn point<'i, A, PI, CI, I>(input: I, x: f32, y: f32, edge: EdgeMode) -> Rgba
+	where A:  Sampler,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      I:  Into<view::Read<'i, PI, CI>>
+{
+	point_with(input, x, y, edge, A::support(), A::kernel)
+}
 
-			for i in left .. right + 1 {
-				let w = kernel((i as f32 - x_in) / scale);
-				let w = (w, w, w, w);
+/// Sample at the given point with the given `EdgeMode`, support and kernel
+/// function.
+pub fn point_with<'i, PI, CI, I, F>(input: I, x: f32, y: f32, edge: EdgeMode, support: f32, mut kernel: F) -> Rgba
+	where PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      I:  Into<view::Read<'i, PI, CI>>,
+	      F:  FnMut(f32) -> f32
+{
+	let input = input.into();
+	let input = &input;
 
-				sum.0 += w.0;
-				sum.1 += w.1;
-				sum.2 += w.2;
-				sum.3 += w.3;
+	let left   = (x - 0.5 - support).floor() as i64;
+	let right  = (x - 0.5 + support).ceil() as i64;
+	let top    = (y - 0.5 - support).floor() as i64;
+	let bottom = (y - 0.5 + support).ceil() as i64;
 
-				let p: (f32, f32, f32, f32) = input.get_clamped(i, y).into().to_pixel();
+	let mut sum = 0.0;
+	let mut t   = (0.0, 0.0, 0.0, 0.0);
 
-				t.0 += p.0 * w.0;
-				t.1 += p.1 * w.1;
-				t.2 += p.2 * w.2;
-				t.3 += p.3 * w.3;
+	for j in top .. bottom + 1 {
+		let wy = kernel(j as f32 + 0.5 - y);
+
+		if wy == 0.0 {
+			continue;
+		}
+
+		for i in left .. right + 1 {
+			let w = wy * kernel(i as f32 + 0.5 - x);
+
+			if w == 0.0 {
+				continue;
 			}
 
-			output.set(x_out, y as u32, &Rgba::new(t.0 / sum.0, t.1 / sum.1, t.2 / sum.2, t.3 / sum.3).clamp().into());
+			sum += w;
+			accumulate(&mut t, input.get_edge(i, j, edge), w);
 		}
 	}
+
+	if sum == 0.0 {
+		return input.get_edge(x.floor() as i64, y.floor() as i64, edge);
+	}
+
+	finish(t, sum)
+}
+
+/// Add the weighted pixel to the sums, with the colors premultiplied by the
+/// alpha so transparent pixels don't bleed their color.
+#[inline]
+fn accumulate(t: &mut (f32, f32, f32, f32), p: Rgba, w: f32) {
+	let w = (p.alpha * w, w);
+
+	t.0 += p.red * w.0;
+	t.1 += p.green * w.0;
+	t.2 += p.blue * w.0;
+	t.3 += p.alpha * w.1;
+}
+
+/// Turn the premultiplied sums back into a straight alpha pixel.
+#[inline]
+fn finish(t: (f32, f32, f32, f32), sum: f32) -> Rgba {
+	// Negative lobes can leave a tiny or negative alpha.
+	if t.3 <= f32::EPSILON {
+		return Rgba::new(0.0, 0.0, 0.0, 0.0);
+	}
+
+	Rgba::new(t.0 / t.3, t.1 / t.3, t.2 / t.3, t.3 / sum).clamp()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use processing::sampler::Lanczos3;
+
+	#[test]
+	fn negative() {
+		let image = Buffer::<Rgba, f32, _>::from_fn(8, 1, |x, _|
+			if x == 0 { Rgba::new(1.0, 0.0, 0.0, 1.0) } else { Rgba::new(0.0, 0.0, 0.0, 0.0) });
+
+		// Only the negative lobe reaches the opaque pixel.
+		assert_eq!(Rgba::new(0.0, 0.0, 0.0, 0.0), point::<Lanczos3, _, _, _>(&image, 2.0, 0.5, EdgeMode::Clamp));
+		assert!(point::<Lanczos3, _, _, _>(&image, 0.5, 0.5, EdgeMode::Clamp).alpha > 0.9);
+	}
 }
@@ -20,7 +20,7 @@ impl<T: Float> super::Sampler<T> for Linear {
 	#[inline]
 	fn kernel(x: T) -> T {
 		if x.abs() < one!() {
-			one!(T) - x
+			one!(T) - x.abs()
 		}
 		else {
 			zero!()
@@ -32,3 +32,31 @@ impl<T: Float> super::Sampler<T> for Linear {
 		one!()
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use buffer::Buffer;
+	use color::Luma;
+	use processing::Sampler;
+	use processing::prelude::*;
+
+	#[test]
+	fn symmetric() {
+		for &x in &[0.0, 0.25, 0.5, 0.75, 1.0] {
+			assert_eq!(<Linear as Sampler<f32>>::kernel(x), <Linear as Sampler<f32>>::kernel(-x));
+		}
+
+		assert_eq!(0.75, <Linear as Sampler<f32>>::kernel(-0.25));
+	}
+
+	#[test]
+	fn gradient() {
+		// Taps before the sampled point used to weigh more than one, pulling the
+		// result towards them, and the taps were off by half a pixel.
+		let image  = Buffer::<Luma, f32, _>::from_fn(2, 1, |x, _| Luma::new(x as f32));
+		let result = image.resize::<Linear>(4, 1);
+
+		assert_eq!(vec![0.0, 0.25, 0.75, 1.0], result.into_raw());
+	}
+}
@@ -15,7 +15,9 @@
 use buffer::Buffer;
 use pixel;
 use view;
-use super::Scaler;
+use edge::EdgeMode;
+use color::Rgba;
+use super::{Scaler, srgb};
 
 /// Trait for scalable types.
 pub trait Scale<P, C>
@@ -40,6 +42,45 @@ pub trait Scale<P, C>
 	fn resize<A>(self, width: u32, height: u32) -> Buffer<P, C, Vec<C>>
 		where A: Scaler<P, C, P, C>;
 
+	/// Resize to the given width and height, handling the edges with the given
+	/// `EdgeMode`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::{read, EdgeMode};
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image   = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	/// let resized = image.resize_with::<scaler::Lanczos3>(100, 100, EdgeMode::Wrap);
+	///
+	/// assert_eq!(resized.width(), 100);
+	/// assert_eq!(resized.height(), 100);
+	/// ```
+	fn resize_with<A>(self, width: u32, height: u32, edge: EdgeMode) -> Buffer<P, C, Vec<C>>
+		where A: Scaler<P, C, P, C>;
+
+	/// Resize to the given width and height in linear light, decoding the sRGB
+	/// input before scaling and encoding it back after.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image   = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	/// let resized = image.resize_linear::<scaler::Lanczos3>(100, 100);
+	///
+	/// assert_eq!(resized.width(), 100);
+	/// assert_eq!(resized.height(), 100);
+	/// ```
+	fn resize_linear<A>(self, width: u32, height: u32) -> Buffer<P, C, Vec<C>>
+		where A: Scaler<Rgba, f32, Rgba, f32>,
+		      P: From<Rgba> + Into<Rgba>;
+
 	/// Scale by the given factor.
 	///
 	/// # Example
@@ -89,6 +130,21 @@ impl<'i, P, C, I> Scale<P, C> for I
 		resize::<A, _, P, C, P, C>(self, width, height)
 	}
 
+	#[inline]
+	fn resize_with<A>(self, width: u32, height: u32, edge: EdgeMode) -> Buffer<P, C, Vec<C>>
+		where A: Scaler<P, C, P, C>,
+	{
+		resize_with::<A, _, P, C, P, C>(self, width, height, edge)
+	}
+
+	#[inline]
+	fn resize_linear<A>(self, width: u32, height: u32) -> Buffer<P, C, Vec<C>>
+		where A: Scaler<Rgba, f32, Rgba, f32>,
+		      P: From<Rgba> + Into<Rgba>
+	{
+		resize_linear::<A, _, P, C, P, C>(self, width, height, Default::default())
+	}
+
 	#[inline]
 	fn scale_by<A>(self, factor: f32) -> Buffer<P, C, Vec<C>>
 		where A: Scaler<P, C, P, C>
@@ -124,6 +180,49 @@ pub fn resize<'i, A, I, PI, CI, PO, CO>(input: I, width: u32, height: u32) -> Bu
 	A::scale(&input.into(), width, height)
 }
 
+/// Resize to the given width and height, handling the edges with the given
+/// `EdgeMode`.
+#[inline]
+pub fn resize_with<'i, A, I, PI, CI, PO, CO>(input: I, width: u32, height: u32, edge: EdgeMode) -> Buffer<PO, CO, Vec<CO>>
+	where A:  Scaler<PI, CI, PO, CO>,
+	      PO: From<PI>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      I:  Into<view::Read<'i, PI, CI>>
+{
+	let input = input.into();
+
+	if input.width() == width && input.height() == height {
+		return input.convert::<PO, CO>();
+	}
+
+	A::scale_with(&input, width, height, edge)
+}
+
+/// Resize to the given width and height in linear light, handling the edges
+/// with the given `EdgeMode`.
+#[inline]
+pub fn resize_linear<'i, A, I, PI, CI, PO, CO>(input: I, width: u32, height: u32, edge: EdgeMode) -> Buffer<PO, CO, Vec<CO>>
+	where A:  Scaler<Rgba, f32, Rgba, f32>,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      I:  Into<view::Read<'i, PI, CI>>
+{
+	let linear = srgb::decode(input);
+
+	if linear.width() == width && linear.height() == height {
+		return srgb::encode(&linear);
+	}
+
+	srgb::encode(&A::scale_with(&linear.readable(Default::default()), width, height, edge))
+}
+
 /// Scale by the given factor.
 #[inline]
 pub fn by<'i, A, I, PI, CI, PO, CO>(input: I, factor: f32) -> Buffer<PO, CO, Vec<CO>>
@@ -143,6 +242,9 @@ pub fn by<'i, A, I, PI, CI, PO, CO>(input: I, factor: f32) -> Buffer<PO, CO, Vec
 }
 
 /// Scale to the given width and height, maintaining the aspect ratio.
+///
+/// Empty inputs have no aspect ratio to maintain, so they're resized to the
+/// given width and height.
 #[inline]
 pub fn to<'i, A, I, PI, CI, PO, CO>(input: I, width: u32, height: u32) -> Buffer<PO, CO, Vec<CO>>
 	where A:  Scaler<PI, CI, PO, CO>,
@@ -154,6 +256,11 @@ pub fn to<'i, A, I, PI, CI, PO, CO>(input: I, width: u32, height: u32) -> Buffer
 	      I:  Into<view::Read<'i, PI, CI>>
 {
 	let input = input.into();
+
+	if input.width() == 0 || input.height() == 0 {
+		return resize::<A, _, PI, CI, PO, CO>(input, width, height);
+	}
+
 	let r_old = input.width() as f32 / input.height() as f32;
 	let r_new = width as f32 / height as f32;
 
@@ -208,4 +315,67 @@ mod test {
 		assert_eq!(Rgb::new(1.0, 0.0, 1.0), result.get(2, 3));
 		assert_eq!(Rgb::new(1.0, 0.0, 1.0), result.get(3, 3));
 	}
+
+	#[test]
+	fn linear() {
+		use processing::scaler::Linear;
+
+		let buffer = buffer::Rgb::from_fn(4, 4, |x, y|
+			if (x + y) % 2 == 0 { Rgb::new(1.0, 1.0, 1.0) } else { Rgb::new(0.0, 0.0, 0.0) });
+
+		assert_eq!(135, buffer.resize::<Linear>(2, 2)[0]);
+		assert_eq!(193, buffer.resize_linear::<Linear>(2, 2)[0]);
+	}
+
+	#[test]
+	fn pixel_art() {
+		use processing::scaler::{Scale2x, Scale3x, Eagle, Xbrz, xbr, hqx};
+
+		let black = Rgb::new(0.0, 0.0, 0.0);
+		let white = Rgb::new(1.0, 1.0, 1.0);
+		let edge  = buffer::Rgb::from_fn(4, 4, |x, y| if x + y < 4 { black } else { white });
+		let flat  = buffer::Rgb::from_pixel(4, 4, &white);
+
+		// The corner along the edge is taken from the other side.
+		assert_eq!(white, edge.scale_by::<Scale2x>(2.0).get(3, 5));
+		assert_eq!(black, edge.scale_by::<Scale2x>(2.0).get(2, 4));
+		assert_eq!(white, edge.scale_by::<Scale3x>(3.0).get(5, 8));
+		assert_eq!(black, edge.scale_by::<Scale3x>(3.0).get(3, 6));
+		assert_eq!(white, edge.scale_by::<Eagle>(2.0).get(3, 5));
+
+		fn check<A: Scaler<Rgb, u8, Rgb, u8>>(edge: &buffer::Rgb, flat: &buffer::Rgb, factor: u32) {
+			let result = edge.scale_by::<A>(factor as f32);
+			assert_eq!((4 * factor, 4 * factor), result.dimensions());
+
+			// The corner along the edge moves towards the other side.
+			assert!(result.get(2 * factor - 1, 3 * factor - 1).red > 0.0);
+			assert_eq!(edge.get(0, 0), result.get(factor, 2 * factor));
+
+			assert!(flat.scale_by::<A>(factor as f32).pixels().all(|(_, _, p)| p.get() == flat.get(0, 0)));
+			assert_eq!((5, 7), edge.resize::<A>(5, 7).dimensions());
+		}
+
+		check::<xbr::X2>(&edge, &flat, 2);
+		check::<xbr::X3>(&edge, &flat, 3);
+		check::<xbr::X4>(&edge, &flat, 4);
+		check::<hqx::X2>(&edge, &flat, 2);
+		check::<hqx::X3>(&edge, &flat, 3);
+		check::<hqx::X4>(&edge, &flat, 4);
+
+		for factor in 2 .. 7 {
+			check::<Xbrz>(&edge, &flat, factor);
+		}
+	}
+
+	#[test]
+	fn seam() {
+		use processing::scaler::Seam;
+
+		let black  = Rgb::new(0.0, 0.0, 0.0);
+		let buffer = buffer::Rgb::from_fn(8, 4, |x, _|
+			if x == 5 { black } else { Rgb::new(1.0, 1.0, 1.0) });
+
+		let result = buffer.resize::<Seam>(4, 4);
+		assert_eq!(4, result.pixels().filter(|(_, _, p)| p.get() == black).count());
+	}
 }
@@ -0,0 +1,62 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use color::Rgba;
+use super::grid::{Grid, rotate, cell};
+
+fixed!(
+	/// Eagle, doubling the size and filling a corner with the neighbouring
+	/// color when all three pixels around it share it.
+	Eagle, 2, eagle);
+
+fn eagle(grid: &Grid, x: i64, y: i64, block: &mut [Rgba]) {
+	for turns in 0 .. 4 {
+		let at = |dx, dy| { let (dx, dy) = rotate((dx, dy), turns); grid.get(x + dx, y + dy) };
+		let (f, h, i) = (at(1, 0), at(0, 1), at(1, 1));
+
+		if f == i && i == h {
+			block[cell(2, turns, 1, 1)] = i;
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use super::super::grid::test::block;
+	use buffer::Buffer;
+	use color::Rgb;
+	use processing::prelude::*;
+
+	#[test]
+	fn corner() {
+		let image = Buffer::<Rgb, f32, _>::from_fn(2, 2, |x, y|
+			if x + y == 0 { Rgb::new(0.0, 0.0, 0.0) } else { Rgb::new(1.0, 1.0, 1.0) });
+
+		let result = image.scale_by::<Eagle>(2.0);
+		assert_eq!(vec![0.0, 0.0, 0.0, 1.0], block(&result, 0, 0, 2));
+		assert_eq!(vec![1.0, 1.0, 1.0, 1.0], block(&result, 1, 0, 2));
+		assert_eq!(vec![1.0, 1.0, 1.0, 1.0], block(&result, 0, 1, 2));
+		assert_eq!(vec![1.0, 1.0, 1.0, 1.0], block(&result, 1, 1, 2));
+	}
+
+	#[test]
+	fn insular() {
+		let image = Buffer::<Rgb, f32, _>::from_fn(3, 3, |x, y|
+			if x == 1 && y == 1 { Rgb::new(0.0, 0.0, 0.0) } else { Rgb::new(1.0, 1.0, 1.0) });
+
+		let result = image.scale_by::<Eagle>(2.0);
+		assert_eq!(vec![1.0, 1.0, 1.0, 1.0], block(&result, 1, 1, 2));
+	}
+}
@@ -0,0 +1,106 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use color::Rgba;
+use super::grid::{Grid, rotate, cell};
+
+fixed!(
+	/// Scale2x, also known as EPX, doubling the size copying a neighbour over
+	/// a corner when it's part of an edge.
+	Scale2x, 2, scale2x);
+
+fixed!(
+	/// Scale3x, the extension of Scale2x to three times the size.
+	Scale3x, 3, scale3x);
+
+fn scale2x(grid: &Grid, x: i64, y: i64, block: &mut [Rgba]) {
+	for turns in 0 .. 4 {
+		let at = |dx, dy| { let (dx, dy) = rotate((dx, dy), turns); grid.get(x + dx, y + dy) };
+		let (b, d, f, h) = (at(0, -1), at(-1, 0), at(1, 0), at(0, 1));
+
+		if h == f && d != h && b != f {
+			block[cell(2, turns, 1, 1)] = f;
+		}
+	}
+}
+
+fn scale3x(grid: &Grid, x: i64, y: i64, block: &mut [Rgba]) {
+	let (b, d, f, h) = (grid.get(x, y - 1), grid.get(x - 1, y), grid.get(x + 1, y), grid.get(x, y + 1));
+
+	if b == h || d == f {
+		return;
+	}
+
+	for turns in 0 .. 4 {
+		let at = |dx, dy| { let (dx, dy) = rotate((dx, dy), turns); grid.get(x + dx, y + dy) };
+		let (b, c, e, f, h, i) = (at(0, -1), at(1, -1), at(0, 0), at(1, 0), at(0, 1), at(1, 1));
+
+		if h == f {
+			block[cell(3, turns, 2, 2)] = f;
+		}
+
+		if (b == f && e != i) || (h == f && e != c) {
+			block[cell(3, turns, 1, 2)] = f;
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use super::super::grid::test::block;
+	use buffer::Buffer;
+	use color::Rgb;
+	use processing::prelude::*;
+
+	fn diagonal() -> Buffer<Rgb, f32, Vec<f32>> {
+		Buffer::from_fn(2, 2, |x, y|
+			if x == y { Rgb::new(0.0, 0.0, 0.0) } else { Rgb::new(1.0, 1.0, 1.0) })
+	}
+
+	#[test]
+	fn scale2x() {
+		let result = diagonal().scale_by::<Scale2x>(2.0);
+
+		assert_eq!(vec![0.0, 0.0, 0.0, 1.0], block(&result, 0, 0, 2));
+		assert_eq!(vec![1.0, 1.0, 0.0, 1.0], block(&result, 1, 0, 2));
+		assert_eq!(vec![1.0, 0.0, 1.0, 1.0], block(&result, 0, 1, 2));
+		assert_eq!(vec![1.0, 0.0, 0.0, 0.0], block(&result, 1, 1, 2));
+	}
+
+	#[test]
+	fn scale3x() {
+		let result = diagonal().scale_by::<Scale3x>(3.0);
+
+		assert_eq!(vec![
+			0.0, 0.0, 0.0,
+			0.0, 0.0, 1.0,
+			0.0, 1.0, 1.0], block(&result, 0, 0, 3));
+
+		assert_eq!(vec![
+			1.0, 1.0, 1.0,
+			0.0, 1.0, 1.0,
+			0.0, 0.0, 1.0], block(&result, 1, 0, 3));
+
+		assert_eq!(vec![
+			1.0, 0.0, 0.0,
+			1.0, 1.0, 0.0,
+			1.0, 1.0, 1.0], block(&result, 0, 1, 3));
+
+		assert_eq!(vec![
+			1.0, 1.0, 0.0,
+			1.0, 0.0, 0.0,
+			0.0, 0.0, 0.0], block(&result, 1, 1, 3));
+	}
+}
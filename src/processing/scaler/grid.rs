@@ -0,0 +1,209 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use view;
+use buffer::Buffer;
+use pixel;
+use color::Rgba;
+use processing::parallel;
+use util::Shared;
+use super::{Scaler, Nearest};
+
+/// The input of the pixel-art scalers, converted once since every pixel is
+/// looked at many times.
+pub struct Grid {
+	width:  u32,
+	height: u32,
+	pixels: Vec<Rgba>,
+}
+
+impl Grid {
+	/// Convert the input.
+	pub fn new<P, C>(input: &view::Read<P, C>) -> Self
+		where P: Into<Rgba> + pixel::Read<C>,
+		      C: pixel::Channel,
+	{
+		let mut pixels = Vec::with_capacity((input.width() * input.height()) as usize);
+
+		for y in 0 .. input.height() {
+			for x in 0 .. input.width() {
+				pixels.push(input.get(x, y).into());
+			}
+		}
+
+		Grid {
+			width:  input.width(),
+			height: input.height(),
+			pixels,
+		}
+	}
+
+	/// Get the width.
+	#[inline]
+	pub fn width(&self) -> u32 {
+		self.width
+	}
+
+	/// Get the height.
+	#[inline]
+	pub fn height(&self) -> u32 {
+		self.height
+	}
+
+	/// Get the pixel, clamping the coordinates to the edges.
+	#[inline]
+	pub fn get(&self, x: i64, y: i64) -> Rgba {
+		let x = x.clamp(0, self.width as i64 - 1) as usize;
+		let y = y.clamp(0, self.height as i64 - 1) as usize;
+
+		self.pixels[y * self.width as usize + x]
+	}
+
+	/// Scale every pixel to a block of the given factor, the block is filled
+	/// with the pixel before being handed to the function.
+	///
+	/// If the requested size isn't the one given by the factor the result is
+	/// resized to it with `Nearest`, an empty grid gives an empty buffer of the
+	/// requested size.
+	pub fn scale<PO, CO, F>(&self, width: u32, height: u32, factor: u32, func: F) -> Buffer<PO, CO, Vec<CO>>
+		where PO: From<Rgba> + pixel::Write<CO> + pixel::Read<CO>,
+		      CO: pixel::Channel,
+		      F:  Fn(i64, i64, &mut [Rgba]) + Shared
+	{
+		if self.width == 0 || self.height == 0 {
+			return Buffer::new(width, height);
+		}
+
+		let mut output = Buffer::<PO, CO, _>::new(self.width * factor, self.height * factor);
+
+		{
+			// Every row of input pixels is turned into a band of output rows.
+			let mut rows  = output.writable(Default::default()).rows().into_iter();
+			let     bands = (0 .. self.height)
+				.map(|_| rows.by_ref().take(factor as usize).collect::<Vec<_>>())
+				.collect::<Vec<_>>();
+
+			parallel::each(bands, |y, mut band| {
+				let mut block = vec![Rgba::new(0.0, 0.0, 0.0, 0.0); (factor * factor) as usize];
+
+				for x in 0 .. self.width {
+					block.fill(self.get(x as i64, y as i64));
+					func(x as i64, y as i64, &mut block);
+
+					for (i, value) in block.iter().enumerate() {
+						let i = i as u32;
+						band[(i / factor) as usize].set(x * factor + i % factor, 0, &(*value).into());
+					}
+				}
+			});
+		}
+
+		if output.width() == width && output.height() == height {
+			output
+		}
+		else {
+			Nearest::scale(&output.readable(Default::default()), width, height)
+		}
+	}
+}
+
+/// Rotate the offset by the given amount of quarter turns, every turn moves
+/// right to up.
+#[inline]
+pub fn rotate((x, y): (i64, i64), turns: u8) -> (i64, i64) {
+	(0 .. turns).fold((x, y), |(x, y), _| (y, -x))
+}
+
+/// Get the index of the cell in a block of the given size, rotated around the
+/// center of the block.
+#[inline]
+pub fn cell(size: u32, turns: u8, row: u32, col: u32) -> usize {
+	let n      = size as i64 - 1;
+	let (x, y) = rotate((2 * col as i64 - n, 2 * row as i64 - n), turns);
+
+	((y + n) / 2 * (n + 1) + (x + n) / 2) as usize
+}
+
+/// Move the color towards the other by the given amount.
+#[inline]
+pub fn mix(a: Rgba, b: Rgba, amount: f32) -> Rgba {
+	Rgba::new(
+		a.red + (b.red - a.red) * amount,
+		a.green + (b.green - a.green) * amount,
+		a.blue + (b.blue - a.blue) * amount,
+		a.alpha + (b.alpha - a.alpha) * amount)
+}
+
+/// Implement `Scaler` for a scaler working on blocks of a fixed factor.
+macro_rules! fixed {
+	($(#[$attr:meta])* $name:ident, $factor:expr, $func:path) => (
+		$(#[$attr])*
+		pub struct $name;
+
+		impl<PI, CI, PO, CO> $crate::processing::Scaler<PI, CI, PO, CO> for $name
+			where PI: Into<$crate::color::Rgba> + $crate::pixel::Read<CI>,
+			      CI: $crate::pixel::Channel,
+			      PO: From<$crate::color::Rgba> + $crate::pixel::Write<CO> + $crate::pixel::Read<CO>,
+			      CO: $crate::pixel::Channel,
+		{
+			#[inline]
+			fn scale(input: &$crate::view::Read<PI, CI>, width: u32, height: u32) -> $crate::buffer::Buffer<PO, CO, Vec<CO>> {
+				let grid = $crate::processing::scaler::grid::Grid::new(input);
+				grid.scale(width, height, $factor, |x, y, block| $func(&grid, x, y, block))
+			}
+		}
+	)
+}
+
+#[cfg(test)]
+pub mod test {
+	use super::*;
+	use color::Rgb;
+
+	#[test]
+	fn turns() {
+		assert_eq!(3, cell(2, 0, 1, 1));
+		assert_eq!(1, cell(2, 1, 1, 1));
+		assert_eq!(0, cell(2, 2, 1, 1));
+		assert_eq!(2, cell(2, 3, 1, 1));
+		assert_eq!(1, cell(3, 2, 2, 1));
+	}
+
+	#[test]
+	fn empty() {
+		let grid   = Grid::new(&Buffer::<Rgb, f32, _>::new(0, 0).readable(Default::default()));
+		let result = grid.scale::<Rgb, f32, _>(4, 3, 2, |_, _, _| ());
+
+		assert_eq!((4, 3), result.dimensions());
+	}
+
+	/// A black triangle in the top left of a 4x4 white image.
+	pub fn edge() -> Buffer<Rgb, f32, Vec<f32>> {
+		Buffer::from_fn(4, 4, |x, y|
+			if x + y < 4 { Rgb::new(0.0, 0.0, 0.0) } else { Rgb::new(1.0, 1.0, 1.0) })
+	}
+
+	/// Get the red channel of the block of the given pixel.
+	pub fn block(image: &Buffer<Rgb, f32, Vec<f32>>, x: u32, y: u32, factor: u32) -> Vec<f32> {
+		let mut values = Vec::new();
+
+		for dy in 0 .. factor {
+			for dx in 0 .. factor {
+				values.push(image.get(x * factor + dx, y * factor + dy).red);
+			}
+		}
+
+		values
+	}
+}
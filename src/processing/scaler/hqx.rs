@@ -0,0 +1,494 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+//! The hqx family of pixel-art scalers.
+//!
+//! Every neighbour is compared to the pixel in `YUV` with the hqx thresholds,
+//! giving a pattern of 8 bits. Each corner of the block is interpolated
+//! following the rules of the hqx tables for the pattern as seen from that
+//! corner, the tables being symmetric every corner uses the same rules on the
+//! mirrored neighbourhood.
+
+use color::Rgba;
+use super::grid::Grid;
+
+fixed!(
+	/// hq2x, doubling the size.
+	X2, 2, x2);
+
+fixed!(
+	/// hq3x, tripling the size.
+	X3, 3, x3);
+
+fixed!(
+	/// hq4x, quadrupling the size.
+	X4, 4, x4);
+
+// The neighbourhood as seen from the top left corner, numbered row by row.
+const TL: usize = 0;
+const T:  usize = 1;
+const L:  usize = 3;
+const C:  usize = 4;
+const R:  usize = 5;
+const B:  usize = 7;
+
+/// The mirroring of the neighbourhood for every corner, top left, top right,
+/// bottom left and bottom right.
+const CORNERS: [(i64, i64); 4] = [(1, 1), (-1, 1), (1, -1), (-1, -1)];
+
+/// The shape around a corner, each one is a group of patterns sharing the
+/// same interpolation in the hqx tables.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Shape {
+	/// An edge goes around the corner, it keeps the color.
+	Sharp,
+
+	/// The pixel continues to the left.
+	Left,
+
+	/// The pixel continues to the top.
+	Top,
+
+	/// The pixel continues across the corner.
+	Diagonal,
+
+	/// Only the left neighbour differs.
+	AlongLeft,
+
+	/// Only the top neighbour differs.
+	AlongTop,
+
+	/// The pixel is isolated.
+	Dot,
+
+	/// An edge curves around the corner, closer to the top.
+	CurveTop,
+
+	/// An edge curves around the corner, closer to the left.
+	CurveLeft,
+
+	/// An edge cuts the corner along a slope.
+	Slope,
+
+	/// An edge cuts the corner.
+	Cross,
+
+	/// The top and left neighbours are within the thresholds.
+	Flat,
+
+	/// Any other pattern.
+	Soft,
+}
+
+/// How far the cell in the middle of a side moves towards the neighbour on
+/// that side, ordered by precedence.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+enum Side {
+	/// The neighbour is within the thresholds.
+	Soft,
+
+	/// The cell keeps the color.
+	Keep,
+
+	/// The cell moves a little towards the neighbour.
+	Little,
+
+	/// The cell moves mostly towards the neighbour.
+	Much,
+}
+
+/// The neighbourhood of a pixel mirrored so the corner is the top left one.
+struct Corner {
+	mirror:  (i64, i64),
+	pixels:  [Rgba; 9],
+	yuv:     [(i32, i32, i32); 9],
+	pattern: u8,
+}
+
+impl Corner {
+	fn new(grid: &Grid, x: i64, y: i64, mirror: (i64, i64)) -> Self {
+		let mut pixels = [Rgba::new(0.0, 0.0, 0.0, 0.0); 9];
+		let mut yuv    = [(0, 0, 0); 9];
+
+		for (i, (pixel, value)) in pixels.iter_mut().zip(yuv.iter_mut()).enumerate() {
+			let (dx, dy) = ((i % 3) as i64 - 1, (i / 3) as i64 - 1);
+
+			*pixel = grid.get(x + dx * mirror.0, y + dy * mirror.1);
+			*value = convert(*pixel);
+		}
+
+		// Every neighbour but the center gives a bit.
+		let pattern = [0, 1, 2, 3, 5, 6, 7, 8].iter().enumerate()
+			.filter(|&(_, &i)| differ(yuv[C], yuv[i]))
+			.fold(0, |pattern, (bit, _)| pattern | 1 << bit);
+
+		Corner {
+			mirror,
+			pixels,
+			yuv,
+			pattern,
+		}
+	}
+
+	/// Check if the pattern matches any of the masked values.
+	fn is(&self, rules: &[(u8, u8)]) -> bool {
+		rules.iter().any(|&(mask, value)| self.pattern & mask == value)
+	}
+
+	/// Check if the two neighbours differ.
+	fn differ(&self, a: usize, b: usize) -> bool {
+		differ(self.yuv[a], self.yuv[b])
+	}
+
+	/// Check if the edge of a `Shape::Slope` runs towards the bottom.
+	fn steep(&self) -> bool {
+		self.pattern & 0x20 != 0
+	}
+
+	fn shape(&self) -> Shape {
+		if self.is(&[(0xbf, 0x37), (0xdb, 0x13)]) && self.differ(T, R) {
+			Shape::Left
+		}
+		else if self.is(&[(0xdb, 0x49), (0xef, 0x6d)]) && self.differ(B, L) {
+			Shape::Top
+		}
+		else if self.is(&[(0x0b, 0x0b), (0xfe, 0x4a), (0xfe, 0x1a)]) && self.differ(L, T) {
+			Shape::Sharp
+		}
+		else if self.is(&[
+			(0x6f, 0x2a), (0x5b, 0x0a), (0xbf, 0x3a), (0xdf, 0x5a), (0x9f, 0x8a),
+			(0xcf, 0x8a), (0xef, 0x4e), (0x3f, 0x0e), (0xfb, 0x5a), (0xbb, 0x8a),
+			(0x7f, 0x5a), (0xaf, 0x8a), (0xeb, 0x8a)]) && self.differ(L, T)
+		{
+			Shape::Diagonal
+		}
+		else if self.is(&[(0x0b, 0x08)]) {
+			Shape::AlongLeft
+		}
+		else if self.is(&[(0x0b, 0x02)]) {
+			Shape::AlongTop
+		}
+		else if self.is(&[(0x2f, 0x2f)]) {
+			Shape::Dot
+		}
+		else if self.is(&[(0xbf, 0x37), (0xdb, 0x13)]) {
+			Shape::CurveTop
+		}
+		else if self.is(&[(0xdb, 0x49), (0xef, 0x6d)]) {
+			Shape::CurveLeft
+		}
+		else if self.is(&[(0x1b, 0x03), (0x4f, 0x43), (0x8b, 0x83), (0x6b, 0x43)]) {
+			Shape::Left
+		}
+		else if self.is(&[(0x4b, 0x09), (0x8b, 0x89), (0x1f, 0x19), (0x3b, 0x19)]) {
+			Shape::Top
+		}
+		else if self.is(&[(0x7e, 0x2a), (0xef, 0xab), (0xbf, 0x8f), (0x7e, 0x0e)]) {
+			Shape::Slope
+		}
+		else if self.is(&[(0xfb, 0x6a), (0x6f, 0x6e), (0x3f, 0x3e), (0xfb, 0xfa), (0xdf, 0xde), (0xdf, 0x1e)]) {
+			Shape::Diagonal
+		}
+		else if self.is(&[(0x0a, 0x00)]) {
+			Shape::Flat
+		}
+		else if self.is(&[
+			(0x4f, 0x4b), (0x9f, 0x1b), (0x2f, 0x0b), (0xbe, 0x0a), (0xee, 0x0a),
+			(0x7e, 0x0a), (0xeb, 0x4b), (0x3b, 0x1b)])
+		{
+			Shape::Cross
+		}
+		else {
+			Shape::Soft
+		}
+	}
+
+	/// Get how far the top and left middle cells move.
+	fn sides(&self, shape: Shape) -> (Side, Side) {
+		let side = |bit: u8, along: bool| {
+			if self.pattern & bit == 0 {
+				Side::Soft
+			}
+			else {
+				match shape {
+					Shape::Cross          => Side::Little,
+					Shape::Slope if along => Side::Much,
+					Shape::Slope          => Side::Little,
+					_                     => Side::Keep,
+				}
+			}
+		};
+
+		(side(0x02, !self.steep()), side(0x08, self.steep()))
+	}
+
+	/// Interpolate the neighbours with the given weights.
+	fn interpolate(&self, weights: &[(usize, u32)]) -> Rgba {
+		let total = weights.iter().map(|&(_, w)| w).sum::<u32>() as f32;
+
+		weights.iter().fold(Rgba::new(0.0, 0.0, 0.0, 0.0), |acc, &(i, w)| {
+			let px = self.pixels[i];
+			let w  = w as f32 / total;
+
+			Rgba::new(acc.red + px.red * w, acc.green + px.green * w,
+				acc.blue + px.blue * w, acc.alpha + px.alpha * w)
+		})
+	}
+
+	/// Get the index of the cell in the block, mirrored back.
+	fn cell(&self, size: usize, row: usize, col: usize) -> usize {
+		let row = if self.mirror.1 < 0 { size - 1 - row } else { row };
+		let col = if self.mirror.0 < 0 { size - 1 - col } else { col };
+
+		row * size + col
+	}
+}
+
+/// Get the corners of the pixel with their shape.
+fn corners(grid: &Grid, x: i64, y: i64) -> Vec<(Corner, Shape)> {
+	CORNERS.iter().map(|&mirror| {
+		let corner = Corner::new(grid, x, y, mirror);
+		let shape  = corner.shape();
+
+		(corner, shape)
+	}).collect()
+}
+
+fn x2(grid: &Grid, x: i64, y: i64, block: &mut [Rgba]) {
+	for (corner, shape) in corners(grid, x, y) {
+		block[corner.cell(2, 0, 0)] = corner.interpolate(match shape {
+			Shape::Sharp     => &[(C, 1)],
+			Shape::Left      => &[(C, 3), (L, 1)],
+			Shape::Top       => &[(C, 3), (T, 1)],
+			Shape::Diagonal  => &[(C, 3), (TL, 1)],
+			Shape::AlongLeft => &[(C, 2), (TL, 1), (T, 1)],
+			Shape::AlongTop  => &[(C, 2), (TL, 1), (L, 1)],
+			Shape::Dot       => &[(C, 14), (L, 1), (T, 1)],
+			Shape::CurveTop  => &[(C, 5), (T, 2), (L, 1)],
+			Shape::CurveLeft => &[(C, 5), (L, 2), (T, 1)],
+			Shape::Slope     => &[(C, 2), (L, 3), (T, 3)],
+			Shape::Cross     => &[(C, 2), (L, 1), (T, 1)],
+			Shape::Flat      => &[(C, 2), (L, 1), (T, 1)],
+			Shape::Soft      => &[(C, 6), (L, 1), (T, 1)],
+		});
+	}
+}
+
+fn x3(grid: &Grid, x: i64, y: i64, block: &mut [Rgba]) {
+	let corners = corners(grid, x, y);
+	let sides   = corners.iter().map(|&(ref corner, shape)| corner.sides(shape)).collect::<Vec<_>>();
+
+	for &(ref corner, shape) in &corners {
+		block[corner.cell(3, 0, 0)] = corner.interpolate(match shape {
+			Shape::Sharp => &[(C, 1)],
+
+			Shape::Left => &[(C, 3), (L, 1)],
+			Shape::Top  => &[(C, 3), (T, 1)],
+
+			Shape::Diagonal | Shape::AlongLeft | Shape::AlongTop =>
+				&[(C, 3), (TL, 1)],
+
+			Shape::Slope => &[(L, 1), (T, 1)],
+			Shape::Cross => &[(C, 2), (L, 7), (T, 7)],
+
+			Shape::Dot | Shape::CurveTop | Shape::CurveLeft | Shape::Flat | Shape::Soft =>
+				&[(C, 2), (L, 1), (T, 1)],
+		});
+	}
+
+	// The middle of every side is shared by two corners, the one moving the
+	// most wins.
+	for &(a, b, top) in &[(0, 1, true), (2, 3, true), (0, 2, false), (1, 3, false)] {
+		let (corner, _) = &corners[a];
+		let (side, neighbour, cell) = if top {
+			(sides[a].0.max(sides[b].0), T, corner.cell(3, 0, 1))
+		}
+		else {
+			(sides[a].1.max(sides[b].1), L, corner.cell(3, 1, 0))
+		};
+
+		let (center, other) = match side {
+			Side::Soft   => (3, 1),
+			Side::Keep   => (1, 0),
+			Side::Little => (7, 1),
+			Side::Much   => (1, 3),
+		};
+
+		block[cell] = corner.interpolate(&[(C, center), (neighbour, other)]);
+	}
+}
+
+fn x4(grid: &Grid, x: i64, y: i64, block: &mut [Rgba]) {
+	for (corner, shape) in corners(grid, x, y) {
+		// The cells of the corner, row by row.
+		let cells: [&[(usize, u32)]; 4] = match shape {
+			Shape::Sharp =>
+				[&[(C, 1)], &[(C, 1)], &[(C, 1)], &[(C, 1)]],
+
+			Shape::Left =>
+				[&[(C, 5), (L, 3)], &[(C, 7), (L, 1)], &[(C, 5), (L, 3)], &[(C, 7), (L, 1)]],
+
+			Shape::Top =>
+				[&[(C, 5), (T, 3)], &[(C, 5), (T, 3)], &[(C, 7), (T, 1)], &[(C, 7), (T, 1)]],
+
+			Shape::Diagonal =>
+				[&[(C, 5), (TL, 3)], &[(C, 3), (TL, 1)], &[(C, 3), (TL, 1)], &[(C, 7), (TL, 1)]],
+
+			Shape::AlongLeft =>
+				[&[(C, 5), (TL, 3)], &[(C, 5), (T, 2), (TL, 1)], &[(C, 3), (TL, 1)], &[(C, 7), (TL, 1)]],
+
+			Shape::AlongTop =>
+				[&[(C, 5), (TL, 3)], &[(C, 3), (TL, 1)], &[(C, 5), (L, 2), (TL, 1)], &[(C, 7), (TL, 1)]],
+
+			Shape::Dot =>
+				[&[(C, 2), (L, 1), (T, 1)], &[(C, 3), (T, 1)], &[(C, 3), (L, 1)], &[(C, 1)]],
+
+			Shape::CurveTop =>
+				[&[(C, 5), (T, 2), (L, 1)], &[(C, 3), (T, 1)], &[(C, 7), (T, 1)], &[(C, 1)]],
+
+			Shape::CurveLeft =>
+				[&[(C, 5), (L, 2), (T, 1)], &[(C, 7), (L, 1)], &[(C, 3), (L, 1)], &[(C, 1)]],
+
+			Shape::Slope if corner.steep() =>
+				[&[(L, 1), (T, 1)], &[(C, 3), (T, 1)], &[(C, 1), (L, 3)], &[(C, 1)]],
+
+			Shape::Slope =>
+				[&[(L, 1), (T, 1)], &[(C, 1), (T, 3)], &[(C, 3), (L, 1)], &[(C, 1)]],
+
+			Shape::Cross =>
+				[&[(L, 1), (T, 1)], &[(C, 1), (T, 1)], &[(C, 1), (L, 1)], &[(C, 1)]],
+
+			Shape::Flat | Shape::Soft =>
+				[&[(C, 2), (L, 1), (T, 1)], &[(C, 5), (T, 2), (L, 1)], &[(C, 5), (L, 2), (T, 1)], &[(C, 6), (L, 1), (T, 1)]],
+		};
+
+		for (i, weights) in cells.iter().enumerate() {
+			block[corner.cell(4, i / 2, i % 2)] = corner.interpolate(weights);
+		}
+	}
+}
+
+/// Convert the color to the `YUV` used by hqx.
+fn convert(color: Rgba) -> (i32, i32, i32) {
+	let r = (color.red.clamp(0.0, 1.0) * 255.0).round();
+	let g = (color.green.clamp(0.0, 1.0) * 255.0).round();
+	let b = (color.blue.clamp(0.0, 1.0) * 255.0).round();
+
+	((0.299 * r + 0.587 * g + 0.114 * b) as i32,
+	 (-0.169 * r - 0.331 * g + 0.5 * b) as i32 + 128,
+	 (0.5 * r - 0.419 * g - 0.081 * b) as i32 + 128)
+}
+
+/// Check if the colors differ past the thresholds.
+fn differ(a: (i32, i32, i32), b: (i32, i32, i32)) -> bool {
+	(a.0 - b.0).abs() > 48 || (a.1 - b.1).abs() > 7 || (a.2 - b.2).abs() > 6
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use super::super::grid::test::{edge, block};
+	use buffer::Buffer;
+	use color::Rgb;
+	use processing::prelude::*;
+
+	/// A black pixel in the middle of a white image.
+	fn dot() -> Buffer<Rgb, f32, Vec<f32>> {
+		Buffer::from_fn(3, 3, |x, y|
+			if x == 1 && y == 1 { Rgb::new(0.0, 0.0, 0.0) } else { Rgb::new(1.0, 1.0, 1.0) })
+	}
+
+	#[test]
+	fn thresholds() {
+		let grey = |v: f32| convert(Rgba::new(v, v, v, 1.0));
+
+		assert!(!differ(grey(0.5), grey(0.5 + 48.0 / 255.0)));
+		assert!(differ(grey(0.5), grey(0.5 + 49.0 / 255.0)));
+		assert!(differ(convert(Rgba::new(1.0, 1.0, 1.0, 1.0)), convert(Rgba::new(1.0, 1.0, 0.9, 1.0))));
+	}
+
+	#[test]
+	fn shapes() {
+		let image = Grid::new(&dot().readable(Default::default()));
+
+		for &mirror in &CORNERS {
+			let corner = Corner::new(&image, 1, 1, mirror);
+
+			assert_eq!(0xff, corner.pattern);
+			assert_eq!(Shape::Dot, corner.shape());
+		}
+
+		let image = Grid::new(&edge().readable(Default::default()));
+		let shape = |x, y, mirror| Corner::new(&image, x, y, mirror).shape();
+
+		assert_eq!(Shape::Flat, shape(1, 2, (1, 1)));
+		assert_eq!(Shape::AlongLeft, shape(1, 2, (-1, 1)));
+		assert_eq!(Shape::AlongTop, shape(1, 2, (1, -1)));
+		assert_eq!(Shape::Cross, shape(1, 2, (-1, -1)));
+	}
+
+	#[test]
+	fn x2() {
+		// Isolated pixels barely lose their corners.
+		assert_eq!(vec![0.125, 0.125, 0.125, 0.125], block(&dot().scale_by::<X2>(2.0), 1, 1, 2));
+
+		let result = edge().scale_by::<X2>(2.0);
+
+		assert_eq!(vec![0.0, 0.0, 0.0, 0.5], block(&result, 1, 2, 2));
+		assert_eq!(vec![0.5, 1.0, 1.0, 1.0], block(&result, 2, 2, 2));
+	}
+
+	#[test]
+	fn x3() {
+		assert_eq!(vec![
+			0.5, 0.0, 0.5,
+			0.0, 0.0, 0.0,
+			0.5, 0.0, 0.5], block(&dot().scale_by::<X3>(3.0), 1, 1, 3));
+
+		let result = edge().scale_by::<X3>(3.0);
+
+		assert_eq!(vec![
+			0.0, 0.0,   0.0,
+			0.0, 0.0,   0.125,
+			0.0, 0.125, 0.875], block(&result, 1, 2, 3));
+
+		assert_eq!(vec![
+			0.125, 0.875, 1.0,
+			0.875, 1.0,   1.0,
+			1.0,   1.0,   1.0], block(&result, 2, 2, 3));
+	}
+
+	#[test]
+	fn x4() {
+		assert_eq!(vec![
+			0.5,  0.25, 0.25, 0.5,
+			0.25, 0.0,  0.0,  0.25,
+			0.25, 0.0,  0.0,  0.25,
+			0.5,  0.25, 0.25, 0.5], block(&dot().scale_by::<X4>(4.0), 1, 1, 4));
+
+		let result = edge().scale_by::<X4>(4.0);
+
+		assert_eq!(vec![
+			0.0, 0.0, 0.0, 0.0,
+			0.0, 0.0, 0.0, 0.0,
+			0.0, 0.0, 0.0, 0.5,
+			0.0, 0.0, 0.5, 1.0], block(&result, 1, 2, 4));
+
+		assert_eq!(vec![
+			0.0, 0.5, 1.0, 1.0,
+			0.5, 1.0, 1.0, 1.0,
+			1.0, 1.0, 1.0, 1.0,
+			1.0, 1.0, 1.0, 1.0], block(&result, 2, 2, 4));
+	}
+}
@@ -15,6 +15,7 @@
 use view;
 use buffer::Buffer;
 use pixel;
+use edge::EdgeMode;
 
 pub trait Scaler<PI, CI, PO, CO>
 	where PI: pixel::Read<CI>,
@@ -23,8 +24,19 @@ pub trait Scaler<PI, CI, PO, CO>
 	      CO: pixel::Channel,
 {
 	fn scale(input: &view::Read<PI, CI>, width: u32, height: u32) -> Buffer<PO, CO, Vec<CO>>;
+
+	/// Scale handling the edges with the given `EdgeMode`, scalers that never
+	/// look outside of the input just ignore it.
+	#[inline]
+	#[allow(unused_variables)]
+	fn scale_with(input: &view::Read<PI, CI>, width: u32, height: u32, edge: EdgeMode) -> Buffer<PO, CO, Vec<CO>> {
+		Self::scale(input, width, height)
+	}
 }
 
+#[macro_use]
+mod grid;
+
 mod sampler;
 pub use super::sampler::Linear;
 pub use super::sampler::Cubic;
@@ -35,4 +47,17 @@ pub use super::sampler::Lanczos3;
 mod nearest;
 pub use self::nearest::Nearest;
 
+mod epx;
+pub use self::epx::{Scale2x, Scale3x};
+
+mod eagle;
+pub use self::eagle::Eagle;
+
+mod xbrz;
+pub use self::xbrz::Xbrz;
+
+mod seam;
+pub use self::seam::Seam;
+
 pub mod xbr;
+pub mod hqx;
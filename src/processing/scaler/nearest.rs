@@ -16,6 +16,7 @@ use view;
 use buffer::Buffer;
 use pixel;
 use util::GetClamped;
+use processing::parallel;
 
 pub struct Nearest;
 
@@ -30,15 +31,17 @@ impl<PI, CI, PO, CO> super::Scaler<PI, CI, PO, CO> for Nearest
 	fn scale(input: &view::Read<PI, CI>, width: u32, height: u32) -> Buffer<PO, CO, Vec<CO>> {
 		let mut output = Buffer::<PO, CO, _>::new(width, height);
 
-		for (x, y) in output.region().absolute() {
-			let v = y as f32 / (height - 1) as f32;
-			let u = x as f32 / (width - 1) as f32;
+		parallel::rows(&mut output, |y, row| {
+			for x in 0 .. width {
+				let v = y as f32 / (height - 1) as f32;
+				let u = x as f32 / (width - 1) as f32;
 
-			output.set(x, y, &input.get_clamped(
-				(u * input.width() as f32) as i64,
-				(v * input.height() as f32) as i64
-			).into());
-		}
+				row.set(x, 0, &input.get_clamped(
+					(u * input.width() as f32) as i64,
+					(v * input.height() as f32) as i64
+				).into());
+			}
+		});
 
 		output
 	}
@@ -15,8 +15,9 @@
 use view;
 use buffer::Buffer;
 use pixel;
+use edge::EdgeMode;
 use color::Rgba;
-use processing::{sample, Sampler};
+use processing::{resample, Sampler};
 
 impl<A, PI, CI, PO, CO> super::Scaler<PI, CI, PO, CO> for A
 	where A:  Sampler,
@@ -29,12 +30,11 @@ impl<A, PI, CI, PO, CO> super::Scaler<PI, CI, PO, CO> for A
 {
 	#[inline]
 	fn scale(input: &view::Read<PI, CI>, width: u32, height: u32) -> Buffer<PO, CO, Vec<CO>> {
-		let mut tmp = Buffer::<Rgba, u8, _>::new(input.width(), height);
-		sample::vertically::<A, _, _, _, _, _, _>(input, &mut tmp);
-
-		let mut out = Buffer::<PO, CO, _>::new(width, height);
-		sample::horizontally::<A, _, _, _, _, _, _>(&tmp, &mut out);
+		Self::scale_with(input, width, height, Default::default())
+	}
 
-		out
+	#[inline]
+	fn scale_with(input: &view::Read<PI, CI>, width: u32, height: u32, edge: EdgeMode) -> Buffer<PO, CO, Vec<CO>> {
+		resample::scale::<A, _, _, _, _, _>(input, width, height, edge)
 	}
 }
@@ -0,0 +1,37 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use view;
+use buffer::Buffer;
+use pixel;
+use color::Rgba;
+use processing::carve;
+
+/// Content-aware scaling, removing or inserting the seams with the lowest
+/// energy instead of resampling.
+pub struct Seam;
+
+impl<PI, CI, PO, CO> super::Scaler<PI, CI, PO, CO> for Seam
+	where PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      PO: From<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+{
+	#[inline]
+	fn scale(input: &view::Read<PI, CI>, width: u32, height: u32) -> Buffer<PO, CO, Vec<CO>> {
+		carve::resize::<_, PI, CI, PO, CO>(input, width, height)
+	}
+}
@@ -0,0 +1,217 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use color::Rgba;
+use processing::scaler::grid::{Grid, rotate, cell, mix};
+
+fixed!(
+	/// xBR level 2, doubling the size.
+	X2, 2, xbr2x);
+
+fixed!(
+	/// xBR level 2, tripling the size.
+	X3, 3, xbr3x);
+
+fixed!(
+	/// xBR level 2, quadrupling the size.
+	X4, 4, xbr4x);
+
+/// The kind of edge found across a corner.
+#[derive(Copy, Clone)]
+enum Edge {
+	/// Both a shallow and a steep edge.
+	LeftUp,
+
+	/// A shallow edge, spreading to the left.
+	Left,
+
+	/// A steep edge, spreading up.
+	Up,
+
+	/// A diagonal edge.
+	Diagonal,
+
+	/// A weak edge, only touching the corner.
+	Weak,
+}
+
+fn xbr2x(grid: &Grid, x: i64, y: i64, block: &mut [Rgba]) {
+	xbr(grid, x, y, block, 2, |edge| match edge {
+		Edge::LeftUp   => &[(1, 1, 224), (1, 0, 64), (0, 1, 64)],
+		Edge::Left     => &[(1, 1, 192), (1, 0, 64)],
+		Edge::Up       => &[(1, 1, 192), (0, 1, 64)],
+		Edge::Diagonal => &[(1, 1, 128)],
+		Edge::Weak     => &[(1, 1, 128)],
+	})
+}
+
+fn xbr3x(grid: &Grid, x: i64, y: i64, block: &mut [Rgba]) {
+	xbr(grid, x, y, block, 3, |edge| match edge {
+		Edge::LeftUp   => &[(2, 2, 256), (2, 1, 192), (1, 2, 192), (2, 0, 64), (0, 2, 64)],
+		Edge::Left     => &[(2, 2, 256), (2, 1, 192), (1, 2, 64), (2, 0, 64)],
+		Edge::Up       => &[(2, 2, 256), (1, 2, 192), (2, 1, 64), (0, 2, 64)],
+		Edge::Diagonal => &[(2, 2, 224), (1, 2, 32), (2, 1, 32)],
+		Edge::Weak     => &[(2, 2, 128)],
+	})
+}
+
+fn xbr4x(grid: &Grid, x: i64, y: i64, block: &mut [Rgba]) {
+	xbr(grid, x, y, block, 4, |edge| match edge {
+		Edge::LeftUp => &[
+			(3, 3, 256), (3, 2, 256), (2, 3, 256),
+			(3, 1, 192), (1, 3, 192),
+			(3, 0, 64), (2, 2, 64), (0, 3, 64)],
+
+		Edge::Left => &[
+			(3, 3, 256), (3, 2, 256),
+			(2, 3, 192), (3, 1, 192),
+			(2, 2, 64), (3, 0, 64)],
+
+		Edge::Up => &[
+			(3, 3, 256), (2, 3, 256),
+			(3, 2, 192), (1, 3, 192),
+			(2, 2, 64), (0, 3, 64)],
+
+		Edge::Diagonal => &[(3, 3, 256), (2, 3, 128), (3, 2, 128)],
+		Edge::Weak     => &[(3, 3, 128)],
+	})
+}
+
+/// Blend the corners of the block, the rules give the cells of the bottom
+/// right corner with how much they move towards the edge, out of 256.
+///
+/// The neighbourhood, relative to the bottom right corner, is named as
+/// follows.
+///
+/// ```text
+///       A1 B1 C1
+///    A0 A  B  C  C4
+///    D0 D  E  F  F4
+///    G0 G  H  I  I4
+///       G5 H5 I5
+/// ```
+#[allow(non_snake_case)]
+fn xbr<F>(grid: &Grid, x: i64, y: i64, block: &mut [Rgba], size: u32, rules: F)
+	where F: Fn(Edge) -> &'static [(u32, u32, u32)]
+{
+	for turns in 0 .. 4 {
+		let at = |dx, dy| { let (dx, dy) = rotate((dx, dy), turns); grid.get(x + dx, y + dy) };
+
+		let (B, C, D, E, F, G, H, I) = (at(0, -1), at(1, -1), at(-1, 0), at(0, 0), at(1, 0), at(-1, 1), at(0, 1), at(1, 1));
+
+		if E == H || E == F {
+			continue;
+		}
+
+		let (F4, I4, H5, I5) = (at(2, 0), at(2, 1), at(0, 2), at(1, 2));
+
+		let e = df(E, C) + df(E, G) + df(I, H5) + df(I, F4) + 4.0 * df(H, F);
+		let i = df(H, D) + df(H, I5) + df(F, I4) + df(F, B) + 4.0 * df(E, I);
+
+		let px = if df(E, F) <= df(E, H) { F } else { H };
+
+		let edge = if e < i && ((!eq(F, B) && !eq(H, D)) || (eq(E, I) && !eq(F, I4) && !eq(H, I5)) || eq(E, G) || eq(E, C)) {
+			let ke = df(F, G);
+			let ki = df(H, C);
+
+			let left = 2.0 * ke <= ki && E != G && D != G;
+			let up   = ke >= 2.0 * ki && E != C && B != C;
+
+			match (left, up) {
+				(true, true)   => Edge::LeftUp,
+				(true, false)  => Edge::Left,
+				(false, true)  => Edge::Up,
+				(false, false) => Edge::Diagonal,
+			}
+		}
+		else if e <= i {
+			Edge::Weak
+		}
+		else {
+			continue;
+		};
+
+		for &(row, col, amount) in rules(edge) {
+			let index = cell(size, turns, row, col);
+			block[index] = mix(block[index], px, amount as f32 / 256.0);
+		}
+	}
+}
+
+/// Get the difference between the colors in `YUV`, with the alpha.
+#[inline]
+fn df(a: Rgba, b: Rgba) -> f32 {
+	let dr = (a.red - b.red) * 255.0;
+	let dg = (a.green - b.green) * 255.0;
+	let db = (a.blue - b.blue) * 255.0;
+	let da = (a.alpha - b.alpha) * 255.0;
+
+	(0.299 * dr + 0.587 * dg + 0.114 * db).abs() +
+	(-0.169 * dr - 0.331 * dg + 0.5 * db).abs() +
+	(0.5 * dr - 0.419 * dg - 0.081 * db).abs() +
+	da.abs()
+}
+
+/// Check if the colors are close enough to be the same.
+#[inline]
+fn eq(a: Rgba, b: Rgba) -> bool {
+	df(a, b) < 155.0
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use processing::scaler::grid::test::{edge, block};
+	use processing::prelude::*;
+
+	#[test]
+	fn x2() {
+		let result = edge().scale_by::<X2>(2.0);
+
+		assert_eq!(vec![0.0, 0.0, 0.0, 0.5], block(&result, 1, 2, 2));
+		assert_eq!(vec![0.5, 1.0, 1.0, 1.0], block(&result, 2, 2, 2));
+	}
+
+	#[test]
+	fn x3() {
+		let result = edge().scale_by::<X3>(3.0);
+
+		assert_eq!(vec![
+			0.0, 0.0,   0.0,
+			0.0, 0.0,   0.125,
+			0.0, 0.125, 0.875], block(&result, 1, 2, 3));
+
+		assert_eq!(vec![
+			0.125, 0.875, 1.0,
+			0.875, 1.0,   1.0,
+			1.0,   1.0,   1.0], block(&result, 2, 2, 3));
+	}
+
+	#[test]
+	fn x4() {
+		let result = edge().scale_by::<X4>(4.0);
+
+		assert_eq!(vec![
+			0.0, 0.0, 0.0, 0.0,
+			0.0, 0.0, 0.0, 0.0,
+			0.0, 0.0, 0.0, 0.5,
+			0.0, 0.0, 0.5, 1.0], block(&result, 1, 2, 4));
+
+		assert_eq!(vec![
+			0.0, 0.5, 1.0, 1.0,
+			0.5, 1.0, 1.0, 1.0,
+			1.0, 1.0, 1.0, 1.0,
+			1.0, 1.0, 1.0, 1.0], block(&result, 2, 2, 4));
+	}
+}
@@ -14,3 +14,6 @@
 
 mod zuper;
 pub use self::zuper::Super;
+
+mod level2;
+pub use self::level2::{X2, X3, X4};
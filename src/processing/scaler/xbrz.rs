@@ -0,0 +1,332 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use view;
+use buffer::Buffer;
+use pixel;
+use color::Rgba;
+use processing::Scaler;
+use super::grid::{Grid, rotate, cell, mix};
+
+/// xBRZ, scaling between two and six times the size.
+///
+/// The factor is picked to cover the requested size, which is then reached
+/// with `Nearest`.
+pub struct Xbrz;
+
+impl<PI, CI, PO, CO> Scaler<PI, CI, PO, CO> for Xbrz
+	where PI: Into<Rgba> + pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      PO: From<Rgba> + pixel::Write<CO> + pixel::Read<CO>,
+	      CO: pixel::Channel,
+{
+	fn scale(input: &view::Read<PI, CI>, width: u32, height: u32) -> Buffer<PO, CO, Vec<CO>> {
+		if input.width() == 0 || input.height() == 0 {
+			return Buffer::new(width, height);
+		}
+
+		let grid   = Grid::new(input);
+		let factor = width.div_ceil(grid.width()).max(height.div_ceil(grid.height())).clamp(2, 6);
+		let blends = blends(&grid);
+
+		grid.scale(width, height, factor, |x, y, block|
+			corners(&grid, &blends[(y * grid.width() as i64 + x) as usize], x, y, block, factor))
+	}
+}
+
+/// How strongly a corner is blended.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Debug)]
+enum Blend {
+	None,
+	Normal,
+	Dominant,
+}
+
+/// The shape of the line across a corner.
+#[derive(Copy, Clone)]
+enum Line {
+	Shallow,
+	Steep,
+	SteepAndShallow,
+	Diagonal,
+	Corner,
+}
+
+/// Weight of the distance in the direction of the center.
+const CENTER_BIAS: f32 = 4.0;
+
+/// Ratio of the distances for a direction to be dominant.
+const DOMINANT_THRESHOLD: f32 = 3.6;
+
+/// Ratio of the distances for a line to be steep or shallow.
+const STEEP_THRESHOLD: f32 = 2.2;
+
+/// Distance under which colors are considered equal.
+const EQUAL_TOLERANCE: f32 = 30.0;
+
+/// Get the index of the corner at the given offset, clockwise from the top
+/// left.
+#[inline]
+fn corner((x, y): (i64, i64)) -> usize {
+	match (x, y) {
+		(-1, -1) => 0,
+		(1, -1)  => 1,
+		(1, 1)   => 2,
+		_        => 3,
+	}
+}
+
+/// Find how every corner of every pixel is blended, looking at the square
+/// of four pixels around it.
+///
+/// ```text
+///    A B C D
+///    E F G H
+///    I J K L
+///    M N O P
+/// ```
+#[allow(non_snake_case)]
+fn blends(grid: &Grid) -> Vec<[Blend; 4]> {
+	let     width  = grid.width() as i64;
+	let     height = grid.height() as i64;
+	let mut blends = vec![[Blend::None; 4]; (width * height) as usize];
+
+	let mut set = |x: i64, y: i64, offset: (i64, i64), blend: Blend| {
+		if x >= 0 && y >= 0 && x < width && y < height {
+			blends[(y * width + x) as usize][corner(offset)] = blend;
+		}
+	};
+
+	for y in -1 .. height {
+		for x in -1 .. width {
+			let at = |dx, dy| grid.get(x + dx, y + dy);
+
+			let (B, C)       = (at(0, -1), at(1, -1));
+			let (E, F, G, H) = (at(-1, 0), at(0, 0), at(1, 0), at(2, 0));
+			let (I, J, K, L) = (at(-1, 1), at(0, 1), at(1, 1), at(2, 1));
+			let (N, O)       = (at(0, 2), at(1, 2));
+
+			if (F == G && J == K) || (F == J && G == K) {
+				continue;
+			}
+
+			let jg = distance(I, F) + distance(F, C) + distance(N, K) + distance(K, H) + CENTER_BIAS * distance(J, G);
+			let fk = distance(E, J) + distance(J, O) + distance(B, G) + distance(G, L) + CENTER_BIAS * distance(F, K);
+
+			if jg < fk {
+				let blend = if DOMINANT_THRESHOLD * jg < fk { Blend::Dominant } else { Blend::Normal };
+
+				if F != G && F != J {
+					set(x, y, (1, 1), blend);
+				}
+
+				if K != J && K != G {
+					set(x + 1, y + 1, (-1, -1), blend);
+				}
+			}
+			else if fk < jg {
+				let blend = if DOMINANT_THRESHOLD * fk < jg { Blend::Dominant } else { Blend::Normal };
+
+				if J != F && J != K {
+					set(x, y + 1, (1, -1), blend);
+				}
+
+				if G != F && G != K {
+					set(x + 1, y, (-1, 1), blend);
+				}
+			}
+		}
+	}
+
+	blends
+}
+
+/// Blend the corners of the block of the pixel.
+fn corners(grid: &Grid, blends: &[Blend; 4], x: i64, y: i64, block: &mut [Rgba], size: u32) {
+	for turns in 0 .. 4 {
+		let blend = |dx, dy| blends[corner(rotate((dx, dy), turns))];
+
+		if blend(1, 1) == Blend::None {
+			continue;
+		}
+
+		let at = |dx, dy| { let (dx, dy) = rotate((dx, dy), turns); grid.get(x + dx, y + dy) };
+		let eq = |a, b| distance(a, b) < EQUAL_TOLERANCE;
+
+		let (b, c)    = (at(0, -1), at(1, -1));
+		let (d, e, f) = (at(-1, 0), at(0, 0), at(1, 0));
+		let (g, h, i) = (at(-1, 1), at(0, 1), at(1, 1));
+
+		let line = blend(1, 1) == Blend::Dominant || !(
+			// Avoid blending twice in adjacent corners, handles insular pixels.
+			(blend(1, -1) != Blend::None && !eq(e, g)) ||
+			(blend(-1, 1) != Blend::None && !eq(e, c)) ||
+			// Only blend the corner of L shapes.
+			(!eq(e, i) && eq(g, h) && eq(h, i) && eq(i, f) && eq(f, c)));
+
+		let px = if distance(e, f) <= distance(e, h) { f } else { h };
+
+		let shape = if line {
+			let fg = distance(f, g);
+			let hc = distance(h, c);
+
+			let shallow = STEEP_THRESHOLD * fg <= hc && e != g && d != g;
+			let steep   = STEEP_THRESHOLD * hc <= fg && e != c && b != c;
+
+			match (shallow, steep) {
+				(true, true)   => Line::SteepAndShallow,
+				(true, false)  => Line::Shallow,
+				(false, true)  => Line::Steep,
+				(false, false) => Line::Diagonal,
+			}
+		}
+		else {
+			Line::Corner
+		};
+
+		for &(row, col, amount) in rules(size, shape) {
+			let (row, col) = if let Line::Steep = shape { (col, row) } else { (row, col) };
+			let index      = cell(size, turns, row, col);
+
+			block[index] = mix(block[index], px, amount);
+		}
+	}
+}
+
+/// Get the cells of the bottom right corner with how much they move towards
+/// the line, steep lines mirror the shallow ones.
+fn rules(size: u32, line: Line) -> &'static [(u32, u32, f32)] {
+	match (size, line) {
+		(2, Line::Shallow) | (2, Line::Steep) =>
+			&[(1, 0, 0.25), (1, 1, 0.75)],
+		(2, Line::SteepAndShallow) =>
+			&[(1, 0, 0.25), (0, 1, 0.25), (1, 1, 5.0 / 6.0)],
+		(2, Line::Diagonal) =>
+			&[(1, 1, 0.5)],
+		(2, Line::Corner) =>
+			&[(1, 1, 0.21)],
+
+		(3, Line::Shallow) | (3, Line::Steep) =>
+			&[(2, 0, 0.25), (1, 2, 0.25), (2, 1, 0.75), (2, 2, 1.0)],
+		(3, Line::SteepAndShallow) =>
+			&[(2, 0, 0.25), (0, 2, 0.25), (2, 1, 0.75), (1, 2, 0.75), (2, 2, 1.0)],
+		(3, Line::Diagonal) =>
+			&[(1, 2, 0.125), (2, 1, 0.125), (2, 2, 0.875)],
+		(3, Line::Corner) =>
+			&[(2, 2, 0.45)],
+
+		(4, Line::Shallow) | (4, Line::Steep) =>
+			&[(3, 0, 0.25), (2, 2, 0.25), (3, 1, 0.75), (2, 3, 0.75), (3, 2, 1.0), (3, 3, 1.0)],
+		(4, Line::SteepAndShallow) =>
+			&[(3, 1, 0.75), (1, 3, 0.75), (3, 0, 0.25), (0, 3, 0.25), (2, 2, 1.0 / 3.0),
+			  (3, 3, 1.0), (3, 2, 1.0), (2, 3, 1.0)],
+		(4, Line::Diagonal) =>
+			&[(3, 2, 0.5), (2, 3, 0.5), (3, 3, 1.0)],
+		(4, Line::Corner) =>
+			&[(3, 3, 0.68), (3, 2, 0.09), (2, 3, 0.09)],
+
+		(5, Line::Shallow) | (5, Line::Steep) =>
+			&[(4, 0, 0.25), (3, 2, 0.25), (2, 4, 0.25), (4, 1, 0.75), (3, 3, 0.75),
+			  (4, 2, 1.0), (4, 3, 1.0), (4, 4, 1.0), (3, 4, 1.0)],
+		(5, Line::SteepAndShallow) =>
+			&[(0, 4, 0.25), (2, 3, 0.25), (1, 4, 0.75), (4, 0, 0.25), (3, 2, 0.25), (4, 1, 0.75),
+			  (3, 3, 2.0 / 3.0), (2, 4, 1.0), (3, 4, 1.0), (4, 4, 1.0), (4, 2, 1.0), (4, 3, 1.0)],
+		(5, Line::Diagonal) =>
+			&[(4, 2, 0.125), (3, 3, 0.125), (2, 4, 0.125), (4, 3, 0.875), (3, 4, 0.875), (4, 4, 1.0)],
+		(5, Line::Corner) =>
+			&[(4, 4, 0.86), (4, 3, 0.23), (3, 4, 0.23)],
+
+		(_, Line::Shallow) | (_, Line::Steep) =>
+			&[(5, 0, 0.25), (4, 2, 0.25), (3, 4, 0.25), (5, 1, 0.75), (4, 3, 0.75), (3, 5, 0.75),
+			  (5, 2, 1.0), (5, 3, 1.0), (5, 4, 1.0), (5, 5, 1.0), (4, 4, 1.0), (4, 5, 1.0)],
+		(_, Line::SteepAndShallow) =>
+			&[(0, 5, 0.25), (2, 4, 0.25), (1, 5, 0.75), (3, 4, 0.75), (5, 0, 0.25), (4, 2, 0.25),
+			  (5, 1, 0.75), (4, 3, 0.75), (2, 5, 1.0), (3, 5, 1.0), (4, 5, 1.0), (5, 5, 1.0),
+			  (4, 4, 1.0), (5, 2, 1.0), (5, 3, 1.0), (5, 4, 1.0)],
+		(_, Line::Diagonal) =>
+			&[(5, 3, 0.5), (4, 4, 0.5), (3, 5, 0.5), (4, 5, 1.0), (5, 5, 1.0), (5, 4, 1.0)],
+		(_, Line::Corner) =>
+			&[(5, 5, 0.97), (4, 5, 0.42), (5, 4, 0.42), (5, 3, 0.06), (3, 5, 0.06)],
+	}
+}
+
+/// Get the distance between the colors in `YCbCr`, transparent colors are
+/// closer.
+fn distance(a: Rgba, b: Rgba) -> f32 {
+	let dr = (a.red - b.red) * 255.0;
+	let dg = (a.green - b.green) * 255.0;
+	let db = (a.blue - b.blue) * 255.0;
+
+	let y  = 0.2627 * dr + 0.6780 * dg + 0.0593 * db;
+	let cb = 0.5 / (1.0 - 0.0593) * (db - y);
+	let cr = 0.5 / (1.0 - 0.2627) * (dr - y);
+	let d  = (y * y + cb * cb + cr * cr).sqrt();
+
+	if a.alpha < b.alpha {
+		a.alpha * d + 255.0 * (b.alpha - a.alpha)
+	}
+	else {
+		b.alpha * d + 255.0 * (a.alpha - b.alpha)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use super::super::grid::test::{edge, block};
+	use color::Rgb;
+	use processing::prelude::*;
+
+	#[test]
+	fn x2() {
+		let result = edge().scale_by::<Xbrz>(2.0);
+
+		assert_eq!(vec![0.0, 0.0, 0.0, 0.5], block(&result, 1, 2, 2));
+		assert_eq!(vec![0.5, 1.0, 1.0, 1.0], block(&result, 2, 2, 2));
+	}
+
+	#[test]
+	fn empty() {
+		let image = Buffer::<Rgb, f32, _>::new(0, 0);
+		assert_eq!((4, 3), image.resize::<Xbrz>(4, 3).dimensions());
+	}
+
+	#[test]
+	fn x3() {
+		let result = edge().scale_by::<Xbrz>(3.0);
+
+		assert_eq!(vec![
+			0.0, 0.0,   0.0,
+			0.0, 0.0,   0.125,
+			0.0, 0.125, 0.875], block(&result, 1, 2, 3));
+
+		assert_eq!(vec![
+			0.125, 0.875, 1.0,
+			0.875, 1.0,   1.0,
+			1.0,   1.0,   1.0], block(&result, 2, 2, 3));
+	}
+
+	#[test]
+	fn x6() {
+		let result = edge().scale_by::<Xbrz>(6.0);
+
+		assert_eq!(vec![
+			0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+			0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+			0.0, 0.0, 0.0, 0.0, 0.0, 0.0,
+			0.0, 0.0, 0.0, 0.0, 0.0, 0.5,
+			0.0, 0.0, 0.0, 0.0, 0.5, 1.0,
+			0.0, 0.0, 0.0, 0.5, 1.0, 1.0], block(&result, 1, 2, 6));
+	}
+}
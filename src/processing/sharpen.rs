@@ -15,8 +15,9 @@
 use buffer::Buffer;
 use pixel;
 use view;
+use edge::EdgeMode;
 use color::{Rgba, ComponentWise, Limited};
-use processing::blur;
+use processing::{blur, parallel};
 
 /// Trait for blurrable types.
 pub trait Sharpen<P, C>
@@ -38,6 +39,22 @@ pub trait Sharpen<P, C>
 	/// image.sharpen(4.0, 0.02);
 	/// ```
 	fn sharpen(self, sigma: f32, threshold: f32) -> Buffer<P, C, Vec<C>>;
+
+	/// Sharpen by the given radius and threshold, handling the edges with the
+	/// given `EdgeMode`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::{read, EdgeMode};
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// image.sharpen_with(4.0, 0.02, EdgeMode::Mirror);
+	/// ```
+	fn sharpen_with(self, sigma: f32, threshold: f32, edge: EdgeMode) -> Buffer<P, C, Vec<C>>;
 }
 
 impl<'i, P, C, I> Sharpen<P, C> for I
@@ -50,6 +67,11 @@ impl<'i, P, C, I> Sharpen<P, C> for I
 	fn sharpen(self, sigma: f32, threshold: f32) -> Buffer<P, C, Vec<C>> {
 		by::<_, P, C, P, C>(self, sigma, threshold)
 	}
+
+	#[inline]
+	fn sharpen_with(self, sigma: f32, threshold: f32, edge: EdgeMode) -> Buffer<P, C, Vec<C>> {
+		by_with::<_, P, C, P, C>(self, sigma, threshold, edge)
+	}
 }
 
 /// Sharpen by the given radius and threshold.
@@ -62,25 +84,42 @@ pub fn by<'i, I, PI, CI, PO, CO>(input: I, sigma: f32, threshold: f32) -> Buffer
 	      PI: Into<Rgba>,
 	      PI: pixel::Read<CI>,
 	      CI: pixel::Channel,
+{
+	by_with(input, sigma, threshold, Default::default())
+}
+
+/// Sharpen by the given radius and threshold, handling the edges with the
+/// given `EdgeMode`.
+#[inline]
+pub fn by_with<'i, I, PI, CI, PO, CO>(input: I, sigma: f32, threshold: f32, edge: EdgeMode) -> Buffer<PO, CO, Vec<CO>>
+	where I:  Into<view::Read<'i, PI, CI>>,
+	      PO: From<Rgba> + Into<Rgba>,
+	      PO: pixel::Read<CO> + pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
 {
 	let     input  = input.into();
-	let mut output = blur::by::<_, PI, CI, PO, CO>(&input, sigma);
+	let mut output = blur::by_with::<_, PI, CI, PO, CO>(&input, sigma, edge);
 
-	for ((_, _, i), (_, _, mut o)) in input.pixels().zip(output.pixels_mut()) {
-		let a = i.get().into();
-		let b = o.get().into();
+	parallel::each(output.view(Default::default()).rows(), |y, mut row| {
+		for x in 0 .. input.width() {
+			let a = input.get(x, y as u32).into();
+			let b = row.get(x, 0).into();
 
-		o.set(&a.component_wise(&b, |a, b| {
-			let diff = (a - b).abs();
+			row.set(x, 0, &a.component_wise(&b, |a, b| {
+				let diff = (a - b).abs();
 
-			if diff > threshold {
-				a + diff
-			}
-			else {
-				a
-			}
-		}).clamp().into())
-	}
+				if diff > threshold {
+					a + diff
+				}
+				else {
+					a
+				}
+			}).clamp().into())
+		}
+	});
 
 	output
 }
@@ -0,0 +1,170 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::any::TypeId;
+
+use buffer::Buffer;
+use pixel;
+use view;
+use color::Rgba;
+
+/// Decode an sRGB encoded channel to linear light.
+#[inline]
+pub fn to_linear(value: f32) -> f32 {
+	if value <= 0.04045 {
+		value / 12.92
+	}
+	else {
+		((value + 0.055) / 1.055).powf(2.4)
+	}
+}
+
+/// Encode a linear light channel to sRGB.
+#[inline]
+pub fn from_linear(value: f32) -> f32 {
+	if value <= 0.003_130_8 {
+		value * 12.92
+	}
+	else {
+		1.055 * value.powf(1.0 / 2.4) - 0.055
+	}
+}
+
+/// Decode the sRGB encoded input to linear light, the alpha is left alone.
+///
+/// Inputs with `u8` channels go through a lookup table.
+///
+/// # Example
+///
+/// ```
+/// use picto::read;
+/// use picto::color::Rgb;
+/// use picto::processing::prelude::*;
+///
+/// let image  = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+/// let linear = srgb::decode(&image);
+///
+/// assert_eq!(image, srgb::encode::<Rgb, u8>(&linear));
+/// ```
+pub fn decode<'i, I, P, C>(input: I) -> Buffer<Rgba, f32, Vec<f32>>
+	where I: Into<view::Read<'i, P, C>>,
+	      P: Into<Rgba>,
+	      P: pixel::Read<C>,
+	      C: pixel::Channel,
+{
+	let input = input.into();
+	let table = if TypeId::of::<C>() == TypeId::of::<u8>() {
+		Some(decoding())
+	}
+	else {
+		None
+	};
+
+	let decode = |value: f32| if let Some(ref table) = table {
+		table[(value * 255.0).round().clamp(0.0, 255.0) as usize]
+	}
+	else {
+		to_linear(value)
+	};
+
+	input.convert_with(|p| {
+		let p = p.into();
+		Rgba::new(decode(p.red), decode(p.green), decode(p.blue), p.alpha)
+	})
+}
+
+/// Encode the linear light input to sRGB.
+#[inline]
+pub fn encode<P, C>(input: &Buffer<Rgba, f32, Vec<f32>>) -> Buffer<P, C, Vec<C>>
+	where P: From<Rgba>,
+	      P: pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	let mut output = Buffer::<P, C, _>::new(input.width(), input.height());
+	encode_into(input, &mut output);
+
+	output
+}
+
+/// Encode the linear light input to sRGB into the output.
+///
+/// Outputs with `u8` channels go through a lookup table.
+pub fn encode_into<'o, O, P, C>(input: &Buffer<Rgba, f32, Vec<f32>>, output: O)
+	where O: Into<view::Write<'o, P, C>>,
+	      P: From<Rgba>,
+	      P: pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	let mut output = output.into();
+	let     table  = if TypeId::of::<C>() == TypeId::of::<u8>() {
+		Some(encoding())
+	}
+	else {
+		None
+	};
+
+	// Channels are truncated when written, so aim at the middle of the found
+	// step.
+	let encode = |value: f32| if let Some(ref table) = table {
+		(table.partition_point(|&threshold| threshold < value) as f32 + 0.5) / 255.0
+	}
+	else {
+		from_linear(value).clamp(0.0, 1.0)
+	};
+
+	for (x, y, p) in input.pixels() {
+		let p = p.get();
+		output.set(x, y, &Rgba::new(encode(p.red), encode(p.green), encode(p.blue), p.alpha).into());
+	}
+}
+
+/// Build the table decoding every `u8` value.
+fn decoding() -> Vec<f32> {
+	(0 .. 256).map(|value| to_linear(value as f32 / 255.0)).collect()
+}
+
+/// Build the table of linear values halfway between two consecutive `u8`
+/// values.
+fn encoding() -> Vec<f32> {
+	let table = decoding();
+	table.windows(2).map(|w| (w[0] + w[1]) / 2.0).collect()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use buffer;
+	use color::Rgb;
+
+	#[test]
+	fn roundtrip() {
+		for value in 0 .. 256 {
+			let value = value as f32 / 255.0;
+			assert!((from_linear(to_linear(value)) - value).abs() < 0.0001);
+		}
+
+		let image = buffer::Rgb::from_fn(16, 16, |x, y|
+			Rgb::new(x as f32 / 15.0, y as f32 / 15.0, 0.5));
+
+		assert_eq!(image, encode::<Rgb, u8>(&decode(&image)));
+	}
+
+	#[test]
+	fn middle() {
+		let linear = Buffer::<Rgba, f32, _>::from_pixel(1, 1, &Rgba::new(0.5, 0.5, 0.5, 1.0));
+		let output = encode::<Rgb, u8>(&linear);
+
+		assert_eq!(188, output[0]);
+	}
+}
@@ -0,0 +1,330 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use buffer::{self, Buffer};
+use pixel;
+use view;
+use color::{Rgba, Luma};
+use processing::Histogram;
+
+/// The amount of bins used to find the Otsu level.
+const BINS: u32 = 256;
+
+/// How the local level is computed for adaptive thresholding.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Adaptive {
+	/// The mean of the neighbourhood.
+	Mean,
+
+	/// The Gaussian-weighted mean of the neighbourhood.
+	Gaussian,
+}
+
+impl Default for Adaptive {
+	#[inline]
+	fn default() -> Self {
+		Adaptive::Mean
+	}
+}
+
+/// Trait for thresholdable types.
+///
+/// Pixels with a luminance of at least the level become white in the
+/// resulting mask, the others become black.
+pub trait Threshold<P, C>
+	where P: Into<Rgba>,
+	      P: pixel::Read<C>,
+	      C: pixel::Channel,
+{
+	/// Threshold the whole image at the given level.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// image.threshold(0.5);
+	/// ```
+	fn threshold(self, level: f32) -> buffer::Luma;
+
+	/// Threshold the whole image at the level picked with Otsu's method.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// image.threshold_otsu();
+	/// ```
+	fn threshold_otsu(self) -> buffer::Luma;
+
+	/// Threshold every pixel at the level of its neighbourhood, the square
+	/// block of the given size around it, lowered by the offset.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::color::Rgb;
+	/// use picto::processing::prelude::*;
+	///
+	/// let image = read::from_path::<Rgb, u8, _>("tests/boat.xyz").unwrap();
+	///
+	/// image.threshold_adaptive(threshold::Adaptive::Gaussian, 11, 0.02);
+	/// ```
+	fn threshold_adaptive(self, method: Adaptive, block: u32, offset: f32) -> buffer::Luma;
+}
+
+impl<'i, P, C, I> Threshold<P, C> for I
+	where P: Into<Rgba>,
+	      P: pixel::Read<C>,
+	      C: pixel::Channel,
+	      I: Into<view::Read<'i, P, C>>
+{
+	#[inline]
+	fn threshold(self, level: f32) -> buffer::Luma {
+		fixed(self, level)
+	}
+
+	#[inline]
+	fn threshold_otsu(self) -> buffer::Luma {
+		otsu(self)
+	}
+
+	#[inline]
+	fn threshold_adaptive(self, method: Adaptive, block: u32, offset: f32) -> buffer::Luma {
+		adaptive(self, method, block, offset)
+	}
+}
+
+/// Threshold the whole image at the given level.
+pub fn fixed<'i, P, C, I>(input: I, level: f32) -> buffer::Luma
+	where P: Into<Rgba>,
+	      P: pixel::Read<C>,
+	      C: pixel::Channel,
+	      I: Into<view::Read<'i, P, C>>
+{
+	let input = input.into();
+
+	Buffer::from_fn(input.width(), input.height(), |x, y|
+		mask(luma(input.get(x, y)) >= level))
+}
+
+/// Threshold the whole image at the level picked with Otsu's method.
+pub fn otsu<'i, P, C, I>(input: I) -> buffer::Luma
+	where P: Into<Rgba>,
+	      P: pixel::Read<C>,
+	      C: pixel::Channel,
+	      I: Into<view::Read<'i, P, C>>
+{
+	let input = input.into();
+	let level = level(&input);
+
+	fixed(&input, level)
+}
+
+/// Pick the level maximizing the variance between the two classes of pixels
+/// (Otsu's method).
+///
+/// # Example
+///
+/// ```
+/// use picto::buffer;
+/// use picto::color::Luma;
+/// use picto::processing::threshold;
+///
+/// let image = buffer::Luma::from_fn(4, 4, |x, _|
+///     if x < 2 { Luma::new(0.2) } else { Luma::new(0.8) });
+///
+/// let level = threshold::level(&image);
+/// assert!(level > 0.2 && level < 0.8);
+/// ```
+pub fn level<'i, P, C, I>(input: I) -> f32
+	where P: Into<Rgba>,
+	      P: pixel::Read<C>,
+	      C: pixel::Channel,
+	      I: Into<view::Read<'i, P, C>>
+{
+	let histogram = Histogram::luma(input, BINS, (0.0, 1.0));
+	let counts    = histogram.get(0);
+	let total     = histogram.total(0) as f64;
+	let sum       = counts.iter().enumerate().map(|(i, &n)| i as f64 * n as f64).sum::<f64>();
+
+	let mut best    = (0.0, 0, 0);
+	let mut weight  = 0.0;
+	let mut partial = 0.0;
+
+	// The first bin of the upper class is picked, ties span the bins between
+	// two populated ones so the middle is taken.
+	for (i, &n) in counts.iter().enumerate().take(BINS as usize - 1) {
+		weight  += n as f64;
+		partial += i as f64 * n as f64;
+
+		if weight == 0.0 || weight == total {
+			continue;
+		}
+
+		let lower    = partial / weight;
+		let upper    = (sum - partial) / (total - weight);
+		let variance = weight * (total - weight) * (lower - upper).powi(2);
+
+		if variance > best.0 {
+			best = (variance, i + 1, i + 1);
+		}
+		else if variance == best.0 && best.2 == i {
+			best.2 = i + 1;
+		}
+	}
+
+	((best.1 + best.2) / 2) as f32 / BINS as f32
+}
+
+/// Threshold every pixel at the level of its neighbourhood, the square block
+/// of the given size around it, lowered by the offset.
+///
+/// Neighbourhoods are limited to the pixels within the image.
+pub fn adaptive<'i, P, C, I>(input: I, method: Adaptive, block: u32, offset: f32) -> buffer::Luma
+	where P: Into<Rgba>,
+	      P: pixel::Read<C>,
+	      C: pixel::Channel,
+	      I: Into<view::Read<'i, P, C>>
+{
+	let input  = input.into();
+	let width  = input.width() as usize;
+	let height = input.height() as usize;
+	let radius = (block / 2) as i64;
+	let values = input.pixels().map(|(_, _, px)| luma(px.get())).collect::<Vec<f32>>();
+
+	let levels = match method {
+		Adaptive::Mean =>
+			separable(&values, width, height, &vec![1.0; radius as usize * 2 + 1]),
+
+		Adaptive::Gaussian => {
+			// The same relation between size and deviation as OpenCV.
+			let sigma   = (0.3 * ((block.max(1) as f32 - 1.0) * 0.5 - 1.0) + 0.8).max(0.1);
+			let weights = (-radius ..= radius)
+				.map(|i| (-((i * i) as f32) / (2.0 * sigma * sigma)).exp())
+				.collect::<Vec<f32>>();
+
+			separable(&values, width, height, &weights)
+		}
+	};
+
+	Buffer::from_fn(input.width(), input.height(), |x, y| {
+		let index = y as usize * width + x as usize;
+		mask(values[index] >= levels[index] - offset)
+	})
+}
+
+/// Compute the weighted means with the symmetric weights horizontally then
+/// vertically, normalizing by the weights falling within the image.
+fn separable(values: &[f32], width: usize, height: usize, weights: &[f32]) -> Vec<f32> {
+	let radius = (weights.len() / 2) as i64;
+
+	let pass = |values: &[f32], at: &dyn Fn(usize, i64) -> Option<usize>| {
+		(0 .. values.len()).map(|index| {
+			let mut sum   = 0.0;
+			let mut total = 0.0;
+
+			for (i, &weight) in weights.iter().enumerate() {
+				if let Some(neighbour) = at(index, i as i64 - radius) {
+					sum   += values[neighbour] * weight;
+					total += weight;
+				}
+			}
+
+			sum / total
+		}).collect::<Vec<f32>>()
+	};
+
+	let horizontal = pass(values, &|index, offset| {
+		let x = (index % width) as i64 + offset;
+
+		if x >= 0 && x < width as i64 { Some((index as i64 + offset) as usize) } else { None }
+	});
+
+	pass(&horizontal, &|index, offset| {
+		let y = (index / width) as i64 + offset;
+
+		if y >= 0 && y < height as i64 { Some((index as i64 + offset * width as i64) as usize) } else { None }
+	})
+}
+
+#[inline]
+fn luma<P: Into<Rgba>>(px: P) -> f32 {
+	Luma::from(px.into().color).luma
+}
+
+#[inline]
+fn mask(value: bool) -> Luma {
+	if value {
+		Luma::new(1.0)
+	}
+	else {
+		Luma::new(0.0)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn fixed() {
+		let image = buffer::Luma::from_raw(3, 1, vec![0, 128, 255]).unwrap();
+		let mask  = image.threshold(0.5);
+
+		assert_eq!(vec![0, 255, 255], mask.into_raw());
+	}
+
+	#[test]
+	fn otsu() {
+		let image = buffer::Luma::from_raw(6, 1, vec![10, 20, 30, 200, 210, 220]).unwrap();
+		let level = super::level(&image);
+
+		assert!(level > 30.0 / 255.0 && level < 200.0 / 255.0);
+		assert_eq!(vec![0, 0, 0, 255, 255, 255], image.threshold_otsu().into_raw());
+	}
+
+	#[test]
+	fn adaptive() {
+		// A dark stroke over a background getting brighter from left to right,
+		// darker than most of the background on the right.
+		let image = buffer::Luma::from_fn(16, 3, |x, y|
+			if x == 12 && y == 1 {
+				Luma::new(0.5)
+			}
+			else {
+				Luma::new(0.2 + x as f32 * 0.04)
+			});
+
+		for &method in &[Adaptive::Mean, Adaptive::Gaussian] {
+			let mask = image.threshold_adaptive(method, 5, 0.05);
+
+			assert_eq!(Luma::new(0.0), mask.get(12, 1));
+			assert_eq!(Luma::new(1.0), mask.get(2, 1));
+			assert_eq!(Luma::new(1.0), mask.get(12, 0));
+		}
+
+		assert_eq!(Luma::new(0.0), image.threshold_otsu().get(2, 1));
+	}
+}
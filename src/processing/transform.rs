@@ -0,0 +1,486 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::ops::Mul;
+
+use pixel;
+use view;
+use color::Rgba;
+use edge::EdgeMode;
+use processing::{sample, Sampler, parallel};
+
+/// An affine transformation.
+///
+/// The matrix is stored row-major, mapping `(x, y)` to
+/// `(m[0][0] * x + m[0][1] * y + m[0][2], m[1][0] * x + m[1][1] * y + m[1][2])`.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct Affine(pub [[f32; 3]; 2]);
+
+/// A projective transformation (homography).
+///
+/// The matrix is stored row-major and operates on homogeneous coordinates.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct Projective(pub [[f32; 3]; 3]);
+
+impl Affine {
+	/// Create the identity transformation.
+	#[inline]
+	pub fn identity() -> Self {
+		Affine([
+			[1.0, 0.0, 0.0],
+			[0.0, 1.0, 0.0]])
+	}
+
+	/// Create a translation by the given offsets.
+	#[inline]
+	pub fn translate(x: f32, y: f32) -> Self {
+		Affine([
+			[1.0, 0.0, x],
+			[0.0, 1.0, y]])
+	}
+
+	/// Create a scaling by the given factors.
+	#[inline]
+	pub fn scale(x: f32, y: f32) -> Self {
+		Affine([
+			[x,   0.0, 0.0],
+			[0.0, y,   0.0]])
+	}
+
+	/// Create a shearing by the given factors.
+	#[inline]
+	pub fn shear(x: f32, y: f32) -> Self {
+		Affine([
+			[1.0, x,   0.0],
+			[y,   1.0, 0.0]])
+	}
+
+	/// Create a rotation around the origin by the given degree, negative
+	/// degrees will turn counter-clockwise.
+	#[inline]
+	pub fn rotate(by: f32) -> Self {
+		let (sin, cos) = sin_cos(by);
+
+		Affine([
+			[cos, -sin, 0.0],
+			[sin, cos,  0.0]])
+	}
+
+	/// Create a transformation applying `self` and then `other`.
+	#[inline]
+	pub fn then(&self, other: &Affine) -> Self {
+		let a = &other.0;
+		let b = &self.0;
+
+		Affine([
+			[a[0][0] * b[0][0] + a[0][1] * b[1][0],
+			 a[0][0] * b[0][1] + a[0][1] * b[1][1],
+			 a[0][0] * b[0][2] + a[0][1] * b[1][2] + a[0][2]],
+			[a[1][0] * b[0][0] + a[1][1] * b[1][0],
+			 a[1][0] * b[0][1] + a[1][1] * b[1][1],
+			 a[1][0] * b[0][2] + a[1][1] * b[1][2] + a[1][2]]])
+	}
+
+	/// Get the inverse transformation, if there is one.
+	#[inline]
+	pub fn inverse(&self) -> Option<Self> {
+		let m   = &self.0;
+		let det = m[0][0] * m[1][1] - m[0][1] * m[1][0];
+
+		if det == 0.0 {
+			return None;
+		}
+
+		let a =  m[1][1] / det;
+		let b = -m[0][1] / det;
+		let d = -m[1][0] / det;
+		let e =  m[0][0] / det;
+
+		Some(Affine([
+			[a, b, -(a * m[0][2] + b * m[1][2])],
+			[d, e, -(d * m[0][2] + e * m[1][2])]]))
+	}
+
+	/// Apply the transformation to the given point.
+	#[inline]
+	pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+		let m = &self.0;
+
+		(m[0][0] * x + m[0][1] * y + m[0][2],
+		 m[1][0] * x + m[1][1] * y + m[1][2])
+	}
+}
+
+impl Default for Affine {
+	#[inline]
+	fn default() -> Self {
+		Affine::identity()
+	}
+}
+
+impl Mul for Affine {
+	type Output = Affine;
+
+	/// Compose the transformations, the right-hand side is applied first.
+	#[inline]
+	fn mul(self, rhs: Affine) -> Affine {
+		rhs.then(&self)
+	}
+}
+
+impl Projective {
+	/// Create the identity transformation.
+	#[inline]
+	pub fn identity() -> Self {
+		Affine::identity().into()
+	}
+
+	/// Compute the homography mapping each of the `from` points to the
+	/// matching `to` point.
+	///
+	/// Returns `None` if the points are degenerate, for instance when three of
+	/// them are collinear, or if any of them isn't finite.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::processing::transform::Projective;
+	///
+	/// // Map a keystoned quadrilateral back to a rectangle.
+	/// let matrix = Projective::from_points(
+	///     &[(10.0, 0.0), (90.0, 0.0), (100.0, 100.0), (0.0, 100.0)],
+	///     &[(0.0, 0.0), (100.0, 0.0), (100.0, 100.0), (0.0, 100.0)]).unwrap();
+	///
+	/// let (x, y) = matrix.apply(90.0, 0.0);
+	/// assert!((x - 100.0).abs() < 0.001 && y.abs() < 0.001);
+	/// ```
+	pub fn from_points(from: &[(f32, f32); 4], to: &[(f32, f32); 4]) -> Option<Self> {
+		// Each correspondence gives two equations in the eight unknowns, the last
+		// element of the matrix is fixed to 1.
+		let mut system = [[0.0f64; 9]; 8];
+
+		if from.iter().chain(to.iter()).any(|&(x, y)| !x.is_finite() || !y.is_finite()) {
+			return None;
+		}
+
+		for (i, (&(x, y), &(u, v))) in from.iter().zip(to.iter()).enumerate() {
+			let (x, y, u, v) = (x as f64, y as f64, u as f64, v as f64);
+
+			system[i * 2]     = [x, y, 1.0, 0.0, 0.0, 0.0, -u * x, -u * y, u];
+			system[i * 2 + 1] = [0.0, 0.0, 0.0, x, y, 1.0, -v * x, -v * y, v];
+		}
+
+		// Gaussian elimination with partial pivoting.
+		for column in 0 .. 8 {
+			let pivot = (column .. 8).max_by(|&a, &b|
+				system[a][column].abs().partial_cmp(&system[b][column].abs()).unwrap()).unwrap();
+
+			if system[pivot][column].abs() < 1e-10 {
+				return None;
+			}
+
+			system.swap(column, pivot);
+
+			let pivot = system[column];
+
+			for (index, row) in system.iter_mut().enumerate() {
+				if index == column {
+					continue;
+				}
+
+				let factor = row[column] / pivot[column];
+
+				for (value, p) in row.iter_mut().zip(pivot.iter()).skip(column) {
+					*value -= factor * p;
+				}
+			}
+		}
+
+		let h = |i: usize| (system[i][8] / system[i][i]) as f32;
+
+		Some(Projective([
+			[h(0), h(1), h(2)],
+			[h(3), h(4), h(5)],
+			[h(6), h(7), 1.0]]))
+	}
+
+	/// Create a transformation applying `self` and then `other`.
+	#[inline]
+	pub fn then(&self, other: &Projective) -> Self {
+		let a = &other.0;
+		let b = &self.0;
+
+		let mut result = [[0.0; 3]; 3];
+
+		for (i, row) in result.iter_mut().enumerate() {
+			for (j, value) in row.iter_mut().enumerate() {
+				*value = a[i][0] * b[0][j] + a[i][1] * b[1][j] + a[i][2] * b[2][j];
+			}
+		}
+
+		Projective(result)
+	}
+
+	/// Get the inverse transformation, if there is one.
+	pub fn inverse(&self) -> Option<Self> {
+		let m   = &self.0;
+		let det = m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1]) -
+		          m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0]) +
+		          m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0]);
+
+		if det == 0.0 {
+			return None;
+		}
+
+		Some(Projective([
+			[(m[1][1] * m[2][2] - m[1][2] * m[2][1]) / det,
+			 (m[0][2] * m[2][1] - m[0][1] * m[2][2]) / det,
+			 (m[0][1] * m[1][2] - m[0][2] * m[1][1]) / det],
+			[(m[1][2] * m[2][0] - m[1][0] * m[2][2]) / det,
+			 (m[0][0] * m[2][2] - m[0][2] * m[2][0]) / det,
+			 (m[0][2] * m[1][0] - m[0][0] * m[1][2]) / det],
+			[(m[1][0] * m[2][1] - m[1][1] * m[2][0]) / det,
+			 (m[0][1] * m[2][0] - m[0][0] * m[2][1]) / det,
+			 (m[0][0] * m[1][1] - m[0][1] * m[1][0]) / det]]))
+	}
+
+	/// Apply the transformation to the given point.
+	///
+	/// Points mapped to infinity will have infinite or `NaN` coordinates.
+	#[inline]
+	pub fn apply(&self, x: f32, y: f32) -> (f32, f32) {
+		let m = &self.0;
+		let w = m[2][0] * x + m[2][1] * y + m[2][2];
+
+		((m[0][0] * x + m[0][1] * y + m[0][2]) / w,
+		 (m[1][0] * x + m[1][1] * y + m[1][2]) / w)
+	}
+}
+
+impl Default for Projective {
+	#[inline]
+	fn default() -> Self {
+		Projective::identity()
+	}
+}
+
+impl Mul for Projective {
+	type Output = Projective;
+
+	/// Compose the transformations, the right-hand side is applied first.
+	#[inline]
+	fn mul(self, rhs: Projective) -> Projective {
+		rhs.then(&self)
+	}
+}
+
+impl From<Affine> for Projective {
+	#[inline]
+	fn from(value: Affine) -> Projective {
+		let m = value.0;
+
+		Projective([m[0], m[1], [0.0, 0.0, 1.0]])
+	}
+}
+
+/// Trait for transformable types.
+pub trait Transform<PI, CI, PO, CO>
+	where PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+{
+	/// Render the transformed input into the output with the given `Sampler`.
+	///
+	/// The transformation maps input coordinates to output coordinates, any
+	/// output pixel not covered by the input is set to `fill`.
+	///
+	/// # Example
+	///
+	/// ```
+	/// use picto::read;
+	/// use picto::buffer;
+	/// use picto::color::Rgba;
+	/// use picto::processing::prelude::*;
+	/// use picto::processing::transform::Affine;
+	///
+	/// let     image  = read::from_path::<Rgba, u8, _>("tests/boat.xyz").unwrap();
+	/// let mut output = buffer::Rgba::new(image.width(), image.height());
+	///
+	/// // Straighten the image by rotating around its center.
+	/// let matrix = Affine::translate(-160.0, -120.0)
+	///     .then(&Affine::rotate(-2.0))
+	///     .then(&Affine::translate(160.0, 120.0));
+	///
+	/// image.transform::<sampler::Cubic, _, _>(&mut output, matrix,
+	///     &Rgba::new(1.0, 1.0, 1.0, 1.0));
+	/// ```
+	fn transform<'o, A, T, O>(self, output: O, by: T, fill: &PO)
+		where A: Sampler,
+		      T: Into<Projective>,
+		      O: Into<view::Write<'o, PO, CO>>;
+}
+
+impl<'i, PI, CI, PO, CO, I> Transform<PI, CI, PO, CO> for I
+	where PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      PO: From<Rgba> + Into<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      I:  Into<view::Read<'i, PI, CI>>
+{
+	#[inline]
+	fn transform<'o, A, T, O>(self, output: O, by: T, fill: &PO)
+		where A: Sampler,
+		      T: Into<Projective>,
+		      O: Into<view::Write<'o, PO, CO>>
+	{
+		it::<A, _, _, PI, CI, PO, CO, _>(self, output, by, fill)
+	}
+}
+
+/// Render the transformed input into the output with the given `Sampler`.
+///
+/// The transformation maps input coordinates to output coordinates, any output
+/// pixel not covered by the input is set to `fill`.
+pub fn it<'i, 'o, A, I, O, PI, CI, PO, CO, T>(input: I, output: O, by: T, fill: &PO)
+	where A:  Sampler,
+	      T:  Into<Projective>,
+	      PO: From<Rgba> + Into<Rgba>,
+	      PO: pixel::Write<CO>,
+	      CO: pixel::Channel,
+	      PI: Into<Rgba>,
+	      PI: pixel::Read<CI>,
+	      CI: pixel::Channel,
+	      I:  Into<view::Read<'i, PI, CI>>,
+	      O:  Into<view::Write<'o, PO, CO>>
+{
+	let     input  = input.into();
+	let mut output = output.into();
+	let     fill   = *fill;
+
+	let inverse = if let Some(inverse) = by.into().inverse() {
+		inverse
+	}
+	else {
+		output.fill(&fill);
+		return;
+	};
+
+	let fill  = fill.into();
+	let width = output.width();
+
+	parallel::rows(output, |y, row| {
+		for x in 0 .. width {
+			let (sx, sy) = inverse.apply(x as f32 + 0.5, y as f32 + 0.5);
+
+			let px = if sx.is_finite() && sy.is_finite() {
+				sample::point::<A, _, _, _>(&input, sx, sy, EdgeMode::Constant(fill))
+			}
+			else {
+				fill
+			};
+
+			row.set(x, 0, &px.into());
+		}
+	});
+}
+
+/// Get the sine and cosine of the given degree, right angles are exact.
+#[inline]
+fn sin_cos(by: f32) -> (f32, f32) {
+	let by = by % 360.0;
+	let by = if by < 0.0 { by + 360.0 } else { by };
+
+	if by == 0.0 {
+		(0.0, 1.0)
+	}
+	else if by == 90.0 {
+		(1.0, 0.0)
+	}
+	else if by == 180.0 {
+		(0.0, -1.0)
+	}
+	else if by == 270.0 {
+		(-1.0, 0.0)
+	}
+	else {
+		by.to_radians().sin_cos()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use processing::sampler::Nearest;
+	use buffer;
+	use color::Rgba;
+
+	#[test]
+	fn affine_inverse() {
+		let matrix  = Affine::rotate(30.0).then(&Affine::translate(10.0, 5.0)).then(&Affine::scale(2.0, 3.0));
+		let inverse = matrix.inverse().unwrap();
+
+		let (x, y) = matrix.apply(4.0, 7.0);
+		let (x, y) = inverse.apply(x, y);
+
+		assert_relative_eq!(4.0, x, epsilon = 0.001);
+		assert_relative_eq!(7.0, y, epsilon = 0.001);
+
+		assert!(Affine::scale(0.0, 1.0).inverse().is_none());
+	}
+
+	#[test]
+	fn from_points() {
+		let from   = [(0.0, 0.0), (10.0, 0.0), (10.0, 10.0), (0.0, 10.0)];
+		let to     = [(1.0, 2.0), (12.0, 1.0), (11.0, 13.0), (-1.0, 9.0)];
+		let matrix = Projective::from_points(&from, &to).unwrap();
+
+		for (&(x, y), &(u, v)) in from.iter().zip(to.iter()) {
+			let (x, y) = matrix.apply(x, y);
+
+			assert_relative_eq!(u, x, epsilon = 0.001);
+			assert_relative_eq!(v, y, epsilon = 0.001);
+		}
+
+		let (x, y) = matrix.inverse().unwrap().apply(11.0, 13.0);
+
+		assert_relative_eq!(10.0, x, epsilon = 0.001);
+		assert_relative_eq!(10.0, y, epsilon = 0.001);
+
+		assert!(Projective::from_points(&from,
+			&[(0.0, 0.0), (1.0, 1.0), (2.0, 2.0), (3.0, 3.0)]).is_none());
+
+		assert!(Projective::from_points(&from,
+			&[(0.0, 0.0), (1.0, f32::NAN), (2.0, 2.0), (3.0, 3.0)]).is_none());
+
+		assert!(Projective::from_points(
+			&[(0.0, 0.0), (f32::INFINITY, 0.0), (10.0, 10.0), (0.0, 10.0)], &to).is_none());
+	}
+
+	#[test]
+	fn translate() {
+		let     input  = buffer::Rgba::from_fn(4, 4, |x, y| Rgba::new(x as f32 / 3.0, y as f32 / 3.0, 0.0, 1.0));
+		let mut output = buffer::Rgba::new(4, 4);
+		let     fill   = Rgba::new(0.0, 0.0, 1.0, 1.0);
+
+		input.transform::<Nearest, _, _>(&mut output, Affine::translate(1.0, 2.0), &fill);
+
+		assert_eq!(fill, output.get(0, 0));
+		assert_eq!(fill, output.get(3, 1));
+		assert_eq!(input.get(0, 0), output.get(1, 2));
+		assert_eq!(input.get(2, 1), output.get(3, 3));
+	}
+}
@@ -0,0 +1,269 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+//! Loading of Glyph Bitmap Distribution Format fonts.
+
+use std::io::{Read, BufRead, BufReader};
+use std::convert::TryFrom;
+
+use error::{self, Error};
+use super::{Font, Glyph};
+
+/// Load a BDF font.
+///
+/// Glyphs are keyed by their encoding as Unicode code point, glyphs without
+/// an encoding are skipped.
+pub fn load<R: Read>(input: R) -> error::Result<Font> {
+	let mut lines    = BufReader::new(input).lines();
+	let mut bounds   = None;
+	let mut ascent   = None;
+	let mut descent  = None;
+	let mut default  = None;
+	let mut glyphs   = Vec::new();
+	let mut started  = false;
+
+	while let Some(line) = lines.next() {
+		let line      = line?;
+		let mut words = line.split_whitespace();
+
+		match words.next() {
+			Some("STARTFONT") =>
+				started = true,
+
+			Some("FONTBOUNDINGBOX") =>
+				bounds = Some(numbers(words, 4)?),
+
+			Some("FONT_ASCENT") =>
+				ascent = Some(numbers(words, 1)?[0]),
+
+			Some("FONT_DESCENT") =>
+				descent = Some(numbers(words, 1)?[0]),
+
+			Some("DEFAULT_CHAR") =>
+				default = Some(numbers(words, 1)?[0]),
+
+			Some("STARTCHAR") if started => {
+				let mut encoding = None;
+				let mut advance  = None;
+				let mut bbx      = None;
+
+				loop {
+					let line      = lines.next().ok_or_else(|| format("unterminated glyph"))??;
+					let mut words = line.split_whitespace();
+
+					match words.next() {
+						Some("ENCODING") =>
+							encoding = Some(numbers(words, 1)?[0]),
+
+						Some("DWIDTH") =>
+							advance = Some(numbers(words, 1)?[0]),
+
+						Some("BBX") =>
+							bbx = Some(numbers(words, 4)?),
+
+						Some("BITMAP") =>
+							break,
+
+						Some("ENDCHAR") =>
+							return Err(format("missing bitmap")),
+
+						_ => ()
+					}
+				}
+
+				let bbx = bbx.or_else(|| bounds.clone()).ok_or_else(|| format("missing bounding box"))?;
+				let (width, height) = (size(bbx[0])?, size(bbx[1])?);
+				let mut bitmap      = Vec::with_capacity(width as usize * height as usize);
+
+				loop {
+					let line = lines.next().ok_or_else(|| format("unterminated glyph"))??;
+					let line = line.trim();
+
+					if line == "ENDCHAR" {
+						break;
+					}
+
+					if bitmap.len() >= width as usize * height as usize {
+						return Err(format("bitmap too long"));
+					}
+
+					if !line.is_ascii() {
+						return Err(format("invalid bitmap"));
+					}
+
+					let bytes = (0 .. line.len() / 2)
+						.map(|i| u8::from_str_radix(&line[i * 2 .. i * 2 + 2], 16))
+						.collect::<Result<Vec<u8>, _>>()
+						.map_err(|_| format("invalid bitmap"))?;
+
+					if bytes.len() * 8 < width as usize {
+						return Err(format("bitmap row too short"));
+					}
+
+					for x in 0 .. width as usize {
+						bitmap.push(bytes[x / 8] >> (7 - x % 8) & 1 == 1);
+					}
+				}
+
+				bitmap.resize(width as usize * height as usize, false);
+
+				if let Some(ch) = encoding.and_then(|n| if n < 0 { None } else { ::std::char::from_u32(n as u32) }) {
+					let advance = match advance {
+						Some(advance) => offset(advance, 0)?,
+						None          => offset(bbx[0], bbx[2])?,
+					}.max(0) as u32;
+
+					glyphs.push((ch, Glyph::new(width, height, bitmap)
+						.bearing(offset(bbx[2], 0)?, offset(bbx[1], bbx[3])?)
+						.advance(advance)));
+				}
+			}
+
+			Some("ENDFONT") =>
+				break,
+
+			_ => ()
+		}
+	}
+
+	if !started {
+		return Err(format("not a BDF font"));
+	}
+
+	let bounds  = bounds.unwrap_or_else(|| vec![0, 0, 0, 0]);
+	let ascent  = match ascent {
+		Some(ascent) => offset(ascent, 0)?,
+		None         => offset(bounds[1], bounds[3])?,
+	}.max(0) as u32;
+	let descent = match descent {
+		Some(descent) => offset(descent, 0)?,
+		None          => offset(bounds[3].saturating_neg(), 0)?,
+	}.max(0) as u32;
+
+	let mut font = Font::new(ascent, descent);
+
+	for (ch, glyph) in glyphs {
+		font.insert(ch, glyph);
+	}
+
+	if let Some(ch) = default.and_then(|n| if n < 0 { None } else { ::std::char::from_u32(n as u32) }) {
+		font.set_default(ch);
+	}
+
+	Ok(font)
+}
+
+#[inline]
+fn format(message: &str) -> Error {
+	Error::Format(message.into())
+}
+
+/// Parse the given amount of integers.
+fn numbers<'a, I: Iterator<Item = &'a str>>(words: I, count: usize) -> error::Result<Vec<i64>> {
+	let values = words.take(count)
+		.map(|w| w.parse::<i64>())
+		.collect::<Result<Vec<_>, _>>()
+		.map_err(|_| format("invalid number"))?;
+
+	if values.len() != count {
+		return Err(format("missing number"));
+	}
+
+	Ok(values)
+}
+
+#[inline]
+fn size(value: i64) -> error::Result<u32> {
+	if !(0 ..= 4096).contains(&value) {
+		Err(format("invalid glyph size"))
+	}
+	else {
+		Ok(value as u32)
+	}
+}
+
+/// Add the two values making sure the result fits an offset.
+#[inline]
+fn offset(a: i64, b: i64) -> error::Result<i32> {
+	a.checked_add(b)
+		.and_then(|value| i32::try_from(value).ok())
+		.ok_or_else(|| format("invalid offset"))
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	const FONT: &str = "STARTFONT 2.1
+FONT -test-
+SIZE 8 75 75
+FONTBOUNDINGBOX 4 6 0 -2
+STARTPROPERTIES 2
+FONT_ASCENT 4
+FONT_DESCENT 2
+ENDPROPERTIES
+CHARS 2
+STARTCHAR A
+ENCODING 65
+SWIDTH 500 0
+DWIDTH 5 0
+BBX 3 4 0 0
+BITMAP
+40
+A0
+E0
+A0
+ENDCHAR
+STARTCHAR g
+ENCODING 103
+DWIDTH 4 0
+BBX 3 4 1 -2
+BITMAP
+60
+A0
+60
+20
+ENDCHAR
+ENDFONT
+";
+
+	#[test]
+	fn load() {
+		let font = super::load(FONT.as_bytes()).unwrap();
+
+		assert_eq!(2, font.len());
+		assert_eq!(4, font.ascent());
+		assert_eq!(2, font.descent());
+
+		let a = font.glyph('A').unwrap();
+		assert_eq!((3, 4, 0, 4, 5), (a.width(), a.height(), a.left(), a.top(), a.advance_width()));
+		assert!(a.get(1, 0));
+		assert!(!a.get(0, 0));
+		assert!(a.get(2, 3));
+
+		let g = font.glyph('g').unwrap();
+		assert_eq!((1, 2), (g.left(), g.top()));
+		assert!(g.get(2, 3));
+		assert!(!g.get(0, 3));
+	}
+
+	#[test]
+	fn invalid() {
+		assert!(super::load("hello".as_bytes()).is_err());
+		assert!(super::load("STARTFONT 2.1\nSTARTCHAR A\nENCODING 65\nBBX 1 1 0 0\nENDCHAR\n".as_bytes()).is_err());
+		assert!(super::load("STARTFONT 2.1\nSTARTCHAR A\nENCODING 65\nBBX 8 1 0 0\nBITMAP\n0\u{e9}\nENDCHAR\n".as_bytes()).is_err());
+		assert!(super::load("STARTFONT 2.1\nSTARTCHAR A\nENCODING 65\nBBX 1 1 0 9223372036854775807\nBITMAP\n80\nENDCHAR\n".as_bytes()).is_err());
+		assert!(super::load("STARTFONT 2.1\nSTARTCHAR A\nENCODING 65\nBBX 1 1 4294967296 0\nBITMAP\n80\nENDCHAR\n".as_bytes()).is_err());
+	}
+}
@@ -0,0 +1,243 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use std::collections::HashMap;
+
+use super::fallback;
+
+/// A bitmap glyph.
+#[derive(Eq, PartialEq, Clone, Debug)]
+pub struct Glyph {
+	width:   u32,
+	height:  u32,
+	left:    i32,
+	top:     i32,
+	advance: u32,
+	bitmap:  Vec<bool>,
+}
+
+impl Glyph {
+	/// Create a `Glyph` from its row-major bitmap, sitting on the baseline and
+	/// advancing by its width.
+	///
+	/// # Panics
+	///
+	/// Requires that the bitmap has `width * height` entries, otherwise it
+	/// will panic.
+	#[inline]
+	pub fn new(width: u32, height: u32, bitmap: Vec<bool>) -> Self {
+		if bitmap.len() != width as usize * height as usize {
+			panic!("wrong dimensions");
+		}
+
+		Glyph {
+			width,
+			height,
+			left:    0,
+			top:     height as i32,
+			advance: width,
+			bitmap,
+		}
+	}
+
+	/// Set the offset of the bitmap from the pen position, horizontally to its
+	/// left edge and vertically from the baseline up to its top row.
+	#[inline]
+	pub fn bearing(mut self, left: i32, top: i32) -> Self {
+		self.left = left;
+		self.top  = top;
+		self
+	}
+
+	/// Set how far the pen moves after the glyph.
+	#[inline]
+	pub fn advance(mut self, value: u32) -> Self {
+		self.advance = value;
+		self
+	}
+
+	/// Get the width of the bitmap.
+	#[inline]
+	pub fn width(&self) -> u32 {
+		self.width
+	}
+
+	/// Get the height of the bitmap.
+	#[inline]
+	pub fn height(&self) -> u32 {
+		self.height
+	}
+
+	/// Get the horizontal offset of the bitmap from the pen position.
+	#[inline]
+	pub fn left(&self) -> i32 {
+		self.left
+	}
+
+	/// Get the distance from the baseline to the top row of the bitmap.
+	#[inline]
+	pub fn top(&self) -> i32 {
+		self.top
+	}
+
+	/// Get how far the pen moves after the glyph.
+	#[inline]
+	pub fn advance_width(&self) -> u32 {
+		self.advance
+	}
+
+	/// Check if the pixel at the given coordinates is set.
+	///
+	/// # Panics
+	///
+	/// Requires that `x < self.width()` and `y < self.height()`, otherwise it
+	/// will panic.
+	#[inline]
+	pub fn get(&self, x: u32, y: u32) -> bool {
+		if x >= self.width || y >= self.height {
+			panic!("out of bounds");
+		}
+
+		self.bitmap[(y * self.width + x) as usize]
+	}
+}
+
+/// A bitmap font.
+///
+/// # Example
+///
+/// ```
+/// use picto::text::Font;
+///
+/// let font = Font::fallback();
+///
+/// assert_eq!((30, 16), font.measure("hello\nworld"));
+/// ```
+#[derive(PartialEq, Clone, Debug)]
+pub struct Font {
+	glyphs:  HashMap<char, Glyph>,
+	ascent:  u32,
+	descent: u32,
+	default: Option<char>,
+}
+
+impl Font {
+	/// Create an empty `Font` with the given distances from the baseline to
+	/// the top and bottom of a line.
+	#[inline]
+	pub fn new(ascent: u32, descent: u32) -> Self {
+		Font {
+			glyphs:  HashMap::new(),
+			ascent,
+			descent,
+			default: None,
+		}
+	}
+
+	/// Get the built-in 5x8 font covering printable ASCII.
+	#[inline]
+	pub fn fallback() -> Self {
+		fallback::font()
+	}
+
+	/// Set the glyph for the character.
+	#[inline]
+	pub fn insert(&mut self, ch: char, glyph: Glyph) {
+		self.glyphs.insert(ch, glyph);
+	}
+
+	/// Set the character drawn in place of missing ones, `?` is used if not
+	/// set.
+	#[inline]
+	pub fn set_default(&mut self, ch: char) {
+		self.default = Some(ch);
+	}
+
+	/// Get the glyph for the character, falling back to the default one.
+	#[inline]
+	pub fn glyph(&self, ch: char) -> Option<&Glyph> {
+		self.glyphs.get(&ch)
+			.or_else(|| self.default.and_then(|ch| self.glyphs.get(&ch)))
+			.or_else(|| self.glyphs.get(&'?'))
+	}
+
+	/// Get the number of glyphs.
+	#[inline]
+	pub fn len(&self) -> usize {
+		self.glyphs.len()
+	}
+
+	/// Check if the font has no glyphs.
+	#[inline]
+	pub fn is_empty(&self) -> bool {
+		self.glyphs.is_empty()
+	}
+
+	/// Get the distance from the baseline to the top of a line.
+	#[inline]
+	pub fn ascent(&self) -> u32 {
+		self.ascent
+	}
+
+	/// Get the distance from the baseline to the bottom of a line.
+	#[inline]
+	pub fn descent(&self) -> u32 {
+		self.descent
+	}
+
+	/// Get the height of a line.
+	#[inline]
+	pub fn line_height(&self) -> u32 {
+		self.ascent + self.descent
+	}
+
+	/// Get the width of a single line of text, control characters are
+	/// ignored.
+	#[inline]
+	pub fn width(&self, line: &str) -> u32 {
+		line.chars()
+			.filter(|c| !c.is_control())
+			.filter_map(|c| self.glyph(c))
+			.map(|g| g.advance)
+			.sum()
+	}
+
+	/// Get the width and height of the text, lines are split on newlines.
+	#[inline]
+	pub fn measure(&self, text: &str) -> (u32, u32) {
+		let width = text.lines().map(|line| self.width(line)).max().unwrap_or(0);
+		let lines = text.lines().count() as u32;
+
+		(width, lines * self.line_height())
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn default() {
+		let mut font = Font::new(1, 0);
+		font.insert('?', Glyph::new(1, 1, vec![true]).advance(2));
+		font.insert('x', Glyph::new(1, 1, vec![false]));
+
+		assert_eq!(Some(false), font.glyph('x').map(|g| g.get(0, 0)));
+		assert_eq!(Some(true), font.glyph('y').map(|g| g.get(0, 0)));
+		assert_eq!(5, font.width("xyz"));
+
+		font.set_default('x');
+		assert_eq!(3, font.width("xyz"));
+	}
+}
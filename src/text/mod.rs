@@ -0,0 +1,290 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use pixel;
+use view::View;
+use color::Rgba;
+use processing::composite;
+
+mod font;
+pub use self::font::{Font, Glyph};
+
+mod fallback;
+
+pub mod bdf;
+pub mod psf;
+
+/// Horizontal alignment of the lines within a block of text.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub enum Align {
+	Left,
+	Center,
+	Right,
+}
+
+impl Default for Align {
+	#[inline]
+	fn default() -> Self {
+		Align::Left
+	}
+}
+
+/// How text is drawn.
+#[derive(PartialEq, Copy, Clone, Debug)]
+pub struct Style {
+	foreground: Rgba,
+	background: Option<Rgba>,
+	align:      Align,
+	spacing:    u32,
+	mode:       composite::Mode,
+}
+
+impl Style {
+	/// Create a `Style` drawing glyphs with the given color and no
+	/// background.
+	#[inline]
+	pub fn new(foreground: Rgba) -> Self {
+		Style {
+			foreground,
+			background: None,
+			align:      Default::default(),
+			spacing:    0,
+			mode:       Default::default(),
+		}
+	}
+
+	/// Set the color filling the box of every line behind the glyphs.
+	#[inline]
+	pub fn background(mut self, value: Rgba) -> Self {
+		self.background = Some(value);
+		self
+	}
+
+	/// Set the `Align`ment, left by default.
+	#[inline]
+	pub fn align(mut self, value: Align) -> Self {
+		self.align = value;
+		self
+	}
+
+	/// Set the additional space between lines, none by default.
+	#[inline]
+	pub fn spacing(mut self, value: u32) -> Self {
+		self.spacing = value;
+		self
+	}
+
+	/// Set the compositing `Mode`, normal blending over the backdrop by
+	/// default.
+	#[inline]
+	pub fn mode(mut self, value: composite::Mode) -> Self {
+		self.mode = value;
+		self
+	}
+}
+
+/// A line of text placed within a `Layout`.
+#[derive(Eq, PartialEq, Copy, Clone, Debug)]
+pub struct Line<'a> {
+	/// The offset of the left edge.
+	pub x: i64,
+
+	/// The offset of the top edge.
+	pub y: i64,
+
+	/// The width of the line.
+	pub width: u32,
+
+	/// The text in the line.
+	pub text: &'a str,
+}
+
+/// Text split in lines and placed within its bounding box.
+///
+/// # Example
+///
+/// ```
+/// use picto::text::{Font, Layout, Align};
+///
+/// let font   = Font::fallback();
+/// let layout = Layout::new(&font, "a\nbcd", Align::Right, 2);
+///
+/// assert_eq!((18, 18), (layout.width(), layout.height()));
+/// assert_eq!((12, 0), (layout.lines()[0].x, layout.lines()[0].y));
+/// assert_eq!((0, 10), (layout.lines()[1].x, layout.lines()[1].y));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Layout<'a> {
+	font:   &'a Font,
+	lines:  Vec<Line<'a>>,
+	width:  u32,
+	height: u32,
+}
+
+impl<'a> Layout<'a> {
+	/// Lay the text out, splitting on newlines and adding the given space
+	/// between lines.
+	pub fn new(font: &'a Font, text: &'a str, align: Align, spacing: u32) -> Self {
+		let step   = font.line_height() + spacing;
+		let widths = text.lines().map(|line| font.width(line)).collect::<Vec<_>>();
+		let width  = widths.iter().cloned().max().unwrap_or(0);
+		let height = (widths.len() as u32 * step).saturating_sub(spacing);
+
+		let lines = text.lines().zip(widths).enumerate().map(|(i, (line, w))| Line {
+			x: match align {
+				Align::Left   => 0,
+				Align::Center => (width - w) as i64 / 2,
+				Align::Right  => (width - w) as i64,
+			},
+
+			y:     i as i64 * step as i64,
+			width: w,
+			text:  line,
+		}).collect();
+
+		Layout {
+			font,
+			lines,
+			width,
+			height,
+		}
+	}
+
+	/// Get the width of the bounding box.
+	#[inline]
+	pub fn width(&self) -> u32 {
+		self.width
+	}
+
+	/// Get the height of the bounding box.
+	#[inline]
+	pub fn height(&self) -> u32 {
+		self.height
+	}
+
+	/// Get the lines.
+	#[inline]
+	pub fn lines(&self) -> &[Line<'a>] {
+		&self.lines
+	}
+
+	/// Get the glyphs with the offset of the top left corner of their bitmap.
+	pub fn glyphs(&self) -> Vec<(i64, i64, &'a Glyph)> {
+		let mut result = Vec::new();
+		let     font   = self.font;
+
+		for line in &self.lines {
+			let     baseline = line.y + font.ascent() as i64;
+			let mut pen      = line.x;
+
+			for glyph in line.text.chars().filter(|c| !c.is_control()).filter_map(|c| font.glyph(c)) {
+				result.push((pen + glyph.left() as i64, baseline - glyph.top() as i64, glyph));
+				pen += glyph.advance_width() as i64;
+			}
+		}
+
+		result
+	}
+}
+
+/// Draw the text with the top left corner of its bounding box at the given
+/// offset, parts falling outside of the output are clipped.
+///
+/// # Example
+///
+/// ```
+/// use picto::{buffer, text, Anchor};
+/// use picto::color::{Rgb, Rgba};
+///
+/// let mut image = buffer::Rgb::from_pixel(100, 100, &Rgb::new(1.0, 1.0, 1.0));
+/// let     font  = text::Font::fallback();
+///
+/// // Stamp a caption in the bottom right corner.
+/// let (width, height) = font.measure("2017-04-01\n12:00");
+/// let (x, y)          = Anchor::BottomRight.offset(width, height, 100, 100);
+///
+/// text::draw(&mut image, (x, y), "2017-04-01\n12:00", &font,
+///     &text::Style::new(Rgba::new(1.0, 1.0, 1.0, 1.0))
+///         .background(Rgba::new(0.0, 0.0, 0.0, 0.5))
+///         .align(text::Align::Right));
+/// ```
+pub fn draw<'o, O, P, C>(output: O, (x, y): (i64, i64), text: &str, font: &Font, style: &Style)
+	where O: Into<View<'o, P, C>>,
+	      P: From<Rgba> + Into<Rgba>,
+	      P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	let mut output = output.into();
+	let     layout = Layout::new(font, text, style.align, style.spacing);
+	let     width  = output.width() as i64;
+	let     height = output.height() as i64;
+
+	let mut blend = |px: i64, py: i64, color: Rgba| {
+		if px >= 0 && py >= 0 && px < width && py < height {
+			let backdrop = output.get(px as u32, py as u32).into();
+			output.set(px as u32, py as u32, &style.mode.apply(backdrop, color).into());
+		}
+	};
+
+	if let Some(background) = style.background {
+		for line in layout.lines() {
+			for py in 0 .. font.line_height() as i64 {
+				for px in 0 .. line.width as i64 {
+					blend(x + line.x + px, y + line.y + py, background);
+				}
+			}
+		}
+	}
+
+	for (gx, gy, glyph) in layout.glyphs() {
+		for py in 0 .. glyph.height() {
+			for px in 0 .. glyph.width() {
+				if glyph.get(px, py) {
+					blend(x + gx + px as i64, y + gy + py as i64, style.foreground);
+				}
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use buffer::Buffer;
+	use color::Luma;
+
+	#[test]
+	fn draw() {
+		let mut image = Buffer::<Luma, f32, _>::from_pixel(20, 10, &Luma::new(0.0));
+		let     font  = Font::fallback();
+
+		super::draw(&mut image, (1, 1), "I", &font, &Style::new(Rgba::new(1.0, 1.0, 1.0, 1.0)));
+
+		// The vertical stroke and the serifs of the `I`.
+		assert_eq!(Luma::new(1.0), image.get(3, 1));
+		assert_eq!(Luma::new(1.0), image.get(3, 4));
+		assert_eq!(Luma::new(1.0), image.get(2, 7));
+		assert_eq!(Luma::new(0.0), image.get(2, 4));
+		assert_eq!(Luma::new(0.0), image.get(3, 8));
+
+		let mut image = Buffer::<Luma, f32, _>::from_pixel(20, 10, &Luma::new(0.0));
+		super::draw(&mut image, (-2, 0), "ab", &font, &Style::new(Rgba::new(0.0, 0.0, 0.0, 1.0))
+			.background(Rgba::new(1.0, 1.0, 1.0, 0.5)));
+
+		// The background covers the advance of both glyphs, clipped.
+		assert_eq!(Luma::new(0.5), image.get(0, 0));
+		assert_eq!(Luma::new(0.5), image.get(9, 7));
+		assert_eq!(Luma::new(0.0), image.get(10, 0));
+	}
+}
@@ -0,0 +1,231 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+//! Loading of PC Screen Font fonts, versions 1 and 2.
+
+use std::io::Read;
+
+use byteorder::{ReadBytesExt, LittleEndian};
+
+use error::{self, Error};
+use super::{Font, Glyph};
+
+const PSF1_MAGIC: [u8; 2] = [0x36, 0x04];
+const PSF2_MAGIC: [u8; 4] = [0x72, 0xb5, 0x4a, 0x86];
+
+/// Load a PSF font.
+///
+/// Glyphs are keyed by the Unicode table when the font has one, otherwise by
+/// their position. The baseline sits at the bottom of the glyphs.
+pub fn load<R: Read>(mut input: R) -> error::Result<Font> {
+	let mut data = Vec::new();
+	input.read_to_end(&mut data)?;
+
+	if data.starts_with(&PSF1_MAGIC) {
+		version1(&data)
+	}
+	else if data.starts_with(&PSF2_MAGIC) {
+		version2(&data)
+	}
+	else {
+		Err(format("not a PSF font"))
+	}
+}
+
+fn version1(data: &[u8]) -> error::Result<Font> {
+	if data.len() < 4 {
+		return Err(format("truncated header"));
+	}
+
+	let mode   = data[2];
+	let height = data[3] as u32;
+	let count  = if mode & 0x01 != 0 { 512 } else { 256 };
+	let glyphs = glyphs(&data[4 ..], count, 8, height)?;
+	let table  = &data[4 + count * height as usize ..];
+
+	let mut font = Font::new(height, 0);
+
+	if mode & 0x06 != 0 {
+		let mut table = table;
+
+		for glyph in glyphs {
+			loop {
+				let value = table.read_u16::<LittleEndian>()
+					.map_err(|_| format("truncated unicode table"))?;
+
+				match value {
+					// Sequences of code points map to a single glyph, skip them.
+					0xfffe => {
+						while table.read_u16::<LittleEndian>().map_err(|_| format("truncated unicode table"))? != 0xffff { }
+						break;
+					}
+
+					0xffff =>
+						break,
+
+					value => if let Some(ch) = ::std::char::from_u32(value as u32) {
+						font.insert(ch, glyph.clone());
+					}
+				}
+			}
+		}
+	}
+	else {
+		insert(&mut font, glyphs);
+	}
+
+	Ok(font)
+}
+
+fn version2(mut data: &[u8]) -> error::Result<Font> {
+	let whole = data;
+	let mut header = [0u32; 8];
+
+	for value in &mut header {
+		*value = data.read_u32::<LittleEndian>().map_err(|_| format("truncated header"))?;
+	}
+
+	let (size, flags, count, length, height, width) =
+		(header[2] as usize, header[3], header[4] as usize, header[5] as usize, header[6], header[7]);
+
+	if length != (width as usize).div_ceil(8) * height as usize || size > whole.len() {
+		return Err(format("invalid header"));
+	}
+
+	let glyphs = glyphs(&whole[size ..], count, width, height)?;
+	let table  = &whole[size + count * length ..];
+
+	let mut font = Font::new(height, 0);
+
+	if flags & 0x01 != 0 {
+		let mut entries = table.split(|&b| b == 0xff);
+
+		for glyph in glyphs {
+			let entry = entries.next().ok_or_else(|| format("truncated unicode table"))?;
+
+			// Sequences of code points map to a single glyph, skip them.
+			let single = entry.split(|&b| b == 0xfe).next().unwrap_or(&[]);
+			let single = ::std::str::from_utf8(single).map_err(|_| format("invalid unicode table"))?;
+
+			for ch in single.chars() {
+				font.insert(ch, glyph.clone());
+			}
+		}
+	}
+	else {
+		insert(&mut font, glyphs);
+	}
+
+	Ok(font)
+}
+
+/// Read the glyph bitmaps, with rows padded to whole bytes.
+fn glyphs(data: &[u8], count: usize, width: u32, height: u32) -> error::Result<Vec<Glyph>> {
+	let stride = (width as usize).div_ceil(8);
+	let length = stride * height as usize;
+
+	if width == 0 || height == 0 || data.len() < count * length {
+		return Err(format("truncated glyphs"));
+	}
+
+	Ok(data.chunks(length).take(count).map(|bytes| {
+		let mut bitmap = Vec::with_capacity(width as usize * height as usize);
+
+		for row in bytes.chunks(stride) {
+			for x in 0 .. width as usize {
+				bitmap.push(row[x / 8] >> (7 - x % 8) & 1 == 1);
+			}
+		}
+
+		Glyph::new(width, height, bitmap)
+	}).collect())
+}
+
+/// Key the glyphs by their position.
+fn insert(font: &mut Font, glyphs: Vec<Glyph>) {
+	for (i, glyph) in glyphs.into_iter().enumerate() {
+		if let Some(ch) = ::std::char::from_u32(i as u32) {
+			font.insert(ch, glyph);
+		}
+	}
+}
+
+#[inline]
+fn format(message: &str) -> Error {
+	Error::Format(message.into())
+}
+
+#[cfg(test)]
+mod test {
+	fn version2(unicode: bool) -> Vec<u8> {
+		let mut data = vec![0x72, 0xb5, 0x4a, 0x86];
+
+		for &value in &[0, 32, if unicode { 1 } else { 0 }, 2, 2, 1, 9] {
+			data.extend_from_slice(&[value as u8, 0, 0, 0]);
+		}
+
+		// A 9 pixels wide row takes two bytes.
+		data.extend_from_slice(&[0xff, 0x80, 0x80, 0x00]);
+
+		if unicode {
+			data.extend_from_slice("é".as_bytes());
+			data.push(0xff);
+			data.extend_from_slice("xy".as_bytes());
+			data.push(0xfe);
+			data.extend_from_slice("z".as_bytes());
+			data.push(0xff);
+		}
+
+		data
+	}
+
+	#[test]
+	fn psf1() {
+		let mut data = vec![0x36, 0x04, 0x00, 0x02];
+		data.resize(4 + 256 * 2, 0);
+		data[4 + 65 * 2] = 0x81;
+
+		let font = super::load(&data[..]).unwrap();
+		let a    = font.glyph('A').unwrap();
+
+		assert_eq!(256, font.len());
+		assert_eq!((8, 2, 2), (a.width(), a.height(), font.line_height()));
+		assert!(a.get(0, 0));
+		assert!(!a.get(1, 0));
+		assert!(a.get(7, 0));
+	}
+
+	#[test]
+	fn psf2() {
+		let font = super::load(&version2(false)[..]).unwrap();
+		let g    = font.glyph('\u{0}').unwrap();
+
+		assert_eq!(2, font.len());
+		assert_eq!((9, 1), (g.width(), g.height()));
+		assert!((0 .. 9).all(|x| g.get(x, 0)));
+
+		let font = super::load(&version2(true)[..]).unwrap();
+
+		assert_eq!(3, font.len());
+		assert!(font.glyph('é').unwrap().get(8, 0));
+		assert!(!font.glyph('y').unwrap().get(8, 0));
+		assert!(font.glyph('z').is_none());
+	}
+
+	#[test]
+	fn invalid() {
+		assert!(super::load(&b"hello"[..]).is_err());
+		assert!(super::load(&[0x36, 0x04, 0x00, 0x10][..]).is_err());
+	}
+}
@@ -0,0 +1,51 @@
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//                    Version 2, December 2004
+//
+// Copyleft (ↄ) meh. <meh@schizofreni.co> | http://meh.schizofreni.co
+//
+// Everyone is permitted to copy and distribute verbatim or modified
+// copies of this license document, and changing it is allowed as long
+// as the name is changed.
+//
+//            DO WHAT THE FUCK YOU WANT TO PUBLIC LICENSE
+//   TERMS AND CONDITIONS FOR COPYING, DISTRIBUTION AND MODIFICATION
+//
+//  0. You just DO WHAT THE FUCK YOU WANT TO.
+
+use pixel;
+use view;
+use edge::EdgeMode;
+use color::Rgba;
+
+/// Getter handling the edges with an `EdgeMode`.
+pub trait Get<P, C>
+	where P: pixel::Read<C>,
+	      C: pixel::Channel,
+{
+	/// Get the pixel at the coordinates, handling coordinates outside of the
+	/// width and height with the given `EdgeMode`, empty views are transparent
+	/// unless the mode is `Constant`.
+	fn get_edge(self, x: i64, y: i64, mode: EdgeMode) -> Rgba;
+}
+
+impl<'a, P, C, T> Get<P, C> for T
+	where P: pixel::Read<C> + Into<Rgba>,
+	      C: pixel::Channel,
+	      T: Into<view::Read<'a, P, C>>,
+{
+	#[inline]
+	fn get_edge(self, x: i64, y: i64, mode: EdgeMode) -> Rgba {
+		let view = self.into();
+
+		match (mode.index(x, view.width()), mode.index(y, view.height()), mode) {
+			(Some(x), Some(y), _) =>
+				view.get(x, y).into(),
+
+			(_, _, EdgeMode::Constant(color)) =>
+				color,
+
+			_ =>
+				Rgba::new(0.0, 0.0, 0.0, 0.0)
+		}
+	}
+}
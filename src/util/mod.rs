@@ -47,3 +47,31 @@ macro_rules! one {
 
 pub mod clamped;
 pub use self::clamped::{clamp, Get as GetClamped, Set as SetClamped};
+
+pub mod edge;
+pub use self::edge::Get as GetEdge;
+
+/// Bounds for the values processed on multiple threads, they're only
+/// required with the `parallel` feature.
+#[cfg(feature = "parallel")]
+pub trait Shared: Send + Sync { }
+
+#[cfg(feature = "parallel")]
+impl<T: ?Sized + Send + Sync> Shared for T { }
+
+/// Bounds for the values processed on multiple threads, they're only
+/// required with the `parallel` feature.
+#[cfg(not(feature = "parallel"))]
+pub trait Shared { }
+
+#[cfg(not(feature = "parallel"))]
+impl<T: ?Sized> Shared for T { }
+
+/// Marker for the pixel type, with the `parallel` feature it doesn't affect
+/// whether the containing type can be shared between threads.
+#[cfg(feature = "parallel")]
+pub type Marker<P> = ::std::marker::PhantomData<fn() -> P>;
+
+/// Marker for the pixel type.
+#[cfg(not(feature = "parallel"))]
+pub type Marker<P> = ::std::marker::PhantomData<P>;
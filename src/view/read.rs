@@ -17,7 +17,10 @@ use std::marker::PhantomData;
 use pixel;
 use buffer::Buffer;
 use region::{self, Region};
+use anchor::Anchor;
 use iter::pixel::Iter as Pixels;
+use view;
+use util::Marker;
 
 /// A read-only view into a `Buffer`.
 ///
@@ -40,7 +43,7 @@ pub struct Read<'a, P, C>
 	owner:  Region,
 	region: Region,
 
-	pixel:   PhantomData<P>,
+	pixel:   Marker<P>,
 	channel: PhantomData<C>,
 }
 
@@ -152,6 +155,19 @@ impl<'a, P, C> Read<'a, P, C>
 		Read::new(&self.data, self.stride, self.owner, Region { x: region.x + self.region.x, y: region.y + self.region.y, .. region })
 	}
 
+	/// Get the channels of the given row.
+	#[inline]
+	pub(crate) fn row(&self, y: u32) -> &[C] {
+		if y >= self.region.height {
+			panic!("out of bounds");
+		}
+
+		let start = ((self.region.y + y) as usize * self.stride)
+			+ (self.region.x as usize * P::channels());
+
+		&self.data[start .. start + self.region.width as usize * P::channels()]
+	}
+
 	/// Get an immutable `Iterator` over the pixels.
 	pub fn pixels(&self) -> Pixels<P, C> {
 		Pixels::new(self.data, self.stride, self.owner, self.region)
@@ -220,6 +236,88 @@ impl<'a, P, C> Read<'a, P, C>
 	}
 }
 
+impl<'a, P, C> Read<'a, P, C>
+	where P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	/// Crop the given region into a new `Buffer`.
+	///
+	/// # Panics
+	///
+	/// Requires that `x + width <= self.width()` and `y + height <= self.height()`,
+	/// otherwise it will panic.
+	#[inline]
+	pub fn crop(&self, region: region::Builder) -> Buffer<P, C, Vec<C>> {
+		let view       = self.readable(region);
+		let mut result = Buffer::<P, C, Vec<_>>::new(view.width(), view.height());
+
+		for (x, y) in view.region.absolute() {
+			result.set(x, y, &view.get(x, y));
+		}
+
+		result
+	}
+
+	/// Pad each side with the given amount of pixels, set to the given pixel.
+	#[inline]
+	pub fn pad(&self, top: u32, right: u32, bottom: u32, left: u32, fill: &P) -> Buffer<P, C, Vec<C>> {
+		let mut result = Buffer::<P, C, Vec<_>>::from_pixel(
+			self.region.width + left + right, self.region.height + top + bottom, fill);
+
+		place(self, &mut result, left as i64, top as i64);
+
+		result
+	}
+
+	/// Change the canvas to the given dimensions, positioning the current
+	/// content at the given anchor.
+	///
+	/// Any area not covered by the content is set to the given pixel, and any
+	/// content outside of the canvas is cropped.
+	#[inline]
+	pub fn extend_canvas(&self, width: u32, height: u32, anchor: Anchor, fill: &P) -> Buffer<P, C, Vec<C>> {
+		let mut result = Buffer::<P, C, Vec<_>>::from_pixel(width, height, fill);
+		let (x, y)     = anchor.offset(self.region.width, self.region.height, width, height);
+
+		place(self, &mut result, x, y);
+
+		result
+	}
+
+	/// Tile the content across the given output, starting from its top left
+	/// corner.
+	#[inline]
+	pub fn tile_into<'o, O>(&self, output: O)
+		where O: Into<view::Write<'o, P, C>>
+	{
+		let mut output = output.into();
+
+		if self.region.width == 0 || self.region.height == 0 {
+			return;
+		}
+
+		for (x, y) in output.region().absolute() {
+			output.set(x, y, &self.get(x % self.region.width, y % self.region.height));
+		}
+	}
+}
+
+/// Copy the input into the output at the given offset, skipping anything
+/// outside of the output.
+fn place<P, C>(input: &Read<P, C>, output: &mut Buffer<P, C, Vec<C>>, x: i64, y: i64)
+	where P: pixel::Read<C> + pixel::Write<C>,
+	      C: pixel::Channel,
+{
+	for (ix, iy) in input.region.absolute() {
+		let ox = ix as i64 + x;
+		let oy = iy as i64 + y;
+
+		if ox >= 0 && oy >= 0 && ox < output.width() as i64 && oy < output.height() as i64 {
+			output.set(ox as u32, oy as u32, &input.get(ix, iy));
+		}
+	}
+}
+
 impl<'a, P, C> From<&'a Read<'a, P, C>> for Read<'a, P, C>
 	where P: pixel::Read<C>,
 	      C: pixel::Channel,
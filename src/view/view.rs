@@ -13,11 +13,14 @@
 //  0. You just DO WHAT THE FUCK YOU WANT TO.
 
 use std::marker::PhantomData;
+use std::cmp;
 
 use pixel;
 use region::{self, Region};
+use anchor::Anchor;
 use buffer::Buffer;
 use iter::pixel::{Iter as Pixels, IterMut as PixelsMut};
+use util::Marker;
 use super::{Read, Write};
 
 /// A view into a `Buffer`.
@@ -41,7 +44,7 @@ pub struct View<'a, P, C>
 	owner:  Region,
 	region: Region,
 
-	pixel:   PhantomData<P>,
+	pixel:   Marker<P>,
 	channel: PhantomData<C>,
 }
 
@@ -198,6 +201,27 @@ impl<'a, P, C> View<'a, P, C>
 		self.writable(Default::default()).fill(pixel)
 	}
 
+	/// Split the view into one view per row, top to bottom.
+	pub(crate) fn rows(self) -> Vec<View<'a, P, C>> {
+		let mut rows = Vec::with_capacity(self.region.height as usize);
+
+		if self.region.height == 0 {
+			return rows;
+		}
+
+		let mut data = &mut self.data[self.region.y as usize * self.stride ..];
+
+		for _ in 0 .. self.region.height {
+			let length      = cmp::min(self.stride, data.len());
+			let (row, rest) = data.split_at_mut(length);
+
+			data = rest;
+			rows.push(View::new(row, self.stride, self.owner, Region { y: 0, height: 1, .. self.region }));
+		}
+
+		rows
+	}
+
 	/// Get a mutable `Iterator` over the pixels.
 	pub fn pixels(&self) -> Pixels<P, C> {
 		Pixels::new(self.data, self.stride, self.owner, self.region)
@@ -288,6 +312,39 @@ impl<'a, P, C> View<'a, P, C>
 	{
 		Read::<P, C>::new(self.data, self.stride, self.owner, self.region).convert_with(func)
 	}
+
+	/// Crop the given region into a new `Buffer`.
+	///
+	/// # Panics
+	///
+	/// Requires that `x + width <= self.width()` and `y + height <= self.height()`,
+	/// otherwise it will panic.
+	#[inline]
+	pub fn crop(&self, region: region::Builder) -> Buffer<P, C, Vec<C>> {
+		Read::<P, C>::new(self.data, self.stride, self.owner, self.region).crop(region)
+	}
+
+	/// Pad each side with the given amount of pixels, set to the given pixel.
+	#[inline]
+	pub fn pad(&self, top: u32, right: u32, bottom: u32, left: u32, fill: &P) -> Buffer<P, C, Vec<C>> {
+		Read::<P, C>::new(self.data, self.stride, self.owner, self.region).pad(top, right, bottom, left, fill)
+	}
+
+	/// Change the canvas to the given dimensions, positioning the current
+	/// content at the given anchor.
+	#[inline]
+	pub fn extend_canvas(&self, width: u32, height: u32, anchor: Anchor, fill: &P) -> Buffer<P, C, Vec<C>> {
+		Read::<P, C>::new(self.data, self.stride, self.owner, self.region).extend_canvas(width, height, anchor, fill)
+	}
+
+	/// Tile the content across the given output, starting from its top left
+	/// corner.
+	#[inline]
+	pub fn tile_into<'o, O>(&self, output: O)
+		where O: Into<Write<'o, P, C>>
+	{
+		Read::<P, C>::new(self.data, self.stride, self.owner, self.region).tile_into(output)
+	}
 }
 
 impl<'a, P, C> From<&'a mut View<'a, P, C>> for View<'a, P, C>
@@ -13,9 +13,11 @@
 //  0. You just DO WHAT THE FUCK YOU WANT TO.
 
 use std::marker::PhantomData;
+use std::cmp;
 
 use pixel;
 use region::{self, Region};
+use util::Marker;
 
 /// A write-only view into a `Buffer`.
 ///
@@ -35,7 +37,7 @@ pub struct Write<'a, P, C>
 	owner:  Region,
 	region: Region,
 
-	pixel:   PhantomData<P>,
+	pixel:   Marker<P>,
 	channel: PhantomData<C>,
 }
 
@@ -162,6 +164,27 @@ impl<'a, P, C> Write<'a, P, C>
 			self.set(x, y, pixel);
 		}
 	}
+
+	/// Split the view into one view per row, top to bottom.
+	pub(crate) fn rows(self) -> Vec<Write<'a, P, C>> {
+		let mut rows = Vec::with_capacity(self.region.height as usize);
+
+		if self.region.height == 0 {
+			return rows;
+		}
+
+		let mut data = &mut self.data[self.region.y as usize * self.stride ..];
+
+		for _ in 0 .. self.region.height {
+			let length      = cmp::min(self.stride, data.len());
+			let (row, rest) = data.split_at_mut(length);
+
+			data = rest;
+			rows.push(Write::new(row, self.stride, self.owner, Region { y: 0, height: 1, .. self.region }));
+		}
+
+		rows
+	}
 }
 
 impl<'a, P, C> From<&'a mut Write<'a, P, C>> for Write<'a, P, C>